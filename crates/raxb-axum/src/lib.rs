@@ -19,7 +19,7 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use hyper::{HeaderMap, StatusCode};
-use raxb::de::XmlDeserialize;
+use raxb::de::{DeserializeLimits, DeserializeOptions, XmlDeserialize};
 use thiserror::Error;
 
 #[derive(Debug, Clone, Default)]
@@ -73,8 +73,24 @@ where
     /// Construct a `RaxbXmlSource<T>` from a byte slice. Most users should prefer to use the `FromRequest` impl
     /// but special cases may require first extracting a `Request` into `Bytes` then optionally
     /// constructing a `RaxbXmlSource<T>`.
+    ///
+    /// Request bodies are untrusted, so [`DeserializeLimits::safe`] is applied.
     pub fn from_bytes(bytes: Bytes) -> Result<Self, RaxbXmlRejection> {
-        raxb::de::from_reader(&*bytes)
+        Self::from_bytes_with_options(
+            bytes,
+            &DeserializeOptions {
+                limits: DeserializeLimits::safe(),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Like [`RaxbXmlSource::from_bytes`], with custom deserialization options.
+    pub fn from_bytes_with_options(
+        bytes: Bytes,
+        options: &DeserializeOptions,
+    ) -> Result<Self, RaxbXmlRejection> {
+        raxb::de::from_slice_with_options(&bytes, options)
             .map(|xml| Self(xml, bytes))
             .map_err(From::from)
     }
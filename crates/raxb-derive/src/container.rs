@@ -68,12 +68,86 @@ pub enum NsValue {
     ExprPath(syn::ExprPath),
 }
 
+/// Naming convention deriving XML names from Rust identifiers, see `#[raxb(rename_all = "…")]`.
+#[derive(Debug, Clone, Copy)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn from_lit(lit: &syn::LitStr) -> syn::Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+            _ => return Err(syn::Error::new_spanned(
+                lit,
+                "invalid rename_all, should be `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case` or `SCREAMING-KEBAB-CASE`",
+            )),
+        })
+    }
+
+    pub fn apply(&self, ident: &syn::Ident) -> String {
+        use heck::{
+            ToKebabCase, ToLowerCamelCase, ToShoutyKebabCase, ToShoutySnakeCase, ToSnakeCase,
+            ToUpperCamelCase,
+        };
+        let ident = ident.to_string();
+        let ident = ident.trim_start_matches("r#");
+        match self {
+            Self::Lower => ident.to_lowercase(),
+            Self::Upper => ident.to_uppercase(),
+            Self::Pascal => ident.to_upper_camel_case(),
+            Self::Camel => ident.to_lower_camel_case(),
+            Self::Snake => ident.to_snake_case(),
+            Self::ScreamingSnake => ident.to_shouty_snake_case(),
+            Self::Kebab => ident.to_kebab_case(),
+            Self::ScreamingKebab => ident.to_shouty_kebab_case(),
+        }
+    }
+
+    fn apply_byte_str(&self, ident: &syn::Ident) -> syn::LitByteStr {
+        syn::LitByteStr::new(self.apply(ident).as_bytes(), ident.span())
+    }
+}
+
+/// How the variant of an enum is selected.
+pub enum EnumTag {
+    /// By the resolved `xsi:type` attribute of the element.
+    XsiType,
+}
+
 pub struct Container<'a> {
     pub struct_fields: Vec<StructField<'a>>, // Struct fields
     pub enum_variants: Vec<EnumVariant<'a>>,
     pub original: &'a syn::DeriveInput,
     pub root: Option<syn::LitByteStr>,
     pub tns: Option<(syn::LitByteStr, NsValue)>,
+    /// Former namespaces accepted in place of the `tns` on deserialization.
+    pub tns_aliases: Vec<NsValue>,
+    pub tag: Option<EnumTag>,
+    /// Missing fields are taken from the `Default` impl of the struct.
+    pub default: bool,
+    /// Function checking the whole struct, `fn(&Self) -> Result<(), E>`.
+    pub validate: Option<syn::ExprPath>,
+    /// Enum of unit variants mapped to text values, like a `xs:enumeration` simple type.
+    pub simple: bool,
+    /// Where predicates replacing the inferred bounds of the type parameters.
+    pub bound: Option<Vec<syn::WherePredicate>>,
+    /// Newtype struct delegating to its inner type.
+    pub transparent: bool,
 }
 
 impl<'a> Container<'a> {
@@ -81,86 +155,352 @@ impl<'a> Container<'a> {
         !self.enum_variants.is_empty()
     }
 
-    pub fn validate(&self) {
-        if self.root.is_some() && self.is_enum() {
-            panic!("for clarity, enum should not have the root attribute. please use a struct to wrap the enum and set its type to untag")
+    pub fn validate(&self) -> syn::Result<()> {
+        let item = self.original;
+        if self.default && self.is_enum() {
+            return Err(syn::Error::new_spanned(
+                item,
+                "the default attribute is only supported on structs",
+            ));
+        }
+        if self.simple && !self.is_enum() {
+            return Err(syn::Error::new_spanned(
+                item,
+                "the simple attribute is only supported on enums",
+            ));
+        }
+        if self.simple && (self.tag.is_some() || self.root.is_some()) {
+            return Err(syn::Error::new_spanned(
+                item,
+                "simple enums do not support the tag and root attributes",
+            ));
+        }
+        if let Some(v) = self.enum_variants.iter().filter(|v| v.other).nth(1) {
+            return Err(syn::Error::new_spanned(
+                v.ident,
+                "only one variant can have the other attribute",
+            ));
+        }
+        if self.tag.is_some() {
+            // the xsi:type value can only use a prefix the enum declares itself
+            let tns_prefix = self.tns.as_ref().map(|(prefix, _)| prefix.value());
+            if let Some(ns) = self
+                .enum_variants
+                .iter()
+                .filter_map(|v| v.ns.as_ref())
+                .find(|ns| Some(ns.value()) != tns_prefix)
+            {
+                return Err(syn::Error::new_spanned(
+                    ns,
+                    "the `ns` of a variant of an enum with the tag attribute should be the `tns` prefix, the namespace of other prefixes is unknown",
+                ));
+            }
+        }
+        if let Some(v) = self.enum_variants.iter().find(|v| v.other) {
+            if self.tag.is_some() {
+                return Err(syn::Error::new_spanned(
+                    v.ident,
+                    "the other attribute is not supported on enums with the tag attribute",
+                ));
+            }
+        }
+        if self.validate.is_some() && self.is_enum() {
+            return Err(syn::Error::new_spanned(
+                item,
+                "the validate attribute is only supported on structs",
+            ));
+        }
+        if self.tag.is_some() && !self.is_enum() {
+            return Err(syn::Error::new_spanned(
+                item,
+                "the tag attribute is only supported on enums",
+            ));
+        }
+        if self.root.is_some() && self.is_enum() && self.tag.is_none() {
+            return Err(syn::Error::new_spanned(item, "for clarity, enum should not have the root attribute. please use a struct to wrap the enum and set its type to untag"));
+        }
+        if let syn::Data::Struct(ds) = &item.data {
+            match &ds.fields {
+                syn::Fields::Unnamed(fields) if fields.unnamed.len() > 1 => {
+                    return Err(syn::Error::new_spanned(
+                        fields,
+                        "tuple structs with more than one field are not supported, use named fields",
+                    ));
+                }
+                syn::Fields::Unnamed(_) if !self.transparent => {
+                    return Err(syn::Error::new_spanned(
+                        item,
+                        "newtype structs need #[raxb(transparent)] to be (de)serialized like their inner type",
+                    ));
+                }
+                syn::Fields::Unnamed(fields) => {
+                    if let Some(attr) = fields.unnamed[0]
+                        .attrs
+                        .iter()
+                        .find(|attr| attr.path() == RAXB || attr.path() == XML)
+                    {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            "the field of a transparent struct does not support attributes",
+                        ));
+                    }
+                    if self.root.is_some()
+                        || self.tns.is_some()
+                        || self.default
+                        || self.validate.is_some()
+                    {
+                        return Err(syn::Error::new_spanned(
+                            item,
+                            "transparent structs do not support the root, tns, default and validate attributes",
+                        ));
+                    }
+                }
+                _ if self.transparent => {
+                    return Err(syn::Error::new_spanned(
+                        item,
+                        "the transparent attribute is only supported on newtype structs, e.g. `struct OrderId(String);`",
+                    ));
+                }
+                _ => {}
+            }
+        } else if self.transparent {
+            return Err(syn::Error::new_spanned(
+                item,
+                "the transparent attribute is only supported on newtype structs, e.g. `struct OrderId(String);`",
+            ));
+        }
+        let mut text = Option::<&StructField>::None;
+        let mut keyed_by_name = Option::<&StructField>::None;
+        let mut tag = Option::<&StructField>::None;
+        let mut any_attr = Option::<&StructField>::None;
+        for f in self.struct_fields.iter() {
+            if matches!(f.key, Some(MapKey::Name)) {
+                if keyed_by_name.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        f.original,
+                        "only one field can be keyed by the element name",
+                    ));
+                }
+                keyed_by_name = Some(f);
+                continue;
+            }
+            match f.ty {
+                EleType::Text => {
+                    if text.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            f.original,
+                            "only one field can have the text type",
+                        ));
+                    }
+                    text = Some(f);
+                }
+                EleType::Tag => {
+                    if tag.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            f.original,
+                            "only one field can have the tag type",
+                        ));
+                    }
+                    if !matches!(f.generic, Generic::None) {
+                        return Err(syn::Error::new_spanned(
+                            &f.original.ty,
+                            "the tag field should be a String, QName or another FromStr type",
+                        ));
+                    }
+                    tag = Some(f);
+                }
+                EleType::AnyAttr => {
+                    if any_attr.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            f.original,
+                            "only one field can have the any_attr type",
+                        ));
+                    }
+                    any_attr = Some(f);
+                }
+                EleType::Attr | EleType::Child | EleType::SelfClosedChild if f.name.is_none() => {
+                    return Err(syn::Error::new_spanned(
+                        f.original,
+                        "missing `name`, e.g. #[raxb(name = b\"element\", ty = \"child\")]",
+                    ));
+                }
+                EleType::Attr if matches!(f.generic, Generic::Vec(_)) => {
+                    return Err(syn::Error::new_spanned(
+                        &f.original.ty,
+                        "Vec<T> cannot be used for attributes, use Option<T> instead",
+                    ));
+                }
+                _ => {}
+            }
+        }
+        if let Some(text) = text {
+            if let Some(f) = self.struct_fields.iter().find(|f| {
+                matches!(
+                    f.ty,
+                    EleType::Child | EleType::SelfClosedChild | EleType::Untag | EleType::Any
+                )
+            }) {
+                return Err(syn::Error::new_spanned(
+                    f.original,
+                    format!(
+                        "a struct with the text field `{}` cannot have children",
+                        text.original
+                            .ident
+                            .as_ref()
+                            .map(|ident| ident.to_string())
+                            .unwrap_or_else(|| "0".to_string())
+                    ),
+                ));
+            }
         }
+        Ok(())
     }
 
-    pub fn from_ast(item: &'a syn::DeriveInput, _derive: Derive) -> Container<'a> {
+    pub fn from_ast(item: &'a syn::DeriveInput, derive: Derive) -> syn::Result<Container<'a>> {
         let mut root = Option::<syn::LitByteStr>::None;
         let mut tns = Option::<(syn::LitByteStr, NsValue)>::None;
-        for meta_item in item
-            .attrs
-            .iter()
-            .flat_map(get_xmlserde_meta_items)
-            .flatten()
-        {
+        let mut tns_aliases = Vec::<NsValue>::new();
+        let mut tag = Option::<EnumTag>::None;
+        let mut default = false;
+        let mut validate = Option::<syn::ExprPath>::None;
+        let mut simple = false;
+        let mut rename_all = Option::<RenameRule>::None;
+        let mut bound = Option::<Vec<syn::WherePredicate>>::None;
+        let mut transparent = false;
+        for meta_item in get_xmlserde_meta_items(&item.attrs)? {
             match meta_item {
                 NameValue(m) if m.path == ROOT => {
-                    let Some(NsValue::LitByte(s)) = get_lit_byte_str(m.value) else {
-                        panic!("parse root failed");
-                    };
-                    root = Some(s);
+                    root = Some(get_byte_str(&m.value, "root")?);
                 }
                 Meta::List(l) if l.path == TNS => {
                     // Parse *any* expressions, then validate each.
-                    let exprs = l
-                        .parse_args_with(Punctuated::<syn::Expr, Comma>::parse_terminated)
-                        .unwrap();
-
-                    let mut iter = exprs.into_iter();
-                    let first = iter.next().expect("tns needs 2 args");
-                    let second = iter.next().expect("tns needs 2 args");
-                    if iter.next().is_some() {
-                        panic!("tns should have exactly 2 arguments");
+                    let exprs =
+                        l.parse_args_with(Punctuated::<syn::Expr, Comma>::parse_terminated)?;
+                    if exprs.len() < 2 {
+                        return Err(syn::Error::new_spanned(
+                            &l,
+                            "tns should have at least 2 arguments, e.g. tns(b\"prefix\", b\"namespace\") or tns(b\"prefix\", b\"namespace\", b\"former namespace\")",
+                        ));
                     }
+                    let mut iter = exprs.into_iter();
+                    let (first, second) = (iter.next().unwrap(), iter.next().unwrap());
 
                     // ---- first argument must be a byte‑string literal (prefix) ----
-                    let Some(NsValue::LitByte(prefix)) = get_lit_byte_str(first) else {
-                        panic!("first tns argument must be a byte string literal")
-                    };
+                    let prefix = get_byte_str(&first, "the first tns argument")?;
 
                     // ---- second argument can be literal OR identifier ----
-                    let Some(ns_val) = get_lit_byte_str(second) else {
-                        panic!("second tns argument must be a byte string literal or a const identifier");
+                    let Some(ns_val) = get_lit_byte_str(second.clone()) else {
+                        return Err(syn::Error::new_spanned(
+                            second,
+                            "the second tns argument should be a byte string literal or a const identifier",
+                        ));
                     };
 
+                    // ---- further arguments are aliases of the namespace ----
+                    for alias in iter {
+                        let Some(alias_val) = get_lit_byte_str(alias.clone()) else {
+                            return Err(syn::Error::new_spanned(
+                                alias,
+                                "tns aliases should be byte string literals or const identifiers",
+                            ));
+                        };
+                        tns_aliases.push(alias_val);
+                    }
+
                     tns = Some((prefix, ns_val));
                 }
-                _ => panic!("unexpected attribute"),
+                NameValue(m) if m.path == TAG => {
+                    let s = get_lit_str(&m.value, "tag")?;
+                    tag = Some(match s.value().as_str() {
+                        "xsi:type" => EnumTag::XsiType,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                s,
+                                "unsupported tag, expected \"xsi:type\"",
+                            ))
+                        }
+                    });
+                }
+                Path(p) if p == DEFAULT => {
+                    default = true;
+                }
+                NameValue(m) if m.path == VALIDATE => {
+                    validate = Some(get_lit_path(&m.value, "validate")?);
+                }
+                Path(p) if p == SIMPLE => {
+                    simple = true;
+                }
+                Path(p) if p == TRANSPARENT => {
+                    transparent = true;
+                }
+                NameValue(m) if m.path == BOUND => {
+                    let predicates = get_lit_str(&m.value, "bound")?
+                        .parse_with(Punctuated::<syn::WherePredicate, Comma>::parse_terminated)?;
+                    bound = Some(predicates.into_iter().collect());
+                }
+                NameValue(m) if m.path == RENAME_ALL => {
+                    rename_all = Some(RenameRule::from_lit(get_lit_str(&m.value, "rename_all")?)?);
+                }
+                meta => return Err(unexpected_attribute(&meta)),
             }
         }
         match &item.data {
             syn::Data::Struct(ds) => {
-                let fields = ds
-                    .fields
-                    .iter()
-                    .filter_map(StructField::from_ast)
-                    .collect::<Vec<_>>();
-                Container {
+                let mut fields = Vec::new();
+                for f in ds.fields.iter() {
+                    match StructField::from_ast(f, &derive, rename_all)? {
+                        Some(field) => fields.push(field),
+                        None => {
+                            return Err(syn::Error::new_spanned(
+                                f,
+                                format!(
+                                    "field `{}` has no `ty`, use #[raxb(skip)] for fields that are not part of the xml",
+                                    f.ident.as_ref().unwrap()
+                                ),
+                            ));
+                        }
+                    }
+                }
+                Ok(Container {
                     struct_fields: fields,
                     enum_variants: vec![],
                     original: item,
                     root,
                     tns,
-                }
+                    tns_aliases,
+                    tag,
+                    default,
+                    validate,
+                    simple,
+                    bound,
+                    transparent,
+                })
             }
             syn::Data::Enum(e) => {
                 let variants = e
                     .variants
                     .iter()
-                    .map(EnumVariant::from_ast)
-                    .collect::<Vec<_>>();
-                Container {
+                    .map(|v| EnumVariant::from_ast(v, simple, rename_all))
+                    .collect::<syn::Result<Vec<_>>>()?;
+                Ok(Container {
                     struct_fields: vec![],
                     enum_variants: variants,
                     original: item,
                     root,
                     tns,
-                }
+                    tns_aliases,
+                    tag,
+                    default,
+                    validate,
+                    simple,
+                    bound,
+                    transparent,
+                })
             }
-            syn::Data::Union(_) => panic!("Only support struct and enum type, union is found"),
+            syn::Data::Union(u) => Err(syn::Error::new_spanned(
+                u.union_token,
+                "only structs and enums are supported, found a union",
+            )),
         }
     }
 }
@@ -173,6 +513,7 @@ pub struct FieldsSummary<'a> {
     pub untags: Vec<StructField<'a>>,
     pub any: Option<StructField<'a>>,
     pub xmlns: Option<StructField<'a>>,
+    pub any_attr: Option<StructField<'a>>,
 }
 
 impl<'a> FieldsSummary<'a> {
@@ -185,6 +526,7 @@ impl<'a> FieldsSummary<'a> {
             untags: vec![],
             any: None,
             xmlns: None,
+            any_attr: None,
         };
         fields.into_iter().for_each(|f| match f.ty {
             EleType::Attr => result.attrs.push(f),
@@ -194,90 +536,420 @@ impl<'a> FieldsSummary<'a> {
             EleType::Untag => result.untags.push(f),
             EleType::Any => result.any = Some(f),
             EleType::XmlNs => result.xmlns = Some(f),
+            EleType::AnyAttr => result.any_attr = Some(f),
+            // read from the name of the element, see `de::ty_struct`
+            EleType::Tag | EleType::Skip => {}
         });
         result
     }
 }
 
+/// Whitespace handling of a field value, see `raxb::de::WhiteSpace`.
+#[derive(Debug, Clone, Copy)]
+pub enum WhiteSpace {
+    Preserve,
+    Replace,
+    Collapse,
+}
+
 pub struct StructField<'a> {
     pub ty: EleType,
     pub name: Option<syn::LitByteStr>,
+    /// Former names also accepted on deserialization, `alias = b"…"`.
+    pub aliases: Vec<syn::LitByteStr>,
     pub original: &'a syn::Field,
     pub generic: Generic<'a>,
     pub ns: Option<syn::LitByteStr>,
     pub value: Option<syn::LitStr>,
     pub default: bool,
+    /// Function returning the value of a missing field, `fn() -> T`.
+    pub default_with: Option<syn::ExprPath>,
+    pub whitespace: Option<WhiteSpace>,
+    /// Function converting the text into the field value, `fn(&str) -> Result<T, E>`.
+    pub deserialize_with: Option<syn::ExprPath>,
+    /// Function converting the field value into text, `fn(&T) -> Result<String, E>`.
+    pub serialize_with: Option<syn::ExprPath>,
+    /// Predicate skipping the field on serialization, `fn(&T) -> bool`.
+    pub skip_serializing_if: Option<syn::ExprPath>,
+    pub facets: Facets,
+    pub wrapper: Option<Wrapper>,
+    pub key: Option<MapKey>,
+}
+
+/// Key of the items of a map field.
+pub enum MapKey {
+    /// The value of an attribute of the item element, `key = b"name"`.
+    Attr(syn::LitByteStr),
+    /// The local name of the item element, `key_by_name`.
+    Name,
+}
+
+/// Element wrapping the items of a repeated child, e.g. `<Lines><Line/><Line/></Lines>`.
+pub struct Wrapper {
+    pub name: syn::LitByteStr,
+    pub ns: Option<syn::LitByteStr>,
+    /// Omits the wrapper on serialization if there are no items.
+    pub skip_empty: bool,
+}
+
+/// Facets checked after deserialization and before serialization, see `raxb::facet`.
+#[derive(Default)]
+pub struct Facets {
+    pub pattern: Option<syn::LitStr>,
+    pub min_length: Option<syn::Expr>,
+    pub max_length: Option<syn::Expr>,
+    pub min: Option<syn::Expr>,
+    pub max: Option<syn::Expr>,
+    pub enumeration: Option<Vec<syn::LitStr>>,
+    pub min_occurs: Option<syn::Expr>,
+    pub max_occurs: Option<syn::Expr>,
+}
+
+impl Facets {
+    pub fn is_empty(&self) -> bool {
+        self.pattern.is_none()
+            && self.min_length.is_none()
+            && self.max_length.is_none()
+            && self.min.is_none()
+            && self.max.is_none()
+            && self.enumeration.is_none()
+            && self.min_occurs.is_none()
+            && self.max_occurs.is_none()
+    }
 }
 
 impl<'a> StructField<'a> {
-    pub fn from_ast(f: &'a syn::Field) -> Option<Self> {
+    pub fn from_ast(
+        f: &'a syn::Field,
+        derive: &Derive,
+        rename_all: Option<RenameRule>,
+    ) -> syn::Result<Option<Self>> {
         let mut name = Option::<syn::LitByteStr>::None;
+        let mut aliases = Vec::<syn::LitByteStr>::new();
         let mut ns = Option::<syn::LitByteStr>::None;
         let mut value = Option::<syn::LitStr>::None;
         let mut ty = Option::<EleType>::None;
         let mut default = false;
+        let mut default_with = Option::<syn::ExprPath>::None;
+        let mut skip_serializing_if = Option::<syn::ExprPath>::None;
+        let mut skip_serializing = false;
+        let mut skip_deserializing = false;
+        let mut facets = Facets::default();
+        let mut whitespace = Option::<WhiteSpace>::None;
+        let mut whitespace_lit = Option::<syn::LitStr>::None;
+        let mut deserialize_with = Option::<syn::ExprPath>::None;
+        let mut serialize_with = Option::<syn::ExprPath>::None;
+        let mut key = Option::<MapKey>::None;
+        let mut wrapper = Option::<syn::LitByteStr>::None;
+        let mut wrapper_ns = Option::<syn::LitByteStr>::None;
+        let mut skip_empty_wrapper = Option::<syn::Path>::None;
         let generic = get_generics(&f.ty);
-        for meta_item in f.attrs.iter().flat_map(get_xmlserde_meta_items).flatten() {
+        let mut ty_span = Option::<proc_macro2::Span>::None;
+        for meta_item in get_xmlserde_meta_items(&f.attrs)? {
             match meta_item {
                 NameValue(m) if m.path == NAME => {
-                    if let Some(NsValue::LitByte(s)) = get_lit_byte_str(m.value) {
-                        name = Some(s);
-                    }
+                    name = Some(get_byte_str(&m.value, "name")?);
+                }
+                NameValue(m) if m.path == ALIAS => {
+                    aliases.push(get_byte_str(&m.value, "alias")?);
                 }
                 NameValue(m) if m.path == NS => {
-                    if let Some(NsValue::LitByte(s)) = get_lit_byte_str(m.value) {
-                        ns = Some(s);
-                    }
+                    ns = Some(get_byte_str(&m.value, "ns")?);
+                }
+                NameValue(m) if m.path == KEY => {
+                    key = Some(MapKey::Attr(get_byte_str(&m.value, "key")?));
+                }
+                Path(p) if p == KEY_BY_NAME => {
+                    key = Some(MapKey::Name);
+                }
+                NameValue(m) if m.path == WRAPPER => {
+                    wrapper = Some(get_byte_str(&m.value, "wrapper")?);
+                }
+                NameValue(m) if m.path == WRAPPER_NS => {
+                    wrapper_ns = Some(get_byte_str(&m.value, "wrapper_ns")?);
+                }
+                Path(p) if p == SKIP_EMPTY_WRAPPER => {
+                    skip_empty_wrapper = Some(p);
                 }
                 NameValue(m) if m.path == VALUE => {
-                    if let Ok(s) = get_lit_str(&m.value) {
-                        value = Some(s.clone());
-                    }
+                    value = Some(get_lit_str(&m.value, "value")?.clone());
                 }
                 NameValue(m) if m.path == TYPE => {
-                    if let Ok(s) = get_lit_str(&m.value) {
-                        let t = match s.value().as_str() {
-                            "attr" => EleType::Attr,
-                            "child" => EleType::Child,
-                            "text" => EleType::Text,
-                            "sfc" => EleType::SelfClosedChild,
-                            "untag" => EleType::Untag,
-                            "any" => EleType::Any,
-                            "xmlns" => EleType::XmlNs,
-                            _ => panic!("invalid type"),
-                        };
-                        ty = Some(t);
-                    }
+                    let s = get_lit_str(&m.value, "ty")?;
+                    let t = match s.value().as_str() {
+                        "attr" => EleType::Attr,
+                        "child" => EleType::Child,
+                        "text" => EleType::Text,
+                        "sfc" => EleType::SelfClosedChild,
+                        "untag" => EleType::Untag,
+                        "any" => EleType::Any,
+                        "xmlns" => EleType::XmlNs,
+                        "tag" => EleType::Tag,
+                        "any_attr" => EleType::AnyAttr,
+                        _ => return Err(syn::Error::new_spanned(
+                            s,
+                            "invalid type, should be `attr`, `child`, `text`, `sfc`, `untag`, `any`, `xmlns`, `tag` or `any_attr`",
+                        )),
+                    };
+                    ty = Some(t);
+                    ty_span = Some(s.span());
                 }
                 Path(p) if p == DEFAULT => {
                     default = true;
                 }
-                _ => panic!("unexpected"),
+                NameValue(m) if m.path == DEFAULT => {
+                    default_with = Some(get_lit_path(&m.value, "default")?);
+                }
+                Path(p) if p == SKIP => {
+                    skip_serializing = true;
+                    skip_deserializing = true;
+                }
+                Path(p) if p == SKIP_SERIALIZING => {
+                    skip_serializing = true;
+                }
+                Path(p) if p == SKIP_DESERIALIZING => {
+                    skip_deserializing = true;
+                }
+                NameValue(m) if m.path == PATTERN => {
+                    let pattern = get_lit_str(&m.value, "pattern")?;
+                    check_pattern(pattern)?;
+                    facets.pattern = Some(pattern.clone());
+                }
+                NameValue(m) if m.path == MIN_LENGTH => {
+                    facets.min_length = Some(m.value);
+                }
+                NameValue(m) if m.path == MAX_LENGTH => {
+                    facets.max_length = Some(m.value);
+                }
+                NameValue(m) if m.path == MIN => {
+                    facets.min = Some(m.value);
+                }
+                NameValue(m) if m.path == MAX => {
+                    facets.max = Some(m.value);
+                }
+                NameValue(m) if m.path == MIN_OCCURS || m.path == MAX_OCCURS => {
+                    if matches!(generic, Generic::None) {
+                        return Err(syn::Error::new_spanned(
+                            &m.path,
+                            "min_occurs and max_occurs are only supported on collection and Option fields",
+                        ));
+                    }
+                    if m.path == MIN_OCCURS {
+                        facets.min_occurs = Some(m.value);
+                    } else {
+                        facets.max_occurs = Some(m.value);
+                    }
+                }
+                Meta::List(l) if l.path == ENUMERATION => {
+                    let values = l
+                        .parse_args_with(Punctuated::<syn::LitStr, Comma>::parse_terminated)
+                        .map_err(|err| {
+                            syn::Error::new(
+                                err.span(),
+                                "enumeration should be a list of string literals, e.g. enumeration(\"a\", \"b\")",
+                            )
+                        })?;
+                    facets.enumeration = Some(values.into_iter().collect());
+                }
+                NameValue(m) if m.path == SKIP_SERIALIZING_IF => {
+                    skip_serializing_if = Some(get_lit_path(&m.value, "skip_serializing_if")?);
+                }
+                NameValue(m) if m.path == WHITESPACE => {
+                    let s = get_lit_str(&m.value, "whitespace")?;
+                    whitespace =
+                        Some(match s.value().as_str() {
+                            "preserve" => WhiteSpace::Preserve,
+                            "replace" => WhiteSpace::Replace,
+                            "collapse" => WhiteSpace::Collapse,
+                            _ => return Err(syn::Error::new_spanned(
+                                s,
+                                "invalid whitespace, should be `preserve`, `replace` or `collapse`",
+                            )),
+                        });
+                    whitespace_lit = Some(s.clone());
+                }
+                NameValue(m) if m.path == WITH => {
+                    let module = get_lit_path(&m.value, "with")?;
+                    deserialize_with = Some(syn::parse_quote!(#module::deserialize));
+                    serialize_with = Some(syn::parse_quote!(#module::serialize));
+                }
+                NameValue(m) if m.path == DESERIALIZE_WITH => {
+                    deserialize_with = Some(get_lit_path(&m.value, "deserialize_with")?);
+                }
+                NameValue(m) if m.path == SERIALIZE_WITH => {
+                    serialize_with = Some(get_lit_path(&m.value, "serialize_with")?);
+                }
+                meta => return Err(unexpected_attribute(&meta)),
             }
         }
-        if let Some(ty) = ty {
+        if (deserialize_with.is_some() || serialize_with.is_some())
+            && !matches!(
+                ty,
+                Some(EleType::Attr | EleType::Child | EleType::Text) | None
+            )
+        {
+            return Err(syn::Error::new(
+                ty_span.unwrap(),
+                "with, deserialize_with and serialize_with are only supported on attr, text and child fields",
+            ));
+        }
+        if let Some(lit) = whitespace_lit {
+            let value_ty = match generic {
+                Generic::Vec(t) | Generic::Opt(t) => t,
+                _ => &f.ty,
+            };
+            let is_string = matches!(value_ty, syn::Type::Path(p)
+                if p.path.get_ident().is_some_and(|i| i == "String"));
+            if !matches!(
+                ty,
+                Some(EleType::Attr | EleType::Child | EleType::Text) | None
+            ) || (!is_string && deserialize_with.is_none())
+            {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "whitespace is only supported on `String` attr, text and child fields or fields with `deserialize_with`",
+                ));
+            }
+        }
+        if matches!(generic, Generic::Map(..)) && !matches!(ty, None | Some(EleType::AnyAttr)) {
+            if !matches!(ty, Some(EleType::Child)) {
+                return Err(syn::Error::new(
+                    ty_span.unwrap(),
+                    "maps are only supported on child fields",
+                ));
+            }
+            match key {
+                Some(MapKey::Attr(_)) if name.is_none() => {
+                    return Err(syn::Error::new_spanned(
+                        f,
+                        "a map keyed by an attribute needs the `name` of its elements",
+                    ));
+                }
+                Some(MapKey::Name) if name.is_some() => {
+                    return Err(syn::Error::new_spanned(
+                        f,
+                        "a map keyed by the element name cannot have a `name`",
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    return Err(syn::Error::new_spanned(
+                        f,
+                        "missing map key, e.g. #[raxb(name = b\"param\", key = b\"name\", ty = \"child\")] or #[raxb(key_by_name, ty = \"child\")]",
+                    ));
+                }
+            }
+        } else if key.is_some() {
+            return Err(syn::Error::new_spanned(
+                &f.ty,
+                "key and key_by_name are only supported on map fields, e.g. `BTreeMap<String, T>`",
+            ));
+        }
+        let wrapper = match wrapper {
+            Some(name) => {
+                if !matches!(generic, Generic::Vec(_))
+                    || !matches!(ty, Some(EleType::Child | EleType::SelfClosedChild))
+                {
+                    return Err(syn::Error::new_spanned(
+                        &name,
+                        "wrapper is only supported on repeated child and sfc fields, e.g. `Vec<T>`",
+                    ));
+                }
+                Some(Wrapper {
+                    name,
+                    ns: wrapper_ns,
+                    skip_empty: skip_empty_wrapper.is_some(),
+                })
+            }
+            None => {
+                if let Some(ns) = wrapper_ns {
+                    return Err(syn::Error::new_spanned(ns, "wrapper_ns needs a `wrapper`"));
+                }
+                if let Some(p) = skip_empty_wrapper {
+                    return Err(syn::Error::new_spanned(
+                        p,
+                        "skip_empty_wrapper needs a `wrapper`",
+                    ));
+                }
+                None
+            }
+        };
+        let skipped = match derive {
+            Derive::Serialize => skip_serializing,
+            Derive::Deserialize => skip_deserializing,
+        };
+        if let Some((rule, ident)) = rename_all.zip(f.ident.as_ref()) {
+            if name.is_none()
+                && matches!(
+                    ty,
+                    Some(EleType::Attr | EleType::Child | EleType::SelfClosedChild)
+                )
+            {
+                name = Some(rule.apply_byte_str(ident));
+            }
+        }
+        if let Some(alias) = aliases.first() {
+            if name.is_none()
+                || !matches!(
+                    ty,
+                    Some(EleType::Attr | EleType::Child | EleType::SelfClosedChild)
+                )
+            {
+                return Err(syn::Error::new_spanned(
+                    alias,
+                    "alias is only supported on attr, child and sfc fields with a `name`",
+                ));
+            }
+        }
+        if skipped {
+            ty = Some(EleType::Skip);
+        } else if (skip_serializing || skip_deserializing) && ty.is_none() {
+            return Err(syn::Error::new_spanned(
+                f,
+                "field with skip_serializing or skip_deserializing needs a `ty`",
+            ));
+        }
+        Ok(if let Some(ty) = ty {
             Some(StructField {
                 ty,
                 name,
+                aliases,
                 original: f,
                 generic,
                 ns,
                 value,
                 default,
+                default_with,
+                whitespace,
+                deserialize_with,
+                serialize_with,
+                skip_serializing_if,
+                facets,
+                wrapper,
+                key,
             })
         } else if f.ident.is_none() {
             Some(StructField {
                 ty: EleType::Text,
                 name,
+                aliases,
                 original: f,
                 generic,
                 ns,
                 value,
                 default,
+                default_with,
+                whitespace,
+                deserialize_with,
+                serialize_with,
+                skip_serializing_if,
+                facets,
+                wrapper,
+                key,
             })
         } else {
             None
-        }
+        })
     }
 
     pub fn is_required(&self) -> bool {
@@ -289,62 +961,176 @@ impl<'a> StructField<'a> {
 #[derive(Debug)]
 pub struct EnumVariant<'a> {
     pub name: Option<syn::LitByteStr>,
+    /// Former names also accepted on deserialization, `alias = b"…"`.
+    pub aliases: Vec<syn::LitByteStr>,
     pub ns: Option<syn::LitByteStr>,
     pub ident: &'a syn::Ident,
     pub ty: Option<&'a syn::Type>,
     pub ele_type: EleType,
+    /// Text value of a variant of a simple enum.
+    pub value: Option<syn::LitStr>,
+    /// Catch-all variant for unknown elements, also of other namespaces, or, in simple enums,
+    /// unknown text values.
+    pub other: bool,
 }
 
 impl<'a> EnumVariant<'a> {
-    pub fn from_ast(v: &'a Variant) -> Self {
+    pub fn from_ast(
+        v: &'a Variant,
+        simple: bool,
+        rename_all: Option<RenameRule>,
+    ) -> syn::Result<Self> {
+        if simple {
+            return Self::from_ast_simple(v, rename_all);
+        }
         let mut name = Option::<syn::LitByteStr>::None;
+        let mut aliases = Vec::<syn::LitByteStr>::new();
         let mut ns = Option::<syn::LitByteStr>::None;
         let mut ele_type = EleType::Child;
-        for meta_item in v.attrs.iter().flat_map(get_xmlserde_meta_items).flatten() {
+        let mut other = false;
+        for meta_item in get_xmlserde_meta_items(&v.attrs)? {
             match meta_item {
+                Path(p) if p == OTHER => other = true,
                 NameValue(m) if m.path == NAME => {
-                    if let Some(NsValue::LitByte(s)) = get_lit_byte_str(m.value) {
-                        name = Some(s);
-                    }
+                    name = Some(get_byte_str(&m.value, "name")?);
+                }
+                NameValue(m) if m.path == ALIAS => {
+                    aliases.push(get_byte_str(&m.value, "alias")?);
                 }
                 NameValue(m) if m.path == NS => {
-                    if let Some(NsValue::LitByte(s)) = get_lit_byte_str(m.value) {
-                        ns = Some(s);
-                    }
+                    ns = Some(get_byte_str(&m.value, "ns")?);
                 }
                 NameValue(m) if m.path == TYPE => {
-                    if let Ok(s) = get_lit_str(&m.value) {
-                        let t = match s.value().as_str() {
-                            "child" => EleType::Child,
-                            "text" => EleType::Text,
-                            _ => panic!("invalid type in enum, should be `text` or `child` only"),
-                        };
-                        ele_type = t;
-                    }
+                    let s = get_lit_str(&m.value, "ty")?;
+                    let t = match s.value().as_str() {
+                        "child" => EleType::Child,
+                        "text" => EleType::Text,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                s,
+                                "invalid type in enum, should be `text` or `child` only",
+                            ))
+                        }
+                    };
+                    ele_type = t;
                 }
-                _ => panic!("unexpected attribute"),
+                meta => return Err(unexpected_attribute(&meta)),
             }
         }
         if v.fields.len() > 1 {
-            panic!("only support 1 field");
+            return Err(syn::Error::new_spanned(
+                &v.fields,
+                "variants can have at most 1 field",
+            ));
         }
-        if matches!(ele_type, EleType::Text) {
-            if name.is_some() {
-                panic!("should omit the `name`");
+        if other {
+            if name.is_some() || ns.is_some() || !aliases.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    v,
+                    "the other variant should omit the `name`, `alias` and `ns`",
+                ));
+            }
+            if v.fields.len() != 1 {
+                return Err(syn::Error::new_spanned(
+                    v,
+                    "the other variant should have 1 field, e.g. `Other(RawXml)` or `Other(String)`",
+                ));
+            }
+        } else if matches!(ele_type, EleType::Text) {
+            if name.is_some() || !aliases.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    v,
+                    "text variants should omit the `name` and `alias`",
+                ));
             }
         } else if name.is_none() {
-            panic!("should have name")
+            let Some(rule) = rename_all else {
+                return Err(syn::Error::new_spanned(
+                    v,
+                    "missing `name`, e.g. #[raxb(name = b\"element\")]",
+                ));
+            };
+            name = Some(rule.apply_byte_str(&v.ident));
         }
         let field = &v.fields.iter().next();
         let ty = field.map(|t| &t.ty);
         let ident = &v.ident;
-        EnumVariant {
+        Ok(EnumVariant {
             name,
+            aliases,
             ns,
             ty,
             ident,
             ele_type,
+            value: None,
+            other,
+        })
+    }
+
+    fn from_ast_simple(v: &'a Variant, rename_all: Option<RenameRule>) -> syn::Result<Self> {
+        let mut value = Option::<syn::LitStr>::None;
+        let mut other = false;
+        for meta_item in get_xmlserde_meta_items(&v.attrs)? {
+            match meta_item {
+                Path(p) if p == OTHER => other = true,
+                NameValue(m) if m.path == VALUE => {
+                    value = Some(get_lit_str(&m.value, "value")?.clone());
+                }
+                meta => {
+                    return Err(syn::Error::new_spanned(
+                        meta,
+                        "unexpected attribute, variants of simple enums only support `value` and `other`",
+                    ))
+                }
+            }
+        }
+        let ident = &v.ident;
+        if other {
+            if value.is_some() {
+                return Err(syn::Error::new_spanned(
+                    v,
+                    "the other variant should omit the `value`",
+                ));
+            }
+            if v.fields.len() != 1 {
+                return Err(syn::Error::new_spanned(
+                    v,
+                    "the other variant of a simple enum should have 1 field, e.g. `Other(String)`",
+                ));
+            }
+            return Ok(EnumVariant {
+                name: None,
+                aliases: vec![],
+                ns: None,
+                ty: v.fields.iter().next().map(|f| &f.ty),
+                ident,
+                ele_type: EleType::Text,
+                value: None,
+                other,
+            });
+        }
+        if !v.fields.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &v.fields,
+                "variants of simple enums should not have fields",
+            ));
         }
+        Ok(EnumVariant {
+            name: None,
+            aliases: vec![],
+            ns: None,
+            ty: None,
+            ident,
+            ele_type: EleType::Text,
+            value: Some(value.unwrap_or_else(|| {
+                let value = match rename_all {
+                    Some(rule) => rule.apply(ident),
+                    None => ident.to_string(),
+                };
+                syn::LitStr::new(&value, ident.span())
+            })),
+            other,
+        })
     }
 }
 
@@ -371,23 +1157,37 @@ pub enum EleType {
     Untag,
     Any,
     XmlNs,
+    /// The name of the element the struct is read from, written as its element name.
+    Tag,
+    /// All attributes not bound to another field, see `raxb::collection::XmlAnyAttributes`.
+    AnyAttr,
+    /// Not part of the xml, see `#[raxb(skip)]`.
+    Skip,
 }
 
-#[allow(dead_code)]
 pub enum Derive {
     Serialize,
     Deserialize,
 }
 
-fn get_xmlserde_meta_items(attr: &syn::Attribute) -> Result<Vec<syn::Meta>, ()> {
-    if attr.path() != RAXB && attr.path() != XML {
-        return Ok(Vec::new());
+fn get_xmlserde_meta_items(attrs: &[syn::Attribute]) -> syn::Result<Vec<syn::Meta>> {
+    let mut items = Vec::new();
+    for attr in attrs {
+        if attr.path() != RAXB && attr.path() != XML {
+            continue;
+        }
+        items.extend(attr.parse_args_with(Punctuated::<Meta, Comma>::parse_terminated)?);
     }
+    Ok(items)
+}
 
-    match attr.parse_args_with(Punctuated::<Meta, Comma>::parse_terminated) {
-        Ok(meta) => Ok(meta.into_iter().collect()),
-        Err(_) => Err(()),
-    }
+fn unexpected_attribute(meta: &syn::Meta) -> syn::Error {
+    let path = meta.path();
+    let name = path
+        .get_ident()
+        .map(|ident| ident.to_string())
+        .unwrap_or_else(|| "?".to_string());
+    syn::Error::new_spanned(meta, format!("unexpected attribute `{name}`"))
 }
 
 fn get_lit_byte_str(expr: syn::Expr) -> Option<NsValue> {
@@ -401,56 +1201,106 @@ fn get_lit_byte_str(expr: syn::Expr) -> Option<NsValue> {
     }
 }
 
-fn get_lit_str(lit: &syn::Expr) -> Result<&syn::LitStr, ()> {
-    if let syn::Expr::Lit(lit) = lit {
-        if let syn::Lit::Str(l) = &lit.lit {
-            return Ok(l);
-        }
+fn get_byte_str(expr: &syn::Expr, attribute: &str) -> syn::Result<syn::LitByteStr> {
+    if let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::ByteStr(l),
+        ..
+    }) = expr
+    {
+        return Ok(l.clone());
     }
-    Err(())
+    Err(syn::Error::new_spanned(
+        expr,
+        format!("{attribute} should be a byte string literal, e.g. b\"name\""),
+    ))
 }
 
+/// Compiles the pattern like `raxb::facet::Pattern`, so an invalid one is a compile error.
+fn check_pattern(pattern: &syn::LitStr) -> syn::Result<()> {
+    #[cfg(feature = "regex")]
+    {
+        // the unanchored pattern first, so that the error shows it as written
+        let value = pattern.value();
+        regex::Regex::new(&value)
+            .and_then(|_| regex::Regex::new(&format!("^(?:{value})$")))
+            .map(|_| ())
+            .map_err(|err| syn::Error::new_spanned(pattern, format!("invalid pattern: {err}")))
+    }
+    #[cfg(not(feature = "regex"))]
+    {
+        Err(syn::Error::new_spanned(
+            pattern,
+            "the `pattern` facet needs the `regex` feature of raxb",
+        ))
+    }
+}
+
+fn get_lit_str<'e>(lit: &'e syn::Expr, attribute: &str) -> syn::Result<&'e syn::LitStr> {
+    if let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(l),
+        ..
+    }) = lit
+    {
+        return Ok(l);
+    }
+    Err(syn::Error::new_spanned(
+        lit,
+        format!("{attribute} should be a string literal"),
+    ))
+}
+
+fn get_lit_path(lit: &syn::Expr, attribute: &str) -> syn::Result<syn::ExprPath> {
+    let s = get_lit_str(lit, attribute)?;
+    s.parse().map_err(|_| {
+        syn::Error::new_spanned(
+            s,
+            format!("{attribute} should be a path, e.g. \"my_module::my_function\""),
+        )
+    })
+}
+
+/// Collections of repeated elements, see `raxb::collection::XmlCollection`. The derive only sees
+/// the tokens of the field type, so collections are recognized by these names, see the
+/// documentation of `raxb::collection`.
+const COLLECTIONS: &[&str] = &[
+    "Vec", "VecDeque", "BTreeSet", "HashSet", "IndexSet", "SmallVec",
+];
+
+/// Maps of repeated elements keyed by an attribute or the element name.
+const MAPS: &[&str] = &["BTreeMap", "HashMap", "IndexMap"];
+
 fn get_generics(t: &syn::Type) -> Generic<'_> {
     match t {
         syn::Type::Path(p) => {
-            let path = &p.path;
-            match path.segments.last() {
-                Some(seg) => {
-                    if seg.ident == "Vec" {
-                        match &seg.arguments {
-                            syn::PathArguments::AngleBracketed(a) => {
-                                let args = &a.args;
-                                if args.len() != 1 {
-                                    Generic::None
-                                } else if let Some(syn::GenericArgument::Type(t)) = args.first() {
-                                    Generic::Vec(t)
-                                } else {
-                                    Generic::None
-                                }
-                            }
-                            _ => Generic::None,
-                        }
-                    } else if seg.ident == "Option" {
-                        match &seg.arguments {
-                            syn::PathArguments::AngleBracketed(a) => {
-                                let args = &a.args;
-                                if args.len() != 1 {
-                                    Generic::None
-                                } else if let Some(syn::GenericArgument::Type(t)) = args.first() {
-                                    Generic::Opt(t)
-                                } else {
-                                    Generic::None
-                                }
-                            }
-                            _ => Generic::None,
-                        }
-                    } else {
-                        Generic::None
-                    }
+            let Some(seg) = p.path.segments.last() else {
+                return Generic::None;
+            };
+            let syn::PathArguments::AngleBracketed(a) = &seg.arguments else {
+                return Generic::None;
+            };
+            // sets and maps may carry a hasher after the item types
+            let Some(syn::GenericArgument::Type(t)) = a.args.first() else {
+                return Generic::None;
+            };
+            if seg.ident == "Option" && a.args.len() == 1 {
+                Generic::Opt(t)
+            } else if seg.ident == "SmallVec" {
+                match t {
+                    syn::Type::Array(array) => Generic::Vec(&array.elem),
+                    _ => Generic::None,
+                }
+            } else if COLLECTIONS.iter().any(|c| seg.ident == c) {
+                Generic::Vec(t)
+            } else if MAPS.iter().any(|m| seg.ident == m) {
+                match a.args.iter().nth(1) {
+                    Some(syn::GenericArgument::Type(v)) => Generic::Map(t, v),
+                    _ => Generic::None,
                 }
-                None => Generic::None,
+            } else {
+                Generic::None
             }
         }
+        syn::Type::Array(array) => Generic::Vec(&array.elem),
         _ => Generic::None,
     }
 }
@@ -458,6 +1308,8 @@ fn get_generics(t: &syn::Type) -> Generic<'_> {
 pub enum Generic<'a> {
     Vec(&'a syn::Type),
     Opt(&'a syn::Type),
+    /// Key and value type of a map.
+    Map(&'a syn::Type, &'a syn::Type),
     None,
 }
 
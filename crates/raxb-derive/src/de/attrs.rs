@@ -1,8 +1,12 @@
 use std::str::FromStr;
 
-use quote::quote;
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
 
-use crate::container::{BuiltInType, FieldsSummary};
+use crate::{
+    container::{BuiltInType, FieldsSummary},
+    utils::{create_name_pattern, create_normalized_value},
+};
 
 pub fn init(fields: &FieldsSummary) -> proc_macro2::TokenStream {
     let v = fields.attrs.iter().map(|f| {
@@ -18,19 +22,81 @@ pub fn init(fields: &FieldsSummary) -> proc_macro2::TokenStream {
             }
         }
     });
+    // not named after the field, which is often called `attributes` like the argument
+    let any_attr = fields.any_attr.as_ref().map(|f| {
+        let ty = &f.original.ty;
+        quote! {
+            let mut __raxb_any_attr = <#ty as ::core::default::Default>::default();
+        }
+    });
     quote! {
         #(#v)*
+        #any_attr
     }
 }
 
-pub fn create_assignments(fields: &FieldsSummary) -> proc_macro2::TokenStream {
-    if fields.attrs.is_empty() {
+/// Attributes of fields with `ns` match by namespace and local name. Fields without `ns` match
+/// by local name, a prefixed attribute only if no unprefixed one has the name.
+pub fn create_assignments(
+    fields: &FieldsSummary,
+    tns_prefix: Option<&syn::LitByteStr>,
+) -> proc_macro2::TokenStream {
+    if fields.attrs.is_empty() && fields.any_attr.is_none() {
         return quote! {};
     }
-    let attrs = fields.attrs.iter().filter_map(|f| {
+    // namespaces of the prefixes of the fields, resolved once for the element
+    let namespaces = fields.attrs.iter().enumerate().filter_map(|(index, f)| {
+        let ns = f.ns.as_ref()?;
+        let ident = format_ident!("__raxb_ns_{}", index);
+        let namespace = if tns_prefix.is_some_and(|prefix| prefix.value() == ns.value()) {
+            quote! { Some(target_ns) }
+        } else {
+            quote! { None }
+        };
+        Some(quote! {
+            let #ident = _raxb::de::attribute_namespace(reader, #ns, #namespace);
+        })
+    });
+    let has_unqualified = fields
+        .attrs
+        .iter()
+        .any(|f| f.name.is_some() && f.ns.is_none());
+    let unqualified_attributes = has_unqualified.then(|| {
+        quote! {
+            let __raxb_attributes = attributes.clone();
+        }
+    });
+    let attrs = fields.attrs.iter().enumerate().filter_map(|(index, f)| {
         f.name.as_ref()?;
         let ident = f.original.ident.as_ref().unwrap();
-        let name = f.name.as_ref().unwrap();
+        let name = create_name_pattern(f.name.as_ref().unwrap(), &f.aliases);
+        let guard = match f.ns.as_ref() {
+            Some(ns) => {
+                let namespace = format_ident!("__raxb_ns_{}", index);
+                quote! {
+                    _raxb::de::is_attribute_in(reader, attr.key, #ns, #namespace.as_deref())
+                }
+            }
+            None => quote! {
+                attr.key.as_namespace_binding().is_none()
+                    && (attr.key.prefix().is_none()
+                        || !__raxb_attributes.clone().flatten().any(|a| {
+                            a.key.prefix().is_none() && matches!(a.key.as_ref(), #name)
+                        }))
+            },
+        };
+        let name = quote! { #name if #guard };
+        if let Some(deserialize_with) = f.deserialize_with.as_ref() {
+            let value = create_normalized_value(f.whitespace, quote! { value });
+            return Some(quote! {
+                #name => {
+                    let value_str = String::from_utf8(attr.value.to_vec())?;
+                    let value = _raxb::quick_xml::escape::unescape(&value_str)?;
+                    let value = #value;
+                    #ident = Some(#deserialize_with(&value).map_err(XmlDeserializeError::custom)?);
+                }
+            });
+        }
         let ty = &f.original.ty;
         let ty = if let Some(opt) = f.generic.get_opt() {
             opt
@@ -42,11 +108,22 @@ pub fn create_assignments(fields: &FieldsSummary) -> proc_macro2::TokenStream {
                 let built_in_type =
                     BuiltInType::from_str(&format!("{ty_ident}")).unwrap_or_default();
                 if built_in_type.is_string() {
+                    let value = create_normalized_value(f.whitespace, quote! { value });
                     return Some(quote! {
                         #name => {
                             let value_str = String::from_utf8(attr.value.to_vec())?;
                             let value = _raxb::quick_xml::escape::unescape(&value_str)?;
-                            #ident = Some(value.to_string());
+                            #ident = Some(#value);
+                        }
+                    });
+                } else if built_in_type.is_unknown() {
+                    return Some(quote! {
+                        #name => {
+                            let value_str = String::from_utf8(attr.value.to_vec())?;
+                            let value = _raxb::quick_xml::escape::unescape(&value_str)?;
+                            use _raxb::de::{ParseAttributeOrDefault as _, ParseAttributeOrError as _};
+                            let parse = _raxb::de::ParseAttribute::<#ty>::new();
+                            #ident = Some((&parse).parse_attribute(value.trim())?);
                         }
                     });
                 } else {
@@ -62,11 +139,25 @@ pub fn create_assignments(fields: &FieldsSummary) -> proc_macro2::TokenStream {
         }
         None
     });
+    let any_attr = fields.any_attr.as_ref().map(|f| {
+        let ty = &f.original.ty;
+        quote_spanned! {ty.span()=>
+            let value_str = String::from_utf8(attr.value.to_vec())?;
+            let value = _raxb::quick_xml::escape::unescape(&value_str)?;
+            let name: _raxb::value::QName = std::str::from_utf8(attr.key.as_ref())?.parse()?;
+            _raxb::collection::XmlAnyAttributes::push_attribute(&mut __raxb_any_attr, name, value.into_owned());
+        }
+    });
     quote! {
+        #(#namespaces)*
+        #unqualified_attributes
         for attr in attributes.flatten() {
+            _raxb::de::check_text_length(attr.value.len())?;
             match attr.key.local_name().as_ref() {
                 #(#attrs)*
-                _ => {}
+                _ => {
+                    #any_attr
+                }
             }
         }
     }
@@ -1,11 +1,15 @@
 use std::str::FromStr;
 
 use quote::quote;
-use syn::{AngleBracketedGenericArguments, GenericArgument, PathArguments};
 
 use crate::{
-    container::{BuiltInType, Container, EleType, FieldsSummary, Generic, NsValue},
-    utils::trace,
+    container::{
+        BuiltInType, Container, EleType, FieldsSummary, Generic, MapKey, NsValue, StructField,
+        WhiteSpace,
+    },
+    utils::{
+        create_name_pattern, create_name_value, create_normalized_value, get_built_in_type, trace,
+    },
 };
 
 pub fn init(fields: &FieldsSummary) -> proc_macro2::TokenStream {
@@ -27,6 +31,10 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
     let mut unqualified_child_terminate_branches = Vec::<proc_macro2::TokenStream>::new();
     let mut unqualified_child_branches = Vec::<proc_macro2::TokenStream>::new();
     let mut unqualified_sfc_branches = Vec::<proc_macro2::TokenStream>::new();
+    // bodies of the fallback start and empty branches for a map keyed by the element name
+    let mut qualified_other = Option::<(proc_macro2::TokenStream, proc_macro2::TokenStream)>::None;
+    let mut unqualified_other =
+        Option::<(proc_macro2::TokenStream, proc_macro2::TokenStream)>::None;
 
     for f in container
         .struct_fields
@@ -34,30 +42,83 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
         .filter(|sf| matches!(sf.ty, EleType::Child | EleType::SelfClosedChild))
     {
         let is_qualified = f.ns.is_some();
+        if matches!(f.key, Some(MapKey::Name)) {
+            let other = Some(create_deserialize_keyed_by_name(f));
+            if is_qualified {
+                qualified_other = other;
+            } else {
+                unqualified_other = other;
+            }
+            continue;
+        }
         if let Some(tag) = f.name.as_ref() {
             let ident = f.original.ident.as_ref().unwrap();
             let ty = &f.original.ty;
-            let (ty, is_array) = match f.generic {
-                Generic::Vec(v) => (v, true),
-                Generic::Opt(opt) => (opt, false),
-                Generic::None => (ty, false),
+            let ty = match f.generic {
+                Generic::Vec(v) => v,
+                Generic::Opt(opt) => opt,
+                Generic::Map(_, v) => v,
+                Generic::None => ty,
             };
+            let assignment = create_assignment(f, ident);
+            let pattern = create_name_pattern(tag, &f.aliases);
+            let tag_value = create_name_value(tag, &f.aliases, quote! { ev });
+            if let Some(wrapper) = f.wrapper.as_ref() {
+                let wrapper_name = &wrapper.name;
+                let deserialize_items =
+                    create_deserialize_wrapped_items(f, &pattern, &tag_value, ty, ident);
+                let trace_start_elment = trace(quote! {
+                    debug!("Start wrapper element with tag '{}'", String::from_utf8_lossy(#wrapper_name));
+                });
+                let (child_branches, sfc_branches) = if wrapper.ns.is_some() {
+                    (&mut qualified_child_branches, &mut qualified_sfc_branches)
+                } else {
+                    (
+                        &mut unqualified_child_branches,
+                        &mut unqualified_sfc_branches,
+                    )
+                };
+                child_branches.push(quote! {
+                    #wrapper_name => {
+                        #trace_start_elment
+                        #deserialize_items
+                    }
+                });
+                sfc_branches.push(quote! {
+                    #wrapper_name => {}
+                });
+                continue;
+            }
             if matches!(f.ty, EleType::Child) {
                 if is_qualified {
                     let (deserialize_value, deserialize_value_sfc, terminates) =
-                        create_deserialize_value(tag, ty, ident, is_array, f.default);
+                        if let Some(deserialize_with) = f.deserialize_with.as_ref() {
+                            create_deserialize_value_with(
+                                deserialize_with,
+                                &assignment,
+                                f.whitespace,
+                            )
+                        } else {
+                            create_deserialize_value(
+                                &tag_value,
+                                ty,
+                                &assignment,
+                                f.default,
+                                f.whitespace,
+                            )
+                        };
                     let trace_start_elment = trace(quote! {
                         debug!("Start element with tag '{}'", String::from_utf8_lossy(#tag));
                     });
                     qualified_child_branches.push(quote! {
-                        #tag => {
+                        #pattern => {
                             #trace_start_elment
                             #deserialize_value
                         }
                     });
                     if let Some(deserialize_value_sfc) = deserialize_value_sfc {
                         qualified_sfc_branches.push(quote! {
-                            #tag => {
+                            #pattern => {
                                 #deserialize_value_sfc
                             }
                         });
@@ -68,7 +129,7 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
                             debug!("End element with tag '{}'", String::from_utf8_lossy(#tag));
                         });
                         qualified_child_terminate_branches.push(quote! {
-                            #tag => {
+                            #pattern => {
                                 #trace_end_elment
                             }
                         });
@@ -78,16 +139,30 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
                         debug!("Start element with tag '{}'", String::from_utf8_lossy(#tag));
                     });
                     let (deserialize_value, deserialize_value_sfc, terminates) =
-                        create_deserialize_value(tag, ty, ident, is_array, f.default);
+                        if let Some(deserialize_with) = f.deserialize_with.as_ref() {
+                            create_deserialize_value_with(
+                                deserialize_with,
+                                &assignment,
+                                f.whitespace,
+                            )
+                        } else {
+                            create_deserialize_value(
+                                &tag_value,
+                                ty,
+                                &assignment,
+                                f.default,
+                                f.whitespace,
+                            )
+                        };
                     unqualified_child_branches.push(quote! {
-                        #tag => {
+                        #pattern => {
                             #trace_start_elment
                             #deserialize_value
                         }
                     });
                     if let Some(deserialize_value_sfc) = deserialize_value_sfc {
                         unqualified_sfc_branches.push(quote! {
-                            #tag => {
+                            #pattern => {
                                 #deserialize_value_sfc
                             }
                         });
@@ -98,7 +173,7 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
                             debug!("End element with tag '{}'", String::from_utf8_lossy(#tag));
                         });
                         unqualified_child_terminate_branches.push(quote! {
-                            #tag => {
+                            #pattern => {
                                 #trace_end_elment
                             }
                         });
@@ -108,17 +183,17 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
             if matches!(f.ty, EleType::SelfClosedChild) {
                 if is_qualified {
                     let deserialize_value_sfc =
-                        create_deserialize_value_sfc(tag, ty, ident, is_array, f.default);
+                        create_deserialize_value_sfc(&tag_value, ty, &assignment, f.default);
                     qualified_sfc_branches.push(quote! {
-                        #tag => {
+                        #pattern => {
                             #deserialize_value_sfc
                         }
                     });
                 } else {
                     let deserialize_value_sfc =
-                        create_deserialize_value_sfc(tag, ty, ident, is_array, f.default);
+                        create_deserialize_value_sfc(&tag_value, ty, &assignment, f.default);
                     unqualified_sfc_branches.push(quote! {
-                        #tag => {
+                        #pattern => {
                             #deserialize_value_sfc
                         }
                     });
@@ -153,7 +228,9 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
         quote! {}
     };
 
-    let has_qualified_children = !qualified_child_branches.is_empty();
+    let (qualified_other_start, qualified_other_empty) = qualified_other.unzip();
+    let has_qualified_children =
+        !qualified_child_branches.is_empty() || qualified_other_start.is_some();
     let qualified_child_branch = if has_qualified_children {
         let qualified_child_branches = qualified_child_branches.into_iter();
         let unexpected_start_event = if cfg!(feature = "trace") {
@@ -163,14 +240,18 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
         } else {
             quote! {}
         };
+        let qualified_other_start = qualified_other_start.unwrap_or_else(|| {
+            quote! {
+                #unexpected_start_event
+                _raxb::de::skip_element(reader, false)?;
+            }
+        });
         quote! {
             (ResolveResult::Bound(ns), Event::Start(ev)) => {
                 match ev.local_name().as_ref() {
                     #(#qualified_child_branches,)*
                     _ => {
-                        #unexpected_start_event
-                        let mut buffer: Vec<u8> = Vec::<u8>::new();
-                        reader.read_to_end_into(ev.name(), &mut buffer)?;
+                        #qualified_other_start
                     },
                 }
             },
@@ -179,14 +260,16 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
         quote! {}
     };
 
-    let has_qualified_sfcs = !qualified_sfc_branches.is_empty();
+    let has_qualified_sfcs = !qualified_sfc_branches.is_empty() || qualified_other_empty.is_some();
     let qualified_sfc_branch = if has_qualified_sfcs {
         let qualified_sfc_branches = qualified_sfc_branches.into_iter();
         quote! {
             (ResolveResult::Bound(ns), Event::Empty(ev)) => {
                 match ev.local_name().as_ref() {
                     #(#qualified_sfc_branches,)*
-                    _ => {},
+                    _ => {
+                        #qualified_other_empty
+                    },
                 }
             },
         }
@@ -220,7 +303,9 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
         quote! {}
     };
 
-    let has_unqualified_children = !unqualified_child_branches.is_empty();
+    let (unqualified_other_start, unqualified_other_empty) = unqualified_other.unzip();
+    let has_unqualified_children =
+        !unqualified_child_branches.is_empty() || unqualified_other_start.is_some();
     let unqualified_child_branch = if has_unqualified_children {
         let unqualified_child_branches = unqualified_child_branches.into_iter();
         let unexpected_start_event = if cfg!(feature = "trace") {
@@ -230,14 +315,18 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
         } else {
             quote! {}
         };
+        let unqualified_other_start = unqualified_other_start.unwrap_or_else(|| {
+            quote! {
+                #unexpected_start_event
+                _raxb::de::skip_element(reader, false)?;
+            }
+        });
         quote! {
             (ResolveResult::Unbound, Event::Start(ev)) => {
                 match ev.local_name().as_ref() {
                     #(#unqualified_child_branches,)*
                     _ => {
-                        #unexpected_start_event
-                        let mut buffer: Vec<u8> = Vec::<u8>::new();
-                        reader.read_to_end_into(ev.name(), &mut buffer)?;
+                        #unqualified_other_start
                     },
                 }
             },
@@ -246,14 +335,17 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
         quote! {}
     };
 
-    let has_unqualified_sfcs = !unqualified_sfc_branches.is_empty();
+    let has_unqualified_sfcs =
+        !unqualified_sfc_branches.is_empty() || unqualified_other_empty.is_some();
     let unqualified_sfc_branch = if has_unqualified_sfcs {
         let unqualified_sfc_branches = unqualified_sfc_branches.into_iter();
         quote! {
             (ResolveResult::Unbound, Event::Empty(ev)) => {
                 match ev.local_name().as_ref() {
                     #(#unqualified_sfc_branches,)*
-                    _ => {},
+                    _ => {
+                        #unqualified_other_empty
+                    },
                 }
             },
         }
@@ -266,11 +358,16 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
             NsValue::LitByte(lit) => quote! { #lit },
             NsValue::ExprPath(path) => quote! { #path },
         };
+        let ns_check = if container.tns_aliases.is_empty() {
+            quote! { ns.as_ref() == #ns_expr }
+        } else {
+            quote! { _raxb::de::is_target_ns::<Self>(#ns_expr, ns.as_ref()) }
+        };
         let trace_end_branch = trace(quote! {
             debug!("Leave struct '{}' with tag '{}' and namespace '{}'", #ident_str, std::str::from_utf8(tag).unwrap(), std::str::from_utf8(ns.as_ref()).unwrap());
         });
         quote! {
-            (ResolveResult::Bound(ns), Event::End(e)) if e.local_name().as_ref() == tag && ns.as_ref() == #ns_expr => {
+            (ResolveResult::Bound(ns), Event::End(e)) if e.local_name().as_ref() == tag && #ns_check => {
                 #trace_end_branch
                 break;
             },
@@ -308,7 +405,9 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
                 let mut buf = Vec::<u8>::new();
 
                 loop {
-                    match reader.read_resolved_event_into(&mut buf)? {
+                    let event = reader.read_resolved_event_into(&mut buf)?;
+                    _raxb::de::check_event(&event.1)?;
+                    match event {
                         #qualified_child_branch
                         #qualified_sfc_branch
                         #unqualified_child_branch
@@ -329,22 +428,185 @@ pub fn create_assignments(container: &Container) -> proc_macro2::TokenStream {
     }
 }
 
-fn create_deserialize_value_sfc(
-    tag: &syn::LitByteStr,
-    ty: &syn::Type,
-    ident: &syn::Ident,
-    is_array: bool,
-    default: bool,
-) -> proc_macro2::TokenStream {
-    let assignment = if is_array {
-        quote! {
+/// Stores the deserialized `value` in the local of the field. Maps read the key from the
+/// attribute `key` or the local name of the start event `ev`.
+fn create_assignment(f: &StructField, ident: &syn::Ident) -> proc_macro2::TokenStream {
+    match (&f.generic, &f.key) {
+        (Generic::Vec(_), _) => quote! {
+            _raxb::de::check_vec_length(#ident.len())?;
             #ident.push(value);
+        },
+        (Generic::Map(key_ty, _), Some(key)) => {
+            let key = match key {
+                MapKey::Attr(key) => quote! {
+                    match ev.try_get_attribute(#key).map_err(_raxb::quick_xml::Error::from)? {
+                        Some(attr) => attr.unescape_value()?.parse().map_err(XmlDeserializeError::custom)?,
+                        None => return Err(XmlDeserializeError::MissingAttribute(S(#key))),
+                    }
+                },
+                MapKey::Name => quote! {
+                    String::from_utf8_lossy(ev.local_name().as_ref()).parse().map_err(XmlDeserializeError::custom)?
+                },
+            };
+            quote! {
+                let __raxb_key: #key_ty = #key;
+                _raxb::de::check_vec_length(#ident.len())?;
+                #ident.insert(__raxb_key, value);
+            }
         }
+        _ => quote! {
+            #ident = Some(value);
+        },
+    }
+}
+
+/// Bodies of the fallback start and empty branches, which read every element not matched by
+/// another field into the map keyed by the element name. The name is not known statically, so
+/// the value reads up to the end of its start event, enums only read their variant.
+fn create_deserialize_keyed_by_name(
+    f: &StructField,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let ident = f.original.ident.as_ref().unwrap();
+    let Generic::Map(_, ty) = f.generic else {
+        unreachable!("key_by_name is only supported on map fields")
+    };
+    let assignment = create_assignment(f, ident);
+    let (deserialize_value, deserialize_value_sfc, reads_end) =
+        if let Some(deserialize_with) = f.deserialize_with.as_ref() {
+            create_deserialize_value_with(deserialize_with, &assignment, f.whitespace)
+        } else {
+            create_deserialize_value(&quote! { &[] }, ty, &assignment, f.default, f.whitespace)
+        };
+    // the element may have any name, also that of the struct, so its end tag can't be left to
+    // the loop of the struct
+    let deserialize_value = if reads_end {
+        deserialize_value
     } else {
+        let trim = if f.whitespace.is_some() {
+            quote! { false }
+        } else {
+            quote! { _raxb::de::is_text_trimmed(reader, ev.attributes()) }
+        };
+        let assign_value = if get_built_in_type(ty).is_string() {
+            let value = create_normalized_value(f.whitespace, quote! { value });
+            quote! {
+                let value = #value;
+                #assignment
+            }
+        } else if f.default {
+            quote! {
+                let value: #ty = value.trim().parse().unwrap_or_default();
+                #assignment
+            }
+        } else {
+            quote! {
+                if !value.is_empty() {
+                    let value: #ty = value.trim().parse().unwrap_or_default();
+                    #assignment
+                }
+            }
+        };
         quote! {
-            #ident = Some(value);
+            let previous_trim = _raxb::de::set_trim_text(reader, #trim);
+            let value = _raxb::de::read_text_content(reader, false)?;
+            _raxb::de::set_trim_text(reader, previous_trim);
+            #assign_value
         }
     };
+    let skip_enum_end =
+        (f.deserialize_with.is_none() && get_built_in_type(ty).is_unknown()).then(|| {
+            quote! {
+                if <#ty as XmlDeserialize>::is_enum() {
+                    _raxb::de::skip_element(reader, false)?;
+                }
+            }
+        });
+    (
+        quote! {
+            #deserialize_value
+            #skip_enum_end
+        },
+        deserialize_value_sfc.unwrap_or_default(),
+    )
+}
+
+/// Reads the items of a wrapper element until its end, skipping other elements.
+fn create_deserialize_wrapped_items(
+    f: &StructField,
+    pattern: &proc_macro2::TokenStream,
+    tag: &proc_macro2::TokenStream,
+    ty: &syn::Type,
+    ident: &syn::Ident,
+) -> proc_macro2::TokenStream {
+    let wrapper_name = &f.wrapper.as_ref().unwrap().name;
+    let assignment = create_assignment(f, ident);
+    let resolved = if f.ns.is_some() {
+        quote! { ResolveResult::Bound(_) }
+    } else {
+        quote! { ResolveResult::Unbound }
+    };
+    let (start_branch, empty_branch) = if matches!(f.ty, EleType::Child) {
+        let (deserialize_value, deserialize_value_sfc, _) =
+            if let Some(deserialize_with) = f.deserialize_with.as_ref() {
+                create_deserialize_value_with(deserialize_with, &assignment, f.whitespace)
+            } else {
+                create_deserialize_value(tag, ty, &assignment, f.default, f.whitespace)
+            };
+        (
+            Some(quote! {
+                (#resolved, Event::Start(ev)) if matches!(ev.local_name().as_ref(), #pattern) => {
+                    #deserialize_value
+                },
+            }),
+            deserialize_value_sfc,
+        )
+    } else {
+        (
+            None,
+            Some(create_deserialize_value_sfc(
+                tag,
+                ty,
+                &assignment,
+                f.default,
+            )),
+        )
+    };
+    let empty_branch = empty_branch.map(|deserialize_value_sfc| {
+        quote! {
+            (#resolved, Event::Empty(ev)) if matches!(ev.local_name().as_ref(), #pattern) => {
+                #deserialize_value_sfc
+            },
+        }
+    });
+    quote! {
+        let mut __raxb_wrapper_buf = Vec::<u8>::new();
+        loop {
+            let event = reader.read_resolved_event_into(&mut __raxb_wrapper_buf)?;
+            _raxb::de::check_event(&event.1)?;
+            match event {
+                #start_branch
+                #empty_branch
+                (_, Event::Start(ev)) => {
+                    _raxb::de::skip_element(reader, false)?;
+                },
+                (_, Event::End(ev)) if ev.local_name().as_ref() == #wrapper_name => {
+                    break;
+                },
+                (_, Event::Eof) => {
+                    break;
+                },
+                _ => {}
+            }
+        }
+    }
+}
+
+fn create_deserialize_value_sfc(
+    tag: &proc_macro2::TokenStream,
+    ty: &syn::Type,
+    assignment: &proc_macro2::TokenStream,
+    default: bool,
+) -> proc_macro2::TokenStream {
     if let syn::Type::Path(p) = ty {
         if let Some(ident) = p.path.get_ident() {
             let built_in_ty: BuiltInType =
@@ -357,12 +619,12 @@ fn create_deserialize_value_sfc(
             } else if built_in_ty.is_unknown() {
                 if default {
                     return quote! {
-                        let value = #ty::xml_deserialize(reader, &[], #tag, ev.attributes(), true).unwrap_or_default();
+                        let value = <#ty as _raxb::de::XmlDeserialize>::xml_deserialize_with_start(reader, &[], #tag, &ev, true).unwrap_or_default();
                         #assignment
                     };
                 } else {
                     return quote! {
-                        let value = #ty::xml_deserialize(reader, &[], #tag, ev.attributes(), true)?;
+                        let value = <#ty as _raxb::de::XmlDeserialize>::xml_deserialize_with_start(reader, &[], #tag, &ev, true)?;
                         #assignment
                     };
                 }
@@ -372,41 +634,81 @@ fn create_deserialize_value_sfc(
     quote! {}
 }
 
-fn create_deserialize_value(
-    tag: &syn::LitByteStr,
-    ty: &syn::Type,
-    ident: &syn::Ident,
-    is_array: bool,
-    default: bool,
+/// Reads the text content of the child and converts it with the `deserialize_with` function.
+fn create_deserialize_value_with(
+    deserialize_with: &syn::ExprPath,
+    assignment: &proc_macro2::TokenStream,
+    whitespace: Option<WhiteSpace>,
 ) -> (
     proc_macro2::TokenStream,
     Option<proc_macro2::TokenStream>,
     bool,
 ) {
-    let assignment = if is_array {
-        quote! {
-            #ident.push(value);
-        }
+    let trim = if whitespace.is_some() {
+        quote! { false }
     } else {
         quote! {
-            #ident = Some(value);
+            _raxb::de::is_text_trimmed(reader, ev.attributes())
         }
     };
+    let value = create_normalized_value(whitespace, quote! { value });
+    (
+        quote! {
+            let previous_trim = _raxb::de::set_trim_text(reader, #trim);
+            let value = _raxb::de::read_text_content(reader, false)?;
+            _raxb::de::set_trim_text(reader, previous_trim);
+            let value = #value;
+            let value = #deserialize_with(&value).map_err(XmlDeserializeError::custom)?;
+            #assignment
+        },
+        Some(quote! {
+            let value = #deserialize_with("").map_err(XmlDeserializeError::custom)?;
+            #assignment
+        }),
+        true,
+    )
+}
+
+fn create_deserialize_value(
+    tag: &proc_macro2::TokenStream,
+    ty: &syn::Type,
+    assignment: &proc_macro2::TokenStream,
+    default: bool,
+    whitespace: Option<WhiteSpace>,
+) -> (
+    proc_macro2::TokenStream,
+    Option<proc_macro2::TokenStream>,
+    bool,
+) {
     if let syn::Type::Path(p) = ty {
         if let Some(ident) = p.path.get_ident() {
             let built_in_ty: BuiltInType =
                 BuiltInType::from_str(&format!("{ident}")).unwrap_or_default();
             if built_in_ty.is_string() {
+                let trim = if whitespace.is_some() {
+                    quote! { false }
+                } else {
+                    quote! {
+                        _raxb::de::is_text_trimmed(reader, ev.attributes())
+                    }
+                };
+                let value = create_normalized_value(whitespace, quote! { value });
                 return (
                     quote! {
                         let mut buffer: Vec<u8> = Vec::<u8>::new();
-                        if let (_, Event::Text(t)) = reader.read_resolved_event_into(&mut buffer)? {
-                            let value = t.unescape()?.to_string();
+                        let trim = #trim;
+                        let previous_trim = _raxb::de::set_trim_text(reader, trim);
+                        let event = reader.read_resolved_event_into(&mut buffer)?;
+                        _raxb::de::check_event(&event.1)?;
+                        if let (_, Event::Text(t)) = event {
+                            let value = t.unescape()?;
+                            let value = #value;
                             #assignment
                         } else {
                             let value = "".to_string();
                             #assignment
                         }
+                        _raxb::de::set_trim_text(reader, previous_trim);
                     },
                     Some(quote! {
                         let value = String::new();
@@ -419,7 +721,9 @@ fn create_deserialize_value(
                     return (
                         quote! {
                             let mut buffer: Vec<u8> = Vec::<u8>::new();
-                            if let (_, Event::Text(t)) = reader.read_resolved_event_into(&mut buffer)? {
+                            let event = reader.read_resolved_event_into(&mut buffer)?;
+                            _raxb::de::check_event(&event.1)?;
+                            if let (_, Event::Text(t)) = event {
                                 let str_value = t.unescape()?;
                                 let value : #ty = str_value.trim().parse().unwrap_or_default();
                                 #assignment
@@ -435,7 +739,9 @@ fn create_deserialize_value(
                     return (
                         quote! {
                             let mut buffer: Vec<u8> = Vec::<u8>::new();
-                            if let (_, Event::Text(t)) = reader.read_resolved_event_into(&mut buffer)? {
+                            let event = reader.read_resolved_event_into(&mut buffer)?;
+                            _raxb::de::check_event(&event.1)?;
+                            if let (_, Event::Text(t)) = event {
                                 let str_value = t.unescape()?;
                                 let value : #ty = str_value.trim().parse().unwrap_or_default();
                                 #assignment
@@ -445,50 +751,39 @@ fn create_deserialize_value(
                         false,
                     );
                 }
-            } else if default {
-                return (
-                    quote! {
-                        let value = #ty::xml_deserialize(reader, target_ns, #tag, ev.attributes(), false).unwrap_or_default();
-                        #assignment
-                    },
-                    None,
-                    true,
-                );
-            } else {
-                return (
-                    quote! {
-                        let value = #ty::xml_deserialize(reader, target_ns, #tag, ev.attributes(), false)?;
-                        #assignment
-                    },
-                    Some(quote! {
-                        let value = #ty::xml_deserialize(reader, target_ns, #tag, ev.attributes(), true)?;
-                        #assignment
-                    }),
-                    true,
-                );
-            }
-        } else if let Some(path) = p.path.segments.first() {
-            let ident = &path.ident;
-            if let PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }) =
-                &path.arguments
-            {
-                let args = args.iter().filter_map(|a| {
-                    if let GenericArgument::Type(p) = a {
-                        Some(p)
-                    } else {
-                        None
-                    }
-                });
-                return (
-                    quote! {
-                        let value = #ident::<#(#args,)*>::xml_deserialize(reader, target_ns, #tag, ev.attributes(), false)?;
-                        #assignment
-                    },
-                    None,
-                    true,
-                );
             }
         }
+        let value = quote! {
+            <#ty as _raxb::de::XmlDeserialize>::xml_deserialize_with_start(reader, target_ns, #tag, &ev, false)
+        };
+        let empty_value = quote! {
+            <#ty as _raxb::de::XmlDeserialize>::xml_deserialize_with_start(reader, target_ns, #tag, &ev, true)
+        };
+        if default {
+            return (
+                quote! {
+                    let value = #value.unwrap_or_default();
+                    #assignment
+                },
+                Some(quote! {
+                    let value = #empty_value.unwrap_or_default();
+                    #assignment
+                }),
+                true,
+            );
+        } else {
+            return (
+                quote! {
+                    let value = #value?;
+                    #assignment
+                },
+                Some(quote! {
+                    let value = #empty_value?;
+                    #assignment
+                }),
+                true,
+            );
+        }
     }
     (quote! {}, None, true)
 }
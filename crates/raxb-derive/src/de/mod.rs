@@ -6,25 +6,22 @@ mod child;
 mod text;
 mod ty_enum;
 mod ty_simple;
+mod ty_simple_enum;
 mod ty_struct;
 
-pub fn xml_deserialize_impl_block(input: DeriveInput) -> proc_macro2::TokenStream {
-    let container = Container::from_ast(&input, container::Derive::Deserialize);
+pub fn xml_deserialize_impl_block(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let container = Container::from_ast(&input, container::Derive::Deserialize)?;
     // eprintln!("validate container");
-    container.validate();
-    if container.is_enum() {
+    container.validate()?;
+    crate::utils::check_inferred_bounds(&container, container::Derive::Deserialize)?;
+    Ok(if container.simple {
+        ty_simple_enum::impl_block(container)
+    } else if container.is_enum() {
         ty_enum::impl_block(container)
+    } else if container.transparent {
+        ty_simple::impl_block(container)
     } else {
-        let is_simple_type = container
-            .struct_fields
-            .iter()
-            .all(|sf| sf.original.ident.is_none());
-        if is_simple_type {
-            // eprintln!("run ty_simple::impl_block");
-            ty_simple::impl_block(container)
-        } else {
-            // eprintln!("run ty_struct::impl_block");
-            ty_struct::impl_block(container)
-        }
-    }
+        // eprintln!("run ty_struct::impl_block");
+        ty_struct::impl_block(container)
+    })
 }
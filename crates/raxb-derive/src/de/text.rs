@@ -2,7 +2,10 @@ use std::str::FromStr;
 
 use quote::quote;
 
-use crate::container::{BuiltInType, FieldsSummary, Generic, StructField};
+use crate::{
+    container::{BuiltInType, FieldsSummary, Generic, StructField},
+    utils::create_normalized_value,
+};
 
 pub fn init(fields: &FieldsSummary) -> proc_macro2::TokenStream {
     if let Some(f) = fields.text.as_ref() {
@@ -20,9 +23,11 @@ fn create_assing_value(
         Generic::Vec(ty) => (ty, true),
         Generic::Opt(opt) => (opt, false),
         Generic::None => (ty, false),
+        Generic::Map(..) => unreachable!("maps are only supported on child fields"),
     };
     let assignment = if is_array {
         quote! {
+            _raxb::de::check_vec_length(#ident.len())?;
             #ident.push(value);
         }
     } else {
@@ -30,14 +35,29 @@ fn create_assing_value(
             #ident = Some(value);
         }
     };
+    if let Some(deserialize_with) = f.deserialize_with.as_ref() {
+        let value = create_normalized_value(f.whitespace, quote! { ev.unescape()? });
+        return (
+            quote! {
+                let value = #value;
+                let value = #deserialize_with(&value).map_err(XmlDeserializeError::custom)?;
+                #assignment
+            },
+            Some(quote! {
+                let value = #deserialize_with("").map_err(XmlDeserializeError::custom)?;
+                #assignment
+            }),
+        );
+    }
     if let syn::Type::Path(p) = ty {
         if let Some(ty_ident) = p.path.get_ident() {
             let built_in_ty: BuiltInType =
                 BuiltInType::from_str(&format!("{ty_ident}")).unwrap_or_default();
             return if built_in_ty.is_string() {
+                let value = create_normalized_value(f.whitespace, quote! { ev.unescape()? });
                 (
                     quote! {
-                        let value = ev.unescape()?.to_string();
+                        let value = #value;
                         #assignment
                     },
                     Some(quote! {
@@ -62,19 +82,29 @@ fn create_assing_value(
 
 pub fn create_assignments(f: &StructField) -> proc_macro2::TokenStream {
     let (assign_value, assign_empty_value) = create_assing_value(f);
+    let (disable_trim, restore_trim) = if f.whitespace.is_some() {
+        (
+            quote! { let previous_trim = _raxb::de::set_trim_text(reader, false); },
+            quote! { _raxb::de::set_trim_text(reader, previous_trim); },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
     quote! {
         if is_empty {
             #assign_empty_value
         } else {
+            #disable_trim
             let mut buf = Vec::<u8>::new();
             loop {
-                match reader.read_resolved_event_into(&mut buf)? {
+                let event = reader.read_resolved_event_into(&mut buf)?;
+                _raxb::de::check_event(&event.1)?;
+                match event {
                     (_, Event::Text(ev)) => {
                         #assign_value
                     },
                     (_, Event::Start(ev)) => {
-                        let mut buffer: Vec<u8> = Vec::<u8>::new();
-                        reader.read_to_end_into(ev.name(), &mut buffer)?;
+                        _raxb::de::skip_element(reader, false)?;
                     },
                     (_, Event::Empty(ev)) => {},
                     (_, Event::End(e)) if e.local_name().as_ref() == tag => {
@@ -86,6 +116,7 @@ pub fn create_assignments(f: &StructField) -> proc_macro2::TokenStream {
                     _ => {},
                 }
             }
+            #restore_trim
         }
     }
 }
@@ -3,8 +3,11 @@ use quote::quote;
 use syn::LitByteStr;
 
 use crate::{
-    container::{Container, EnumVariant},
-    utils::{create_tns_impl, get_built_in_type, trace},
+    container::{Container, Derive, EnumVariant, NsValue},
+    utils::{
+        create_generics, create_name_pattern, create_name_value, create_root_impl, create_tns_impl,
+        get_built_in_type, trace,
+    },
 };
 
 fn create_variant(
@@ -15,11 +18,14 @@ fn create_variant(
     let name = variant.name.as_ref();
     let variant_ident = variant.ident;
     let ty = variant.ty.as_ref();
+    let pattern = name.map(|name| create_name_pattern(name, &variant.aliases));
     if let Some((name, ty)) = name.zip(ty) {
         let built_in_type = get_built_in_type(ty);
         let assignment = if built_in_type.is_bool() || built_in_type.is_number() {
             quote! {
-                let value = if let (_, Event::Text(t)) = reader.read_resolved_event_into(&mut buf)? {
+                let event = reader.read_resolved_event_into(&mut buf)?;
+                _raxb::de::check_event(&event.1)?;
+                if let (_, Event::Text(t)) = event {
                     let str_value = t.unescape()?;
                     let value : #ty = str_value.parse().unwrap_or_default();
                     result = Some(#ident::#variant_ident(value));
@@ -27,33 +33,36 @@ fn create_variant(
             }
         } else if built_in_type.is_string() {
             quote! {
-                let value = if let (_, Event::Text(t)) = reader.read_resolved_event_into(&mut buf)? {
+                let event = reader.read_resolved_event_into(&mut buf)?;
+                _raxb::de::check_event(&event.1)?;
+                if let (_, Event::Text(t)) = event {
                     let str_value = t.unescape()?;
                     let value = str_value.to_string();
                     result = Some(#ident::#variant_ident(value));
                 };
             }
         } else {
+            let tag = create_name_value(name, &variant.aliases, quote! { e });
             quote! {
-                let value = #ty::xml_deserialize(
+                let value = <#ty as XmlDeserialize>::xml_deserialize_with_start(
                     reader,
                     target_ns,
-                    #name,
-                    e.attributes(),
+                    #tag,
+                    &e,
                     #empty,
                 )?;
                 result = Some(#ident::#variant_ident(value));
             }
         };
         return Some(quote! {
-            #name => {
+            #pattern => {
                 #assignment
                 break;
             },
         });
-    } else if let Some(name) = name {
+    } else if name.is_some() {
         return Some(quote! {
-            #name => {
+            #pattern => {
                 result = Some(#ident::#variant_ident);
                 break;
             },
@@ -62,7 +71,183 @@ fn create_variant(
     None
 }
 
+/// Bodies of the fallback branches of start and empty events, which capture the element in the
+/// `other` variant or skip it, also for elements of other namespaces than the target namespace.
+/// A `String` captures the local name of the element, the namespace is not kept. Otherwise the
+/// variant type reads the element from its start event like `RawXml`, the tag is not known
+/// statically.
+fn create_other_variant(container: &Container, empty: bool) -> proc_macro2::TokenStream {
+    let ident = &container.original.ident;
+    let skip = if empty {
+        quote! {}
+    } else {
+        quote! {
+            _raxb::de::skip_element(reader, false)?;
+        }
+    };
+    let Some(variant) = container.enum_variants.iter().find(|v| v.other) else {
+        return skip;
+    };
+    let variant_ident = variant.ident;
+    let ty = variant.ty.unwrap();
+    if get_built_in_type(ty).is_string() {
+        quote! {
+            let name = String::from_utf8(e.local_name().as_ref().to_vec())?;
+            #skip
+            result = Some(#ident::#variant_ident(name));
+            break;
+        }
+    } else {
+        quote! {
+            let value = <#ty as XmlDeserialize>::xml_deserialize_with_start(
+                reader,
+                target_ns,
+                &[],
+                &e,
+                #empty,
+            )?;
+            result = Some(#ident::#variant_ident(value));
+            break;
+        }
+    }
+}
+
+fn create_enum_err(container: &Container) -> LitByteStr {
+    let enum_err = container
+        .enum_variants
+        .iter()
+        .filter_map(|v| v.name.as_ref())
+        .map(|v| format!("'{}'", String::from_utf8(v.value()).unwrap()))
+        .collect::<Vec<String>>()
+        .join("|");
+    LitByteStr::new(enum_err.as_bytes(), Span::call_site())
+}
+
+/// Selects the variant by the resolved `xsi:type` attribute of the current element and lets
+/// the variant type deserialize the element itself.
+fn xsi_type_impl_block(container: Container) -> proc_macro2::TokenStream {
+    let ident = &container.original.ident;
+    let root_impl = create_root_impl(&container);
+    let tns_impl = create_tns_impl(&container);
+    let enum_err = create_enum_err(&container);
+    let ns_check = if let Some((_, ns_val)) = container.tns.as_ref() {
+        let ns_token = match ns_val {
+            NsValue::LitByte(lit) => quote! { #lit },
+            NsValue::ExprPath(path) => quote! { #path },
+        };
+        if container.tns_aliases.is_empty() {
+            quote! { type_ns.as_deref() == Some(#ns_token) }
+        } else {
+            quote! { type_ns.as_deref().is_some_and(|ns| _raxb::de::is_target_ns::<Self>(#ns_token, ns)) }
+        }
+    } else {
+        quote! { true }
+    };
+    let ns_check = &ns_check;
+    // with the start tag the variant type gets it too, e.g. `RawXml` keeps the original name
+    let branches = |start: bool| {
+        let (method, element) = if start {
+            (quote! { xml_deserialize_with_start }, quote! { start })
+        } else {
+            (quote! { xml_deserialize }, quote! { attributes })
+        };
+        container.enum_variants.iter().filter_map(move |variant| {
+            let name = create_name_pattern(variant.name.as_ref()?, &variant.aliases);
+            let variant_ident = variant.ident;
+            let value = if let Some(ty) = variant.ty {
+                quote! {
+                    Self::#variant_ident(<#ty as XmlDeserialize>::#method(
+                        reader,
+                        target_ns,
+                        tag,
+                        #element,
+                        is_empty,
+                    )?)
+                }
+            } else {
+                quote! {
+                    {
+                        _raxb::de::skip_element(reader, is_empty)?;
+                        Self::#variant_ident
+                    }
+                }
+            };
+            Some(quote! {
+                #name if #ns_check => {
+                    return Ok(#value);
+                }
+            })
+        })
+    };
+    let branches_with_start = branches(true);
+    let branches = branches(false);
+    let generics = create_generics(&container, Derive::Deserialize);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals, unused_attributes, unused_qualifications)]
+        const _: () = {
+            #[allow(unused_extern_crates, clippy::useless_attribute, clippy::manual_flatten, clippy::single_match)]
+            extern crate raxb as _raxb;
+
+            use _raxb::{
+                de::{XmlDeserialize, XmlDeserializeError, XmlDeserializeResult},
+                quick_xml::{events::{attributes::Attributes, BytesStart}, NsReader},
+                ty::{XmlTag, XmlTargetNs, S},
+            };
+            #[automatically_derived]
+            impl #impl_generics XmlDeserialize for #ident #type_generics #where_clause {
+                fn xml_deserialize<R: std::io::BufRead>(
+                    reader: &mut NsReader<R>,
+                    target_ns: XmlTag,
+                    tag: XmlTargetNs,
+                    attributes: Attributes,
+                    is_empty: bool,
+                ) -> XmlDeserializeResult<Self> {
+                    let Some((type_ns, type_name)) = _raxb::de::xsi_type(reader, attributes.clone())? else {
+                        return Err(XmlDeserializeError::MissingVariant(S(#enum_err)));
+                    };
+                    match type_name.as_slice() {
+                        #(#branches)*
+                        _ => {}
+                    }
+                    Err(XmlDeserializeError::UnknownVariant(
+                        String::from_utf8_lossy(&type_name).to_string(),
+                        S(#enum_err),
+                    ))
+                }
+
+                fn xml_deserialize_with_start<R: std::io::BufRead>(
+                    reader: &mut NsReader<R>,
+                    target_ns: XmlTag,
+                    tag: XmlTargetNs,
+                    start: &BytesStart,
+                    is_empty: bool,
+                ) -> XmlDeserializeResult<Self> {
+                    let Some((type_ns, type_name)) = _raxb::de::xsi_type(reader, start.attributes())? else {
+                        return Err(XmlDeserializeError::MissingVariant(S(#enum_err)));
+                    };
+                    match type_name.as_slice() {
+                        #(#branches_with_start)*
+                        _ => {}
+                    }
+                    Err(XmlDeserializeError::UnknownVariant(
+                        String::from_utf8_lossy(&type_name).to_string(),
+                        S(#enum_err),
+                    ))
+                }
+
+                #root_impl
+                #tns_impl
+            }
+        };
+    }
+}
+
 pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
+    if container.tag.is_some() {
+        return xsi_type_impl_block(container);
+    }
     let ident = &container.original.ident;
     let ident_str = ident.to_string();
     let tns_impl = create_tns_impl(&container);
@@ -82,15 +267,11 @@ pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
     let unqualified_variants = variants.iter();
     let unqualified_empty_variants = empty_variants.iter();
 
-    let enum_err = container
-        .enum_variants
-        .iter()
-        .filter_map(|v| v.name.as_ref())
-        .map(|v| format!("'{}'", String::from_utf8(v.value()).unwrap()))
-        .collect::<Vec<String>>()
-        .join("|");
-    let enum_err = LitByteStr::new(enum_err.as_bytes(), Span::call_site());
-    let (impl_generics, type_generics, where_clause) = container.original.generics.split_for_impl();
+    let other_start = create_other_variant(&container, false);
+    let other_empty = create_other_variant(&container, true);
+    let enum_err = create_enum_err(&container);
+    let generics = create_generics(&container, Derive::Deserialize);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
     let trace_event = |event_type: &'static str| {
         trace(quote! {
             if tag.is_empty() {
@@ -146,41 +327,55 @@ pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
                     attributes: _raxb::quick_xml::events::attributes::Attributes,
                     is_empty: bool,
                 ) -> _raxb::de::XmlDeserializeResult<Self> {
-                    let target_ns = Self::target_ns().unwrap_or(target_ns);
+                    let target_ns = _raxb::de::resolve_target_ns::<Self, R>(reader, target_ns, None);
                     #trace_enter_enum
-                    let mut result = Option::<#ident>::None;
+                    // the variants are read inside the element `tag`, if there is one
+                    let _depth = if tag.is_empty() {
+                        None
+                    } else {
+                        Some(_raxb::de::enter_element()?)
+                    };
+                    let mut result = Option::<Self>::None;
                     let mut buf = Vec::<u8>::new();
                     loop {
-                        match reader.read_resolved_event_into(&mut buf)? {
+                        let event = reader.read_resolved_event_into(&mut buf)?;
+                        _raxb::de::check_event(&event.1)?;
+                        match event {
                             (ResolveResult::Unbound, Event::Start(e)) => {
                                 match e.local_name().as_ref() {
                                     #(#unqualified_variants)*
                                     _ => {
-                                        let mut buf = Vec::<u8>::new();
-                                        reader.read_to_end_into(e.name(), &mut buf)?;
+                                        #other_start
                                     }
                                 }
                             }
                             (ResolveResult::Unbound, Event::Empty(e)) => {
                                 match e.local_name().as_ref() {
                                     #(#unqualified_empty_variants)*
-                                    _ => {}
+                                    _ => {
+                                        #other_empty
+                                    }
                                 }
                             }
-                            (ResolveResult::Bound(ns), Event::Start(e)) => if ns.as_ref() == target_ns {
+                            (ResolveResult::Bound(ns), Event::Start(e)) => if _raxb::de::is_target_ns::<Self>(target_ns, ns.as_ref()) {
                                 match e.local_name().as_ref() {
                                     #(#qualified_variants)*
                                     _ => {
-                                        let mut buf = Vec::<u8>::new();
-                                        reader.read_to_end_into(e.name(), &mut buf)?;
+                                        #other_start
                                     }
                                 }
+                            } else {
+                                #other_start
                             }
-                            (ResolveResult::Bound(ns), Event::Empty(e)) => if ns.as_ref() == target_ns {
+                            (ResolveResult::Bound(ns), Event::Empty(e)) => if _raxb::de::is_target_ns::<Self>(target_ns, ns.as_ref()) {
                                 match e.local_name().as_ref() {
                                     #(#qualified_empty_variants)*
-                                    _ => {}
+                                    _ => {
+                                        #other_empty
+                                    }
                                 }
+                            } else {
+                                #other_empty
                             }
                             (_, Event::Eof) => {
                                 break;
@@ -193,8 +388,10 @@ pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
                             #trace_leave_enum
                         } else {
                             loop {
-                                match reader.read_resolved_event_into(&mut buf)? {
-                                    (ResolveResult::Bound(ns), Event::End(e)) => if ns.as_ref() == target_ns && e.local_name().as_ref() == tag {
+                                let event = reader.read_resolved_event_into(&mut buf)?;
+                                _raxb::de::check_event(&event.1)?;
+                                match event {
+                                    (ResolveResult::Bound(ns), Event::End(e)) => if _raxb::de::is_target_ns::<Self>(target_ns, ns.as_ref()) && e.local_name().as_ref() == tag {
                                         #trace_leave_enum
                                         break;
                                     },
@@ -1,7 +1,91 @@
 use quote::quote;
 
-use crate::container::Container;
+use crate::{
+    container::{Container, Derive},
+    utils::{create_generics, get_built_in_type},
+};
 
-pub fn impl_block(_container: Container) -> proc_macro2::TokenStream {
-    quote! {}
+/// Newtype struct with `#[raxb(transparent)]`, deserialized like its inner type. Newtypes of
+/// built-in types also get a `FromStr` impl to be usable as attributes and text.
+pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
+    let ident = &container.original.ident;
+    let inner = &container.struct_fields[0].original.ty;
+    let generics = create_generics(&container, Derive::Deserialize);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let built_in_type = get_built_in_type(inner);
+    let from_str_impl = if built_in_type.is_unknown() {
+        quote! {}
+    } else {
+        let value = if built_in_type.is_string() {
+            quote! { value.to_string() }
+        } else {
+            quote! { value.parse()? }
+        };
+        quote! {
+            #[automatically_derived]
+            impl ::core::str::FromStr for #ident {
+                type Err = XmlDeserializeError;
+
+                fn from_str(value: &str) -> XmlDeserializeResult<Self> {
+                    Ok(Self(#value))
+                }
+            }
+        }
+    };
+    quote! {
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals, unused_attributes, unused_qualifications)]
+        const _: () = {
+            #[allow(unused_extern_crates, clippy::useless_attribute)]
+            extern crate raxb as _raxb;
+
+            use _raxb::{
+                de::{XmlDeserialize, XmlDeserializeError, XmlDeserializeResult},
+                quick_xml::{
+                    events::{attributes::Attributes, BytesStart},
+                    NsReader,
+                },
+                ty::{XmlTag, XmlTargetNs},
+            };
+            #from_str_impl
+            #[automatically_derived]
+            impl #impl_generics XmlDeserialize for #ident #type_generics #where_clause {
+                fn is_enum() -> bool {
+                    <#inner as XmlDeserialize>::is_enum()
+                }
+
+                fn root() -> Option<XmlTag> {
+                    <#inner as XmlDeserialize>::root()
+                }
+
+                fn target_ns() -> Option<XmlTargetNs> {
+                    <#inner as XmlDeserialize>::target_ns()
+                }
+
+                fn target_ns_aliases() -> &'static [XmlTargetNs] {
+                    <#inner as XmlDeserialize>::target_ns_aliases()
+                }
+
+                fn xml_deserialize<R: std::io::BufRead>(
+                    reader: &mut NsReader<R>,
+                    target_ns: XmlTag,
+                    tag: XmlTargetNs,
+                    attributes: Attributes,
+                    is_empty: bool,
+                ) -> XmlDeserializeResult<Self> {
+                    <#inner as XmlDeserialize>::xml_deserialize(reader, target_ns, tag, attributes, is_empty).map(Self)
+                }
+
+                fn xml_deserialize_with_start<R: std::io::BufRead>(
+                    reader: &mut NsReader<R>,
+                    target_ns: XmlTag,
+                    tag: XmlTargetNs,
+                    start: &BytesStart,
+                    is_empty: bool,
+                ) -> XmlDeserializeResult<Self> {
+                    <#inner as XmlDeserialize>::xml_deserialize_with_start(reader, target_ns, tag, start, is_empty).map(Self)
+                }
+            }
+        };
+    }
 }
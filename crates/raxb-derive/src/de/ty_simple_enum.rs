@@ -0,0 +1,73 @@
+use quote::quote;
+
+use crate::{
+    container::{Container, Derive},
+    utils::create_generics,
+};
+
+pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
+    let ident = &container.original.ident;
+    let values = container
+        .enum_variants
+        .iter()
+        .filter_map(|v| v.value.as_ref().map(|value| value.value()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let expected = syn::LitByteStr::new(values.as_bytes(), ident.span());
+    let branches = container.enum_variants.iter().filter_map(|v| {
+        let variant = v.ident;
+        let value = v.value.as_ref()?;
+        Some(quote! {
+            #value => Ok(Self::#variant),
+        })
+    });
+    let fallback = if let Some(other) = container.enum_variants.iter().find(|v| v.other) {
+        let variant = other.ident;
+        quote! {
+            _ => Ok(Self::#variant(value.into())),
+        }
+    } else {
+        quote! {
+            _ => Err(XmlDeserializeError::UnknownVariant(value.to_string(), _raxb::ty::S(#expected))),
+        }
+    };
+    let generics = create_generics(&container, Derive::Deserialize);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals, unused_attributes, unused_qualifications)]
+        const _: () = {
+            #[allow(unused_extern_crates, clippy::useless_attribute)]
+            extern crate raxb as _raxb;
+
+            use _raxb::{
+                de::{XmlDeserialize, XmlDeserializeError, XmlDeserializeResult},
+                quick_xml::{events::attributes::Attributes, NsReader},
+                ty::{XmlTag, XmlTargetNs},
+            };
+            #[automatically_derived]
+            impl #impl_generics ::core::str::FromStr for #ident #type_generics #where_clause {
+                type Err = XmlDeserializeError;
+
+                fn from_str(value: &str) -> XmlDeserializeResult<Self> {
+                    match value {
+                        #(#branches)*
+                        #fallback
+                    }
+                }
+            }
+            #[automatically_derived]
+            impl #impl_generics XmlDeserialize for #ident #type_generics #where_clause {
+                fn xml_deserialize<R: std::io::BufRead>(
+                    reader: &mut NsReader<R>,
+                    _target_ns: XmlTag,
+                    _tag: XmlTargetNs,
+                    _attributes: Attributes,
+                    is_empty: bool,
+                ) -> XmlDeserializeResult<Self> {
+                    _raxb::de::read_text_content(reader, is_empty)?.trim().parse()
+                }
+            }
+        };
+    }
+}
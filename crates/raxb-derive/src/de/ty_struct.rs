@@ -1,20 +1,95 @@
-use quote::quote;
-use syn::LitByteStr;
+use quote::{quote, quote_spanned};
+use syn::{spanned::Spanned, LitByteStr};
 
 use crate::{
-    container::{Container, EleType, FieldsSummary, StructField},
-    utils::{create_root_impl, create_tns_impl, trace},
+    container::{Container, Derive, EleType, FieldsSummary, Generic, StructField},
+    utils::{
+        create_constraint_checks, create_generics, create_root_impl, create_tns_impl,
+        get_field_name, is_qname, trace,
+    },
 };
 
-fn create_return_value(fields: &[StructField]) -> proc_macro2::TokenStream {
+/// With `container_default`, missing fields are taken from `__raxb_default`.
+fn create_return_value(
+    fields: &[StructField],
+    container_default: bool,
+) -> proc_macro2::TokenStream {
     let branch = fields.iter().map(|f| {
         let ident = f.original.ident.as_ref().unwrap();
         let field_name: LitByteStr = syn::parse_str(&format!("b\"{ident}\"")).unwrap();
-        if f.default {
+        let name = get_field_name(f);
+        let collection = quote! {
+            _raxb::collection::XmlCollection::from_items(#ident)
+                .map_err(|facet| _raxb::facet::Violation::new(#name, facet))?
+        };
+        if matches!(f.ty, EleType::Tag) {
+            if is_qname(&f.original.ty) {
+                quote! {
+                    #ident: __raxb_tag_name,
+                }
+            } else {
+                quote! {
+                    #ident: std::str::from_utf8(_raxb::quick_xml::name::QName(__raxb_name).local_name().into_inner())?
+                        .parse()
+                        .map_err(XmlDeserializeError::custom)?,
+                }
+            }
+        } else if matches!(f.ty, EleType::AnyAttr) {
+            quote! {
+                #ident: __raxb_any_attr,
+            }
+        } else if matches!(f.ty, EleType::Skip) {
+            let ty = &f.original.ty;
+            if let Some(default_with) = f.default_with.as_ref() {
+                quote! {
+                    #ident: #default_with(),
+                }
+            } else if container_default {
+                quote! {
+                    #ident: __raxb_default.#ident,
+                }
+            } else {
+                quote_spanned! {ty.span()=>
+                    #ident: <#ty as ::core::default::Default>::default(),
+                }
+            }
+        } else if let Some(default_with) = f.default_with.as_ref() {
+            match f.generic {
+                Generic::Vec(_) => quote! {
+                    #ident: if #ident.is_empty() { #default_with() } else { #collection },
+                },
+                Generic::Opt(_) => quote! {
+                    #ident: #ident.or_else(#default_with),
+                },
+                Generic::Map(..) => quote! {
+                    #ident: if #ident.is_empty() { #default_with() } else { #ident },
+                },
+                Generic::None => quote! {
+                    #ident: #ident.unwrap_or_else(#default_with),
+                },
+            }
+        }
+        else if f.default && f.is_required() {
             quote! {
                 #ident: #ident.unwrap_or_default(),
             }
         }
+        else if container_default {
+            match f.generic {
+                Generic::Vec(_) => quote! {
+                    #ident: if #ident.is_empty() { __raxb_default.#ident } else { #collection },
+                },
+                Generic::Opt(_) => quote! {
+                    #ident: #ident.or(__raxb_default.#ident),
+                },
+                Generic::Map(..) => quote! {
+                    #ident: if #ident.is_empty() { __raxb_default.#ident } else { #ident },
+                },
+                Generic::None => quote! {
+                    #ident: #ident.unwrap_or(__raxb_default.#ident),
+                },
+            }
+        }
         else if f.is_required() {
             if matches!(f.ty, EleType::Attr) {
                 quote! {
@@ -25,6 +100,10 @@ fn create_return_value(fields: &[StructField]) -> proc_macro2::TokenStream {
                     #ident: #ident.ok_or(_raxb::de::XmlDeserializeError::MissingElement(_raxb::ty::S(#field_name)))?,
                 }
             }
+        } else if matches!(f.generic, Generic::Vec(_)) {
+            quote! {
+                #ident: #collection,
+            }
         } else {
             quote! {
                 #ident,
@@ -48,11 +127,33 @@ pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
     } else {
         super::child::create_assignments(&container)
     };
-    let return_value = create_return_value(&container.struct_fields);
+    let return_value = create_return_value(&container.struct_fields, container.default);
+    let default_value = if container.default {
+        quote! {
+            let __raxb_default = <Self as ::core::default::Default>::default();
+        }
+    } else {
+        quote! {}
+    };
+    // resolved before the children are read, while the bindings of the element are in scope
+    let tag_name = container
+        .struct_fields
+        .iter()
+        .any(|f| matches!(f.ty, EleType::Tag) && is_qname(&f.original.ty))
+        .then(|| {
+            quote! {
+                let __raxb_tag_name = _raxb::de::element_qname(reader, __raxb_name)?;
+            }
+        });
+    let constraint_checks = create_constraint_checks(&container, quote! { __raxb_value });
+    let generics = create_generics(&container, Derive::Deserialize);
     let summary = FieldsSummary::from_fields(container.struct_fields);
     let fields_init = create_fields_init(&summary);
-    let attr_assignments = super::attrs::create_assignments(&summary);
-    let (impl_generics, type_generics, where_clause) = container.original.generics.split_for_impl();
+    let attr_assignments = super::attrs::create_assignments(
+        &summary,
+        container.tns.as_ref().map(|(prefix, _)| prefix),
+    );
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
     let trace_enter_struct = trace(quote! {
         if target_ns.is_empty() {
             debug!("Enter struct '{}' with tag '{}'", #ident_str, std::str::from_utf8(tag).unwrap());
@@ -69,32 +170,67 @@ pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
             use _raxb::{
                 de::{XmlDeserialize, XmlDeserializeError, XmlDeserializeResult},
                 quick_xml::{
-                    events::{attributes::Attributes, Event},
+                    events::{attributes::Attributes, BytesStart, Event},
                     name::ResolveResult,
                     NsReader,
                 },
                 ty::{XmlTag, XmlTargetNs, S},
             };
             #[automatically_derived]
-            impl #impl_generics XmlDeserialize for #ident #type_generics #where_clause {
-                fn xml_deserialize<R: std::io::BufRead>(
+            impl #impl_generics #ident #type_generics #where_clause {
+                fn __raxb_xml_deserialize<R: std::io::BufRead>(
                     reader: &mut NsReader<R>,
                     target_ns: XmlTag,
-                    tag: XmlTargetNs,
+                    tag: &[u8],
+                    __raxb_name: &[u8],
                     attributes: Attributes,
                     is_empty: bool,
                 ) -> XmlDeserializeResult<Self> {
-                    let target_ns = Self::target_ns().unwrap_or(target_ns);
                     #trace_enter_struct
+                    let _depth = _raxb::de::enter_element()?;
+                    #tag_name
 
                     #fields_init
+                    let trim = _raxb::de::is_text_trimmed(reader, attributes.clone());
+                    let previous_trim = _raxb::de::set_trim_text(reader, trim);
                     #attr_assignments
                     #field_assignments
+                    _raxb::de::set_trim_text(reader, previous_trim);
 
-                    Ok(Self {
+                    #default_value
+                    let __raxb_value = Self {
                         #return_value
-                    })
+                    };
+                    #constraint_checks
+                    Ok(__raxb_value)
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics XmlDeserialize for #ident #type_generics #where_clause {
+                fn xml_deserialize<R: std::io::BufRead>(
+                    reader: &mut NsReader<R>,
+                    target_ns: XmlTag,
+                    tag: XmlTargetNs,
+                    attributes: Attributes,
+                    is_empty: bool,
+                ) -> XmlDeserializeResult<Self> {
+                    let target_ns = _raxb::de::resolve_target_ns::<Self, R>(reader, target_ns, None);
+                    Self::__raxb_xml_deserialize(reader, target_ns, tag, tag, attributes, is_empty)
                 }
+
+                fn xml_deserialize_with_start<R: std::io::BufRead>(
+                    reader: &mut NsReader<R>,
+                    target_ns: XmlTag,
+                    tag: XmlTargetNs,
+                    start: &BytesStart,
+                    is_empty: bool,
+                ) -> XmlDeserializeResult<Self> {
+                    let target_ns = _raxb::de::resolve_target_ns::<Self, R>(reader, target_ns, Some(start));
+                    let tag = if tag.is_empty() { start.local_name().into_inner() } else { tag };
+                    Self::__raxb_xml_deserialize(reader, target_ns, tag, start.name().into_inner(), start.attributes(), is_empty)
+                }
+
                 #root_impl
                 #tns_impl
             }
@@ -28,11 +28,15 @@ use ser::xml_serialize_impl_block;
 #[proc_macro_derive(XmlDeserialize, attributes(raxb, xml))]
 pub fn derive_xml_deserialize(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    xml_deserialize_impl_block(input).into()
+    xml_deserialize_impl_block(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
 #[proc_macro_derive(XmlSerialize, attributes(raxb, xml))]
 pub fn derive_xml_serialize(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    xml_serialize_impl_block(input).into()
+    xml_serialize_impl_block(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
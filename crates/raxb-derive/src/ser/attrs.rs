@@ -1,8 +1,8 @@
 use quote::quote;
 
 use crate::{
-    container::{BuiltInConstType, Container, EleType, Generic},
-    utils::{get_built_in_const_type, get_built_in_type},
+    container::{BuiltInConstType, Container, EleType, Generic, StructField},
+    utils::{create_skip_serializing_if, get_built_in_const_type, get_built_in_type},
 };
 
 fn create_attribute_value_impl(ty: &syn::Type) -> proc_macro2::TokenStream {
@@ -17,63 +17,97 @@ fn create_attribute_value_impl(ty: &syn::Type) -> proc_macro2::TokenStream {
     }
 }
 
-pub fn create_attribute_blocks(container: &Container) -> Vec<proc_macro2::TokenStream> {
-    let mut blocks: Vec<proc_macro2::TokenStream> = Vec::new();
-    for f in container
+fn attribute_fields<'a, 'b>(
+    container: &'b Container<'a>,
+) -> impl Iterator<Item = (&'b StructField<'a>, String)> {
+    container
         .struct_fields
         .iter()
         .filter(|sf| sf.name.is_some() && matches!(sf.ty, EleType::Attr))
-    {
-        let name = f.name.as_ref().unwrap();
-        let ident = f.original.ident.as_ref().unwrap();
-        let v = name.value();
-        let name =
-            f.ns.as_ref()
-                .map(|ns| ns.value())
-                .map(|ns| {
-                    format!(
-                        "{}:{}",
-                        std::str::from_utf8(&ns).unwrap(),
-                        std::str::from_utf8(&v).unwrap()
-                    )
-                })
-                .unwrap_or(String::from_utf8(v).unwrap());
-        let ty = &f.original.ty;
-        match f.generic {
-            Generic::Vec(_) => {
-                eprintln!("WARNING: Vec<T> cannot be used for attributes, use Option<T> instead");
+        .map(|f| {
+            let v = f.name.as_ref().unwrap().value();
+            let name =
+                f.ns.as_ref()
+                    .map(|ns| ns.value())
+                    .map(|ns| {
+                        format!(
+                            "{}:{}",
+                            std::str::from_utf8(&ns).unwrap(),
+                            std::str::from_utf8(&v).unwrap()
+                        )
+                    })
+                    .unwrap_or(String::from_utf8(v).unwrap());
+            (f, name)
+        })
+}
+
+/// Qualified names of all attributes written by the struct itself.
+pub fn attribute_names(container: &Container) -> Vec<String> {
+    attribute_fields(container).map(|(_, name)| name).collect()
+}
+
+pub fn create_attribute_blocks(container: &Container) -> Vec<proc_macro2::TokenStream> {
+    attribute_fields(container)
+        .filter_map(|(f, name)| {
+            create_attribute_block(f, &name).map(|block| create_skip_serializing_if(f, block))
+        })
+        .collect()
+}
+
+fn create_attribute_block(f: &StructField, name: &str) -> Option<proc_macro2::TokenStream> {
+    let ident = f.original.ident.as_ref().unwrap();
+    let ty = &f.original.ty;
+    if let Some(serialize_with) = f.serialize_with.as_ref() {
+        let write_value = quote! {
+            let value = #serialize_with(value).map_err(_raxb::ser::XmlSerializeError::custom)?;
+            el_writer = el_writer.with_attribute((#name, value.as_str()));
+        };
+        return Some(if matches!(f.generic, Generic::Opt(_)) {
+            quote! {
+                if let Some(value) = self.#ident.as_ref() {
+                    #write_value
+                }
             }
-            Generic::Opt(ty) => {
-                let attribute_value_impl = create_attribute_value_impl(ty);
-                blocks.push(quote! {
-                    if let Some(value) = self.#ident.as_ref() {
-                        el_writer = el_writer.with_attribute((#name, {
-                            #attribute_value_impl
-                        }));
-                    }
-                })
+        } else {
+            quote! {
+                let value = &self.#ident;
+                #write_value
             }
-            Generic::None => {
-                if f.value.is_some()
-                    && matches!(get_built_in_const_type(ty), BuiltInConstType::ConstStr)
-                {
-                    let const_val = f.value.as_ref().unwrap();
-                    let v = const_val.value();
-                    let value = v.as_str();
-                    blocks.push(quote! {
-                        el_writer = el_writer.with_attribute((#name, #value));
-                    })
-                } else {
-                    let attribute_value_impl = create_attribute_value_impl(ty);
-                    blocks.push(quote! {
-                        el_writer = el_writer.with_attribute((#name, {
-                            let value = &self.#ident;
-                            #attribute_value_impl
-                        }));
-                    })
+        });
+    }
+    match f.generic {
+        // rejected by `Container::validate` and `StructField::from_ast`
+        Generic::Vec(_) | Generic::Map(..) => None,
+        Generic::Opt(ty) => {
+            let attribute_value_impl = create_attribute_value_impl(ty);
+            Some(quote! {
+                if let Some(value) = self.#ident.as_ref() {
+                    el_writer = el_writer.with_attribute((#name, {
+                        #attribute_value_impl
+                    }));
                 }
+            })
+        }
+        Generic::None => {
+            if let Some(const_val) = f
+                .value
+                .as_ref()
+                .filter(|_| matches!(get_built_in_const_type(ty), BuiltInConstType::ConstStr))
+            {
+                let v = const_val.value();
+                let value = v.as_str();
+                Some(quote! {
+                    el_writer = el_writer.with_attribute((#name, #value));
+                })
+            } else {
+                let attribute_value_impl = create_attribute_value_impl(ty);
+                Some(quote! {
+                    el_writer = el_writer.with_attribute((#name, {
+                        let value = &self.#ident;
+                        #attribute_value_impl
+                    }));
+                })
             }
         }
     }
-    blocks
 }
@@ -1,8 +1,8 @@
 use quote::quote;
 
 use crate::{
-    container::{Container, EleType, Generic},
-    utils::get_built_in_type,
+    container::{Container, EleType, Generic, MapKey, StructField},
+    utils::{create_skip_serializing_if, get_built_in_type},
 };
 
 fn create_write_text_value(name: &str) -> proc_macro2::TokenStream {
@@ -19,143 +19,292 @@ fn create_write_any_builtin_value(name: &str) -> proc_macro2::TokenStream {
     }
 }
 
+/// Writes an element for every entry of a map, with the key as attribute or element name.
+fn create_map_block(f: &StructField, ty: &syn::Type) -> proc_macro2::TokenStream {
+    let ident = f.original.ident.as_ref().unwrap();
+    let ns =
+        f.ns.as_ref()
+            .map(|ns| String::from_utf8(ns.value()).unwrap());
+    let (name, key_attr) = match f.key.as_ref().unwrap() {
+        MapKey::Attr(key) => {
+            let name = String::from_utf8(f.name.as_ref().unwrap().value()).unwrap();
+            let name = match ns {
+                Some(ns) => format!("{ns}:{name}"),
+                None => name,
+            };
+            let key = String::from_utf8(key.value()).unwrap();
+            (quote! { #name }, Some(quote! { (#key, key.as_str()) }))
+        }
+        MapKey::Name => {
+            let name = match ns {
+                Some(ns) => quote! { format!("{}:{}", #ns, key).as_str() },
+                None => quote! { key.as_str() },
+            };
+            (name, None)
+        }
+    };
+    let with_key = key_attr
+        .as_ref()
+        .map(|key_attr| quote! { .with_attribute(#key_attr) });
+    let write_text = |text: proc_macro2::TokenStream| {
+        quote! {
+            writer.create_element(#name)
+                #with_key
+                .write_text_content(_raxb::quick_xml::events::BytesText::from_escaped(_raxb::quick_xml::escape::escape(#text)))?;
+        }
+    };
+    let write_value = if let Some(serialize_with) = f.serialize_with.as_ref() {
+        let write_text = write_text(quote! { &value });
+        quote! {
+            let value = #serialize_with(value).map_err(_raxb::ser::XmlSerializeError::custom)?;
+            #write_text
+        }
+    } else {
+        let built_in_type = get_built_in_type(ty);
+        if built_in_type.is_string() {
+            write_text(quote! { value })
+        } else if built_in_type.is_unknown() {
+            let key_attr = key_attr.into_iter();
+            quote! {
+                value.xml_serialize_with_attributes(#name, &[#(#key_attr),*], writer)?;
+            }
+        } else {
+            write_text(quote! { &value.to_string() })
+        }
+    };
+    let check_key = matches!(f.key, Some(MapKey::Name)).then(|| {
+        quote! {
+            _raxb::ser::check_name(&key)?;
+        }
+    });
+    quote! {
+        for (key, value) in self.#ident.iter() {
+            let key = key.to_string();
+            #check_key
+            #write_value
+        }
+    }
+}
+
 pub fn create_child_blocks(container: &Container) -> Vec<proc_macro2::TokenStream> {
-    let mut blocks: Vec<proc_macro2::TokenStream> = Vec::new();
-    for f in container.struct_fields.iter().filter(|sf| {
-        sf.name.is_some() && matches!(sf.ty, EleType::Child | EleType::SelfClosedChild)
-    }) {
-        let ident = f.original.ident.as_ref().unwrap();
+    container
+        .struct_fields
+        .iter()
+        .filter(|sf| {
+            (sf.name.is_some() || sf.key.is_some())
+                && matches!(sf.ty, EleType::Child | EleType::SelfClosedChild)
+        })
+        .filter_map(|f| {
+            create_child_block(f)
+                .map(|block| create_wrapper_block(f, block))
+                .map(|block| create_skip_serializing_if(f, block))
+        })
+        .collect()
+}
 
-        let unqualified_name = f.name.as_ref().unwrap();
-        let unqualified_name_buf = unqualified_name.value();
-        let unqualified_name = std::str::from_utf8(&unqualified_name_buf).unwrap();
+/// Writes the items of a repeated child inside the wrapper element, empty if there are none.
+fn create_wrapper_block(
+    f: &StructField,
+    block: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let Some(wrapper) = f.wrapper.as_ref() else {
+        return block;
+    };
+    let ident = f.original.ident.as_ref().unwrap();
+    let name_buf = wrapper.name.value();
+    let name = std::str::from_utf8(&name_buf).unwrap();
+    let name = if let Some(ns) = wrapper.ns.as_ref() {
+        let ns_buf = ns.value();
+        let ns = std::str::from_utf8(&ns_buf).unwrap();
+        format!("{ns}:{name}")
+    } else {
+        name.to_string()
+    };
+    let write_items = quote! {
+        writer
+            .create_element(#name)
+            .write_inner_content::<_, _raxb::ser::XmlSerializeError>(|writer| {
+                #block
+                Ok(())
+            })?;
+    };
+    if wrapper.skip_empty {
+        quote! {
+            if self.#ident.iter().next().is_some() {
+                #write_items
+            }
+        }
+    } else {
+        quote! {
+            if self.#ident.iter().next().is_some() {
+                #write_items
+            } else {
+                writer.create_element(#name).write_empty()?;
+            }
+        }
+    }
+}
 
-        let combined_name = if let Some(ns) = f.ns.as_ref() {
-            let ns_buf = ns.value();
-            let ns = std::str::from_utf8(&ns_buf).unwrap();
-            std::borrow::Cow::Owned(format!("{ns}:{unqualified_name}"))
-        } else {
-            std::borrow::Cow::Borrowed(unqualified_name)
+fn create_child_block(f: &StructField) -> Option<proc_macro2::TokenStream> {
+    let ident = f.original.ident.as_ref().unwrap();
+    if let Generic::Map(_, ty) = f.generic {
+        return Some(create_map_block(f, ty));
+    }
+
+    let unqualified_name = f.name.as_ref().unwrap();
+    let unqualified_name_buf = unqualified_name.value();
+    let unqualified_name = std::str::from_utf8(&unqualified_name_buf).unwrap();
+
+    let combined_name = if let Some(ns) = f.ns.as_ref() {
+        let ns_buf = ns.value();
+        let ns = std::str::from_utf8(&ns_buf).unwrap();
+        std::borrow::Cow::Owned(format!("{ns}:{unqualified_name}"))
+    } else {
+        std::borrow::Cow::Borrowed(unqualified_name)
+    };
+    let name = combined_name.as_ref();
+    let ty = &f.original.ty;
+    let is_sfc = matches!(f.ty, EleType::SelfClosedChild);
+    if let Some(serialize_with) = f.serialize_with.as_ref() {
+        let write_value = create_write_text_value(name);
+        let write_value = quote! {
+            let value = #serialize_with(value).map_err(_raxb::ser::XmlSerializeError::custom)?;
+            #write_value
         };
-        let name = combined_name.as_ref();
-        let ty = &f.original.ty;
-        let is_sfc = matches!(f.ty, EleType::SelfClosedChild);
-        match f.generic {
-            Generic::Vec(ty) => {
-                let built_in_type = get_built_in_type(ty);
-                if built_in_type.is_string() {
-                    let write_value = create_write_text_value(name);
-                    blocks.push(quote! {
+        return Some(match f.generic {
+            Generic::Vec(_) => quote! {
+                for value in self.#ident.iter() {
+                    #write_value
+                }
+            },
+            Generic::Opt(_) => quote! {
+                if let Some(value) = self.#ident.as_ref() {
+                    #write_value
+                }
+            },
+            Generic::None => quote! {
+                let value = &self.#ident;
+                #write_value
+            },
+            Generic::Map(..) => unreachable!("maps are written by create_map_block"),
+        });
+    }
+    match f.generic {
+        Generic::Map(..) => unreachable!("maps are written by create_map_block"),
+        Generic::Vec(ty) => {
+            let built_in_type = get_built_in_type(ty);
+            if built_in_type.is_string() {
+                let write_value = create_write_text_value(name);
+                return Some(quote! {
+                    for value in self.#ident.iter() {
+                        #write_value
+                    }
+                });
+            } else if built_in_type.is_bool() {
+                if is_sfc {
+                    return Some(quote! {
                         for value in self.#ident.iter() {
-                            #write_value
+                            if *value {
+                                writer.create_element(#name)
+                                    .write_empty()?;
+                            }
                         }
                     });
-                } else if built_in_type.is_bool() {
-                    if is_sfc {
-                        blocks.push(quote! {
-                            for value in self.#ident.iter() {
-                                if value {
-                                    writer.create_element(#name)
-                                        .write_empty()?;
-                                }
-                            }
-                        });
-                    } else {
-                        let write_value = create_write_any_builtin_value(name);
-                        blocks.push(quote! {
-                            for value in self.#ident.iter() {
-                                #write_value
-                            }
-                        });
-                    }
-                } else if built_in_type.is_number() {
+                } else {
                     let write_value = create_write_any_builtin_value(name);
-                    blocks.push(quote! {
+                    return Some(quote! {
                         for value in self.#ident.iter() {
                             #write_value
                         }
                     });
-                } else if built_in_type.is_unknown() {
-                    blocks.push(quote! {
-                        for value in self.#ident.iter() {
-                            value.xml_serialize(#name, writer)?;
-                        }
-                    });
                 }
+            } else if built_in_type.is_number() {
+                let write_value = create_write_any_builtin_value(name);
+                return Some(quote! {
+                    for value in self.#ident.iter() {
+                        #write_value
+                    }
+                });
+            } else if built_in_type.is_unknown() {
+                return Some(quote! {
+                    for value in self.#ident.iter() {
+                        value.xml_serialize(#name, writer)?;
+                    }
+                });
             }
-            Generic::Opt(ty) => {
-                let built_in_type = get_built_in_type(ty);
-                if built_in_type.is_string() {
-                    let write_value = create_write_text_value(name);
-                    blocks.push(quote! {
-                        if let Some(value) = self.#ident.as_ref() {
-                            #write_value
-                        }
-                    })
-                } else if built_in_type.is_bool() {
-                    if is_sfc {
-                        blocks.push(quote! {
-                            if self.#ident.unwrap_or(false) {
-                                writer.create_element(#name)
-                                    .write_empty()?;
-                            }
-                        });
-                    } else {
-                        let write_value = create_write_any_builtin_value(name);
-                        blocks.push(quote! {
-                            if let Some(value) = self.#ident.as_ref() {
-                                #write_value
-                            }
-                        });
+        }
+        Generic::Opt(ty) => {
+            let built_in_type = get_built_in_type(ty);
+            if built_in_type.is_string() {
+                let write_value = create_write_text_value(name);
+                return Some(quote! {
+                    if let Some(value) = self.#ident.as_ref() {
+                        #write_value
                     }
-                } else if built_in_type.is_number() {
-                    let write_value = create_write_any_builtin_value(name);
-                    blocks.push(quote! {
-                        if let Some(value) = self.#ident.as_ref() {
-                            #write_value
+                });
+            } else if built_in_type.is_bool() {
+                if is_sfc {
+                    return Some(quote! {
+                        if self.#ident.unwrap_or(false) {
+                            writer.create_element(#name)
+                                .write_empty()?;
                         }
                     });
-                } else if built_in_type.is_unknown() {
-                    blocks.push(quote! {
+                } else {
+                    let write_value = create_write_any_builtin_value(name);
+                    return Some(quote! {
                         if let Some(value) = self.#ident.as_ref() {
-                            value.xml_serialize(#name, writer)?;
+                            #write_value
                         }
                     });
                 }
+            } else if built_in_type.is_number() {
+                let write_value = create_write_any_builtin_value(name);
+                return Some(quote! {
+                    if let Some(value) = self.#ident.as_ref() {
+                        #write_value
+                    }
+                });
+            } else if built_in_type.is_unknown() {
+                return Some(quote! {
+                    if let Some(value) = self.#ident.as_ref() {
+                        value.xml_serialize(#name, writer)?;
+                    }
+                });
             }
-            Generic::None => {
-                let built_in_type = get_built_in_type(ty);
-                if built_in_type.is_string() {
-                    blocks.push(quote! {
-                        writer.create_element(#name)
-                            .write_text_content(_raxb::quick_xml::events::BytesText::from_escaped(_raxb::quick_xml::escape::escape(&self.#ident)))?;
-                    })
-                } else if built_in_type.is_bool() {
-                    if is_sfc {
-                        blocks.push(quote! {
-                            if self.#ident {
-                                writer.create_element(#name)
-                                    .write_empty()?;
-                            }
-                        });
-                    } else {
-                        blocks.push(quote! {
+        }
+        Generic::None => {
+            let built_in_type = get_built_in_type(ty);
+            if built_in_type.is_string() {
+                return Some(quote! {
+                    writer.create_element(#name)
+                        .write_text_content(_raxb::quick_xml::events::BytesText::from_escaped(_raxb::quick_xml::escape::escape(&self.#ident)))?;
+                });
+            } else if built_in_type.is_bool() {
+                if is_sfc {
+                    return Some(quote! {
+                        if self.#ident {
                             writer.create_element(#name)
-                                .write_text_content(_raxb::quick_xml::events::BytesText::from_escaped(_raxb::quick_xml::escape::escape(&self.#ident.to_string())))?;
-                        });
-                    }
-                } else if built_in_type.is_number() {
-                    blocks.push(quote! {
+                                .write_empty()?;
+                        }
+                    });
+                } else {
+                    return Some(quote! {
                         writer.create_element(#name)
                             .write_text_content(_raxb::quick_xml::events::BytesText::from_escaped(_raxb::quick_xml::escape::escape(&self.#ident.to_string())))?;
                     });
-                } else if built_in_type.is_unknown() {
-                    blocks.push(quote! {
-                        self.#ident.xml_serialize(#name, writer)?;
-                    });
                 }
+            } else if built_in_type.is_number() {
+                return Some(quote! {
+                    writer.create_element(#name)
+                        .write_text_content(_raxb::quick_xml::events::BytesText::from_escaped(_raxb::quick_xml::escape::escape(&self.#ident.to_string())))?;
+                });
+            } else if built_in_type.is_unknown() {
+                return Some(quote! {
+                    self.#ident.xml_serialize(#name, writer)?;
+                });
             }
         }
     }
-    blocks
+    None
 }
@@ -1,7 +1,7 @@
 use quote::quote;
 
 use crate::{
-    container::{Container, EleType, Generic},
+    container::{Container, EleType, Generic, StructField},
     utils::get_built_in_type,
 };
 
@@ -27,56 +27,97 @@ fn write_any_value() -> proc_macro2::TokenStream {
 }
 
 pub fn create_text_block(container: &Container) -> Option<proc_macro2::TokenStream> {
-    if let Some(f) = container
+    let f = container
         .struct_fields
         .iter()
-        .find(|sf| matches!(sf.ty, EleType::Text))
-    {
-        let ident = f.original.ident.as_ref().unwrap();
-        let ty = &f.original.ty;
-        match f.generic {
-            Generic::Vec(_) => {
-                let write_value = write_text_value_ref();
-                return Some(quote! {
-                    let value = self.#ident.iter().map(|v| v.to_string()).join(",");
+        .find(|sf| matches!(sf.ty, EleType::Text))?;
+    let block = create_text_value_block(f)?;
+    let Some(skip_serializing_if) = f.skip_serializing_if.as_ref() else {
+        return Some(block);
+    };
+    let ident = f.original.ident.as_ref().unwrap();
+    Some(quote! {
+        if #skip_serializing_if(&self.#ident) {
+            el_writer.write_empty()?;
+        } else {
+            #block
+        }
+    })
+}
+
+fn create_text_value_block(f: &StructField) -> Option<proc_macro2::TokenStream> {
+    let ident = f.original.ident.as_ref().unwrap();
+    let ty = &f.original.ty;
+    if let Some(serialize_with) = f.serialize_with.as_ref() {
+        let write_value = write_text_value_ref();
+        let convert = quote! {
+            #serialize_with(value).map_err(_raxb::ser::XmlSerializeError::custom)?
+        };
+        return Some(match f.generic {
+            Generic::Vec(_) => quote! {
+                let mut values = Vec::<String>::new();
+                for value in self.#ident.iter() {
+                    values.push(#convert);
+                }
+                let value = values.join(",");
+                #write_value
+            },
+            Generic::Opt(_) => quote! {
+                if let Some(value) = self.#ident.as_ref() {
+                    let value = #convert;
                     #write_value
-                });
-            }
-            Generic::Opt(ty) => {
-                let built_in_type = get_built_in_type(ty);
-                if built_in_type.is_string() {
-                    let write_value = write_text_value();
-                    return Some(quote! {
-                        if let Some(value) = self.#ident.as_ref() {
-                            #write_value
-                        }
-                    });
-                } else {
-                    let write_value = write_any_value();
-                    return Some(quote! {
-                        if let Some(value) = self.#ident.as_ref() {
-                            #write_value
-                        }
-                    });
                 }
-            }
-            Generic::None => {
-                let built_in_type = get_built_in_type(ty);
-                if built_in_type.is_string() {
-                    let write_value = write_text_value();
-                    return Some(quote! {
-                        let value = &self.#ident;
-                        #write_value;
-                    });
-                } else {
-                    let write_value = write_text_value_ref();
-                    return Some(quote! {
-                        let value = self.#ident.to_string();
+            },
+            Generic::None => quote! {
+                let value = &self.#ident;
+                let value = #convert;
+                #write_value
+            },
+            Generic::Map(..) => unreachable!("maps are only supported on child fields"),
+        });
+    }
+    match f.generic {
+        Generic::Map(..) => unreachable!("maps are only supported on child fields"),
+        Generic::Vec(_) => {
+            let write_value = write_text_value_ref();
+            Some(quote! {
+                let value = self.#ident.iter().map(|v| v.to_string()).join(",");
+                #write_value
+            })
+        }
+        Generic::Opt(ty) => {
+            let built_in_type = get_built_in_type(ty);
+            if built_in_type.is_string() {
+                let write_value = write_text_value();
+                Some(quote! {
+                    if let Some(value) = self.#ident.as_ref() {
                         #write_value
-                    });
-                }
+                    }
+                })
+            } else {
+                let write_value = write_any_value();
+                Some(quote! {
+                    if let Some(value) = self.#ident.as_ref() {
+                        #write_value
+                    }
+                })
+            }
+        }
+        Generic::None => {
+            let built_in_type = get_built_in_type(ty);
+            if built_in_type.is_string() {
+                let write_value = write_text_value();
+                Some(quote! {
+                    let value = &self.#ident;
+                    #write_value;
+                })
+            } else {
+                let write_value = write_text_value_ref();
+                Some(quote! {
+                    let value = self.#ident.to_string();
+                    #write_value
+                })
             }
         }
     }
-    None
 }
@@ -1,6 +1,9 @@
 use quote::quote;
 
-use crate::{container::Container, utils::get_built_in_type};
+use crate::{
+    container::{Container, Derive, NsValue},
+    utils::{create_generics, get_built_in_type},
+};
 
 fn create_root_impl(container: &Container) -> proc_macro2::TokenStream {
     if let Some(root) = container.root.as_ref() {
@@ -27,11 +30,117 @@ fn create_root_impl(container: &Container) -> proc_macro2::TokenStream {
     }
 }
 
+/// Writes the variant value as the element itself and names the variant in its `xsi:type`
+/// attribute.
+fn xsi_type_impl_block(container: Container) -> proc_macro2::TokenStream {
+    let root_impl = create_root_impl(&container);
+    let ident = &container.original.ident;
+    let tns_prefix = container
+        .tns
+        .as_ref()
+        .map(|(prefix, _)| String::from_utf8(prefix.value()).unwrap())
+        .filter(|prefix| !prefix.is_empty());
+    let tns_declaration =
+        if let Some(((_, ns_val), prefix)) = container.tns.as_ref().zip(tns_prefix.as_ref()) {
+            let ns_token = match ns_val {
+                NsValue::LitByte(lit) => quote! { #lit },
+                NsValue::ExprPath(path) => quote! { #path },
+            };
+            let key = format!("xmlns:{prefix}");
+            quote! {
+                (#key, std::str::from_utf8(#ns_token)?),
+            }
+        } else {
+            quote! {}
+        };
+    let serialize_branches = container.enum_variants.iter().filter_map(|variant| {
+        let variant_ident = variant.ident;
+        let name = String::from_utf8(variant.name.as_ref()?.value()).unwrap();
+        let variant_prefix = variant.ns.as_ref().map(|ns| String::from_utf8(ns.value()).unwrap());
+        let (xsi_type, declaration) = match variant_prefix.as_ref().or(tns_prefix.as_ref()) {
+            Some(prefix) if Some(prefix) == tns_prefix.as_ref() => {
+                (format!("{prefix}:{name}"), tns_declaration.clone())
+            }
+            Some(prefix) => (format!("{prefix}:{name}"), quote! {}),
+            None => (name, quote! {}),
+        };
+        let attributes = quote! {
+            &[
+                ("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"),
+                #declaration
+                ("xsi:type", #xsi_type),
+            ]
+        };
+        Some(if variant.ty.is_some() {
+            quote! {
+                Self::#variant_ident(v) => {
+                    let own: &[(&str, &str)] = #attributes;
+                    v.xml_serialize_with_attributes(tag, &_raxb::ser::merge_attributes(own, attributes), writer)?
+                }
+            }
+        } else {
+            quote! {
+                Self::#variant_ident => {
+                    let own: &[(&str, &str)] = #attributes;
+                    writer.create_element(tag).with_attributes(_raxb::ser::merge_attributes(own, attributes)).write_empty()?;
+                }
+            }
+        })
+    });
+    let generics = create_generics(&container, Derive::Serialize);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals, unused_attributes, unused_qualifications)]
+        const _: () = {
+            #[allow(unused_extern_crates, clippy::useless_attribute, clippy::manual_flatten, clippy::single_match)]
+            extern crate raxb as _raxb;
+
+            #[automatically_derived]
+            impl #impl_generics _raxb::ser::XmlSerialize for #ident #type_generics #where_clause {
+                #root_impl
+                fn xml_serialize<W: std::io::Write>(&self, tag: &str, writer: &mut _raxb::quick_xml::Writer<W>) -> _raxb::ser::XmlSerializeResult<()> {
+                    self.xml_serialize_with_attributes(tag, &[], writer)
+                }
+
+                fn xml_serialize_with_attributes<W: std::io::Write>(&self, tag: &str, attributes: &[(&str, &str)], writer: &mut _raxb::quick_xml::Writer<W>) -> _raxb::ser::XmlSerializeResult<()> {
+                    match self {
+                        #(#serialize_branches)*
+                    }
+                    Ok(())
+                }
+            }
+        };
+    }
+}
+
 pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
+    if container.tag.is_some() {
+        return xsi_type_impl_block(container);
+    }
     let root_impl = create_root_impl(&container);
     let ident = &container.original.ident;
     let serialize_branches = container.enum_variants.iter().filter_map(|variant| {
         let variant_ident = variant.ident;
+        if variant.other {
+            // the local name of the captured element or the captured element itself
+            let write = if get_built_in_type(variant.ty?).is_string() {
+                quote! {
+                    writer
+                        .create_element(v.as_str())
+                        .with_attributes(__raxb_attributes.iter().copied())
+                        .write_empty()?;
+                }
+            } else {
+                quote! { v.xml_serialize_with_attributes("", __raxb_attributes, writer)?; }
+            };
+            return Some(quote! {
+                Self::#variant_ident(v) => {
+                    #write
+                    Ok::<(), _raxb::ser::XmlSerializeError>(())
+                }
+            });
+        }
         if let Some((name, ty)) = variant.name.as_ref().zip(variant.ty.as_ref()) {
             let v = name.value();
             let name = if let Some(ns) = variant.ns.as_ref() {
@@ -48,8 +157,11 @@ pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
             if built_in_type.is_bool() {
                 return Some(quote! {
                     Self::#variant_ident(v) => {
-                        if v {
-                            writer.create_element(#name).write_empty()?;
+                        if *v {
+                            writer
+                                .create_element(#name)
+                                .with_attributes(__raxb_attributes.iter().copied())
+                                .write_empty()?;
                         }
                         Ok::<(), _raxb::ser::XmlSerializeError>(())
                     }
@@ -57,27 +169,31 @@ pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
             } else if built_in_type.is_number() {
                 return Some(quote! {
                     Self::#variant_ident(v) => {
-                        writer.create_element(#name).write_text_content(
-                            &v.to_string()
-                        )?;
+                        writer
+                            .create_element(#name)
+                            .with_attributes(__raxb_attributes.iter().copied())
+                            .write_text_content(_raxb::quick_xml::events::BytesText::new(
+                                &v.to_string(),
+                            ))?;
                         Ok::<(), _raxb::ser::XmlSerializeError>(())
                     }
                 });
             } else if built_in_type.is_string() {
                 return Some(quote! {
                     Self::#variant_ident(v) => {
-                        writer.create_element(#name).write_text_content(
-                            _raxb::quick_xml::events::BytesText::from_escaped(
-                                _raxb::quick_xml::escape::escape(&v),
-                            ),
-                        )?;
+                        writer
+                            .create_element(#name)
+                            .with_attributes(__raxb_attributes.iter().copied())
+                            .write_text_content(_raxb::quick_xml::events::BytesText::from_escaped(
+                                _raxb::quick_xml::escape::escape(v),
+                            ))?;
                         Ok::<(), _raxb::ser::XmlSerializeError>(())
                     }
                 });
             } else {
                 return Some(quote! {
                     Self::#variant_ident(v) => {
-                        v.xml_serialize(#name, writer)?;
+                        v.xml_serialize_with_attributes(#name, __raxb_attributes, writer)?;
                         Ok::<(), _raxb::ser::XmlSerializeError>(())
                     }
                 });
@@ -96,14 +212,18 @@ pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
             };
             return Some(quote! {
                 Self::#variant_ident => {
-                    writer.create_element(#name).write_empty()?;
+                    writer
+                        .create_element(#name)
+                        .with_attributes(__raxb_attributes.iter().copied())
+                        .write_empty()?;
                     Ok::<(), _raxb::ser::XmlSerializeError>(())
                 }
             });
         }
         None
     });
-    let (impl_generics, type_generics, where_clause) = container.original.generics.split_for_impl();
+    let generics = create_generics(&container, Derive::Serialize);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
     let serialize_branches_1 = serialize_branches.clone();
     let serialize_branches_2 = serialize_branches;
     quote! {
@@ -117,12 +237,19 @@ pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
             impl #impl_generics _raxb::ser::XmlSerialize for #ident #type_generics #where_clause {
                 #root_impl
                 fn xml_serialize<W: std::io::Write>(&self, tag: &str, writer: &mut _raxb::quick_xml::Writer<W>) -> _raxb::ser::XmlSerializeResult<()> {
+                    self.xml_serialize_with_attributes(tag, &[], writer)
+                }
+
+                /// Without a `tag` the attributes are added to the element of the variant.
+                fn xml_serialize_with_attributes<W: std::io::Write>(&self, tag: &str, attributes: &[(&str, &str)], writer: &mut _raxb::quick_xml::Writer<W>) -> _raxb::ser::XmlSerializeResult<()> {
                     if tag.is_empty() {
+                        let __raxb_attributes = attributes;
                         match self {
                             #(#serialize_branches_1,)*
                         }?;
                     } else {
-                        writer.create_element(tag).write_inner_content(|writer| {
+                        writer.create_element(tag).with_attributes(attributes.iter().copied()).write_inner_content(|writer| {
+                            let __raxb_attributes: &[(&str, &str)] = &[];
                             match self {
                                 #(#serialize_branches_2,)*
                             }
@@ -0,0 +1,69 @@
+use quote::quote;
+
+use crate::{
+    container::{Container, Derive},
+    utils::{create_generics, get_built_in_type},
+};
+
+/// Newtype struct with `#[raxb(transparent)]`, serialized like its inner type. Newtypes of
+/// built-in types also get a `Display` impl to be usable as attributes and text.
+pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
+    let ident = &container.original.ident;
+    let inner = &container.struct_fields[0].original.ty;
+    let generics = create_generics(&container, Derive::Serialize);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let display_impl = if get_built_in_type(inner).is_unknown() {
+        quote! {}
+    } else {
+        quote! {
+            #[automatically_derived]
+            impl ::core::fmt::Display for #ident {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::fmt::Display::fmt(&self.0, f)
+                }
+            }
+        }
+    };
+    quote! {
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals, unused_attributes, unused_qualifications)]
+        const _: () = {
+            #[allow(unused_extern_crates, clippy::useless_attribute)]
+            extern crate raxb as _raxb;
+
+            use _raxb::{
+                quick_xml::Writer,
+                ser::{XmlSerialize, XmlSerializeResult},
+                ty::XmlTag,
+            };
+            #display_impl
+            #[automatically_derived]
+            impl #impl_generics XmlSerialize for #ident #type_generics #where_clause {
+                fn is_enum() -> bool {
+                    <#inner as XmlSerialize>::is_enum()
+                }
+
+                fn root() -> Option<XmlTag> {
+                    <#inner as XmlSerialize>::root()
+                }
+
+                fn xml_serialize<W: std::io::Write>(
+                    &self,
+                    tag: &str,
+                    writer: &mut Writer<W>,
+                ) -> XmlSerializeResult<()> {
+                    self.0.xml_serialize(tag, writer)
+                }
+
+                fn xml_serialize_with_attributes<W: std::io::Write>(
+                    &self,
+                    tag: &str,
+                    attributes: &[(&str, &str)],
+                    writer: &mut Writer<W>,
+                ) -> XmlSerializeResult<()> {
+                    self.0.xml_serialize_with_attributes(tag, attributes, writer)
+                }
+            }
+        };
+    }
+}
@@ -0,0 +1,55 @@
+use quote::quote;
+
+use crate::{
+    container::{Container, Derive},
+    utils::create_generics,
+};
+
+pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
+    let ident = &container.original.ident;
+    let branches = container.enum_variants.iter().map(|v| {
+        let variant = v.ident;
+        if let Some(value) = v.value.as_ref() {
+            quote! {
+                Self::#variant => #value,
+            }
+        } else {
+            quote! {
+                Self::#variant(value) => value.as_ref(),
+            }
+        }
+    });
+    let generics = create_generics(&container, Derive::Serialize);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals, unused_attributes, unused_qualifications)]
+        const _: () = {
+            #[allow(unused_extern_crates, clippy::useless_attribute)]
+            extern crate raxb as _raxb;
+
+            #[automatically_derived]
+            impl #impl_generics ::core::fmt::Display for #ident #type_generics #where_clause {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.write_str(match self {
+                        #(#branches)*
+                    })
+                }
+            }
+            #[automatically_derived]
+            impl #impl_generics _raxb::ser::XmlSerialize for #ident #type_generics #where_clause {
+                fn xml_serialize<W: std::io::Write>(&self, tag: &str, writer: &mut _raxb::quick_xml::Writer<W>) -> _raxb::ser::XmlSerializeResult<()> {
+                    self.xml_serialize_with_attributes(tag, &[], writer)
+                }
+
+                fn xml_serialize_with_attributes<W: std::io::Write>(&self, tag: &str, attributes: &[(&str, &str)], writer: &mut _raxb::quick_xml::Writer<W>) -> _raxb::ser::XmlSerializeResult<()> {
+                    writer
+                        .create_element(tag)
+                        .with_attributes(attributes.iter().copied())
+                        .write_text_content(_raxb::quick_xml::events::BytesText::new(&self.to_string()))?;
+                    Ok(())
+                }
+            }
+        };
+    }
+}
@@ -1,6 +1,9 @@
 use quote::quote;
 
-use crate::container::Container;
+use crate::{
+    container::{Container, Derive, EleType},
+    utils::{create_constraint_checks, create_generics, is_qname},
+};
 
 use super::{child::create_child_blocks, text::create_text_block};
 
@@ -37,10 +40,9 @@ fn create_root_element_impl(container: &Container) -> proc_macro2::TokenStream {
         Vec::default()
     };
     let attribute_blocks = super::attrs::create_attribute_blocks(container);
-    let has_attributes = !attribute_blocks.is_empty();
-    let mut has_child_blocks = !child_blocks.is_empty();
+    let attribute_names = super::attrs::attribute_names(container);
+    let has_child_blocks = !child_blocks.is_empty();
     let children = if let Some(text_block) = text_block {
-        has_child_blocks = true;
         text_block
     } else if has_child_blocks {
         let child_blocks = child_blocks.into_iter();
@@ -56,31 +58,72 @@ fn create_root_element_impl(container: &Container) -> proc_macro2::TokenStream {
             el_writer.write_empty()?;
         }
     };
-    // let create_el = if let Some((prefix, ns)) = container.tns.as_ref() {
-    //     let prefix_buf = prefix.value();
-    //     let prefix = std::str::from_utf8(&prefix_buf).unwrap();
-    //     let ns_buf = ns.value();
-    //     let ns = std::str::from_utf8(&ns_buf).unwrap();
-    //     let key = if !prefix.is_empty() {
-    //         format!("xmlns:{prefix}")
-    //     } else {
-    //         "xmlns".to_string()
-    //     };
-    //     quote! { writer.create_element(tag).with_attribute((#key, #ns)) }
-    // } else {
-    // };
-    let create_el = quote! { writer.create_element(tag) };
-    if has_attributes || has_child_blocks {
-        let attribute_blocks = attribute_blocks.into_iter();
+    let attribute_blocks = attribute_blocks.into_iter();
+    let constraint_checks = create_constraint_checks(container, quote! { self });
+    // the tag field replaces the element name unless it is empty
+    let tag_field = container
+        .struct_fields
+        .iter()
+        .find(|f| matches!(f.ty, EleType::Tag));
+    let tag_name = tag_field.map(|f| {
+        let ident = f.original.ident.as_ref().unwrap();
         quote! {
-            let mut el_writer = #create_el;
-            #(#attribute_blocks)*
-            #children
+            let __raxb_tag = self.#ident.to_string();
+            let tag = if __raxb_tag.is_empty() { tag } else { __raxb_tag.as_str() };
         }
-    } else {
+    });
+    // a `QName` tag declares its namespace, unless an attribute of the element already does
+    let tag_declaration = tag_field.filter(|f| is_qname(&f.original.ty)).map(|f| {
+        let ident = f.original.ident.as_ref().unwrap();
+        quote! {
+            let tag_declaration = self.#ident.namespace_declaration().filter(|(key, _)| {
+                !own_attributes.contains(&key.as_str())
+                    && !attributes.iter().any(|(name, _)| name == key)
+            });
+            if let Some((key, value)) = tag_declaration.as_ref() {
+                el_writer = el_writer.with_attribute((key.as_str(), *value));
+            }
+        }
+    });
+    let tag_declaration_check = tag_declaration.is_some().then(|| {
         quote! {
-            #create_el.write_empty()?;
+            && tag_declaration.as_ref().is_none_or(|(key, _)| *key != name)
         }
+    });
+    // written after the own attributes, unless the struct or the caller writes the same name
+    let any_attributes = container
+        .struct_fields
+        .iter()
+        .find(|f| matches!(f.ty, EleType::AnyAttr))
+        .map(|f| {
+            let ident = f.original.ident.as_ref().unwrap();
+            quote! {
+                for (name, value) in _raxb::collection::XmlAnyAttributes::iter_attributes(&self.#ident) {
+                    let name = name.to_string();
+                    if !own_attributes.contains(&name.as_str())
+                        && !attributes.iter().any(|(key, _)| *key == name)
+                        #tag_declaration_check
+                    {
+                        el_writer = el_writer.with_attribute((name.as_str(), value));
+                    }
+                }
+            }
+        });
+    quote! {
+        #constraint_checks
+        #tag_name
+        let mut el_writer = writer.create_element(tag);
+        #(#attribute_blocks)*
+        let own_attributes: &[&str] = &[#(#attribute_names),*];
+        #tag_declaration
+        #any_attributes
+        for attribute in attributes
+            .iter()
+            .filter(|(key, _)| !own_attributes.contains(key))
+        {
+            el_writer = el_writer.with_attribute(*attribute);
+        }
+        #children
     }
 }
 
@@ -88,7 +131,8 @@ pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
     let root_impl = create_root_impl(&container);
     let create_root_element = create_root_element_impl(&container);
     let ident = &container.original.ident;
-    let (impl_generics, type_generics, where_clause) = container.original.generics.split_for_impl();
+    let generics = create_generics(&container, Derive::Serialize);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
     quote! {
         #[doc(hidden)]
         #[allow(non_upper_case_globals, unused_attributes, unused_qualifications)]
@@ -100,6 +144,10 @@ pub fn impl_block(container: Container) -> proc_macro2::TokenStream {
             impl #impl_generics _raxb::ser::XmlSerialize for #ident #type_generics #where_clause {
                 #root_impl
                 fn xml_serialize<W: std::io::Write>(&self, tag: &str, writer: &mut _raxb::quick_xml::Writer<W>) -> _raxb::ser::XmlSerializeResult<()> {
+                    self.xml_serialize_with_attributes(tag, &[], writer)
+                }
+
+                fn xml_serialize_with_attributes<W: std::io::Write>(&self, tag: &str, attributes: &[(&str, &str)], writer: &mut _raxb::quick_xml::Writer<W>) -> _raxb::ser::XmlSerializeResult<()> {
                     #create_root_element
                     Ok(())
                 }
@@ -8,11 +8,40 @@ pub const ROOT: Symbol = Symbol("root");
 pub const RAXB: Symbol = Symbol("raxb");
 pub const XML: Symbol = Symbol("xml");
 pub const NAME: Symbol = Symbol("name");
+pub const ALIAS: Symbol = Symbol("alias");
 pub const VALUE: Symbol = Symbol("value");
 pub const DEFAULT: Symbol = Symbol("default");
 pub const TYPE: Symbol = Symbol("ty");
 pub const TNS: Symbol = Symbol("tns");
 pub const NS: Symbol = Symbol("ns");
+pub const TAG: Symbol = Symbol("tag");
+pub const WHITESPACE: Symbol = Symbol("whitespace");
+pub const WITH: Symbol = Symbol("with");
+pub const DESERIALIZE_WITH: Symbol = Symbol("deserialize_with");
+pub const SERIALIZE_WITH: Symbol = Symbol("serialize_with");
+pub const SKIP_SERIALIZING_IF: Symbol = Symbol("skip_serializing_if");
+pub const SKIP: Symbol = Symbol("skip");
+pub const SKIP_SERIALIZING: Symbol = Symbol("skip_serializing");
+pub const SKIP_DESERIALIZING: Symbol = Symbol("skip_deserializing");
+pub const PATTERN: Symbol = Symbol("pattern");
+pub const MIN_LENGTH: Symbol = Symbol("min_length");
+pub const MAX_LENGTH: Symbol = Symbol("max_length");
+pub const MIN_OCCURS: Symbol = Symbol("min_occurs");
+pub const MAX_OCCURS: Symbol = Symbol("max_occurs");
+pub const KEY: Symbol = Symbol("key");
+pub const KEY_BY_NAME: Symbol = Symbol("key_by_name");
+pub const WRAPPER: Symbol = Symbol("wrapper");
+pub const WRAPPER_NS: Symbol = Symbol("wrapper_ns");
+pub const SKIP_EMPTY_WRAPPER: Symbol = Symbol("skip_empty_wrapper");
+pub const MIN: Symbol = Symbol("min");
+pub const MAX: Symbol = Symbol("max");
+pub const ENUMERATION: Symbol = Symbol("enumeration");
+pub const VALIDATE: Symbol = Symbol("validate");
+pub const SIMPLE: Symbol = Symbol("simple");
+pub const OTHER: Symbol = Symbol("other");
+pub const RENAME_ALL: Symbol = Symbol("rename_all");
+pub const BOUND: Symbol = Symbol("bound");
+pub const TRANSPARENT: Symbol = Symbol("transparent");
 
 impl PartialEq<Symbol> for Ident {
     fn eq(&self, other: &Symbol) -> bool {
@@ -2,7 +2,10 @@ use std::str::FromStr;
 
 use quote::quote;
 
-use crate::container::{BuiltInConstType, BuiltInType, Container, Generic, NsValue, StructField};
+use crate::container::{
+    BuiltInConstType, BuiltInType, Container, Derive, EleType, Generic, NsValue, StructField,
+    WhiteSpace,
+};
 
 pub fn create_ident(f: &StructField) -> proc_macro2::TokenStream {
     let ident = f.original.ident.as_ref().unwrap();
@@ -14,12 +17,275 @@ pub fn create_ident(f: &StructField) -> proc_macro2::TokenStream {
         Generic::Opt(opt) => quote! {
             let mut #ident = Option::<#opt>::None;
         },
+        Generic::Map(..) => quote! {
+            let mut #ident = <#ty as ::core::default::Default>::default();
+        },
         Generic::None => quote! {
             let mut #ident = Option::<#ty>::None;
         },
     }
 }
 
+/// Converts the text value `value` into an owned `String` honoring the field's whitespace handling.
+pub fn create_normalized_value(
+    whitespace: Option<WhiteSpace>,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let variant = match whitespace {
+        Some(WhiteSpace::Preserve) => quote! { Preserve },
+        Some(WhiteSpace::Replace) => quote! { Replace },
+        Some(WhiteSpace::Collapse) => quote! { Collapse },
+        None => return quote! { #value.to_string() },
+    };
+    quote! {
+        _raxb::de::WhiteSpace::#variant.normalize(&#value).into_owned()
+    }
+}
+
+/// Generics of the impl: the `bound` predicates of the container or, without them,
+/// `T: XmlDeserialize`/`T: XmlSerialize` for every type parameter `T` used in the type of a
+/// child, untag field, variant or the field of a transparent struct. Fields read and written with `with` functions are left out.
+pub fn create_generics(container: &Container, derive: Derive) -> syn::Generics {
+    let mut generics = container.original.generics.clone();
+    if let Some(bound) = container.bound.as_ref() {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(bound.iter().cloned());
+        return generics;
+    }
+    let params = generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect::<Vec<_>>();
+    if params.is_empty() {
+        return generics;
+    }
+    let mut types = Vec::<&syn::Type>::new();
+    for f in container.struct_fields.iter() {
+        let with = match derive {
+            Derive::Deserialize => f.deserialize_with.is_some(),
+            Derive::Serialize => f.serialize_with.is_some(),
+        };
+        if !with && (container.transparent || matches!(f.ty, EleType::Child | EleType::Untag)) {
+            types.push(&f.original.ty);
+        }
+    }
+    types.extend(container.enum_variants.iter().filter_map(|v| v.ty));
+    let mut used = Vec::<&syn::Ident>::new();
+    for ty in types {
+        collect_type_params(ty, &params, &mut used);
+    }
+    let bound = match derive {
+        Derive::Deserialize => quote! { _raxb::de::XmlDeserialize },
+        Derive::Serialize => quote! { _raxb::ser::XmlSerialize },
+    };
+    let predicates = &mut generics.make_where_clause().predicates;
+    for param in params.iter().filter(|p| used.contains(p)) {
+        predicates.push(syn::parse_quote! { #param: #bound });
+    }
+    generics
+}
+
+/// Bounds are not inferred for the type parameters of attr, text and tag fields, they need
+/// `FromStr` and `Display` rather than `XmlDeserialize`/`XmlSerialize`.
+pub fn check_inferred_bounds(container: &Container, derive: Derive) -> syn::Result<()> {
+    if container.bound.is_some() || container.transparent {
+        return Ok(());
+    }
+    let params = container
+        .original
+        .generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect::<Vec<_>>();
+    if params.is_empty() {
+        return Ok(());
+    }
+    for f in container.struct_fields.iter() {
+        let with = match derive {
+            Derive::Deserialize => f.deserialize_with.is_some(),
+            Derive::Serialize => f.serialize_with.is_some(),
+        };
+        if with || !matches!(f.ty, EleType::Attr | EleType::Text | EleType::Tag) {
+            continue;
+        }
+        let mut used = Vec::<&syn::Ident>::new();
+        collect_type_params(&f.original.ty, &params, &mut used);
+        if let Some(param) = used.first() {
+            let bound = match f.ty {
+                EleType::Attr => format!("{param}: std::str::FromStr + Default + std::fmt::Display"),
+                EleType::Text => format!(
+                    "{param}: std::str::FromStr + std::fmt::Display, raxb::de::XmlDeserializeError: From<{param}::Err>"
+                ),
+                _ => format!(
+                    "{param}: std::str::FromStr + std::fmt::Display, {param}::Err: std::fmt::Display"
+                ),
+            };
+            return Err(syn::Error::new_spanned(
+                &f.original.ty,
+                format!(
+                    "the bounds of `{param}` cannot be inferred for attr, text and tag fields, add them to the container, e.g. #[raxb(bound = \"{bound}\")]"
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn collect_type_params<'p>(
+    ty: &syn::Type,
+    params: &'p [syn::Ident],
+    used: &mut Vec<&'p syn::Ident>,
+) {
+    match ty {
+        syn::Type::Path(p) => {
+            if let Some(qself) = p.qself.as_ref() {
+                collect_type_params(&qself.ty, params, used);
+            }
+            if let Some(param) = p
+                .path
+                .segments
+                .first()
+                .and_then(|seg| params.iter().find(|param| **param == seg.ident))
+            {
+                if !used.contains(&param) {
+                    used.push(param);
+                }
+            }
+            for seg in p.path.segments.iter() {
+                if let syn::PathArguments::AngleBracketed(args) = &seg.arguments {
+                    for arg in args.args.iter() {
+                        if let syn::GenericArgument::Type(ty) = arg {
+                            collect_type_params(ty, params, used);
+                        }
+                    }
+                }
+            }
+        }
+        syn::Type::Array(a) => collect_type_params(&a.elem, params, used),
+        syn::Type::Group(g) => collect_type_params(&g.elem, params, used),
+        syn::Type::Paren(p) => collect_type_params(&p.elem, params, used),
+        syn::Type::Ptr(p) => collect_type_params(&p.elem, params, used),
+        syn::Type::Reference(r) => collect_type_params(&r.elem, params, used),
+        syn::Type::Slice(s) => collect_type_params(&s.elem, params, used),
+        syn::Type::Tuple(t) => {
+            for elem in t.elems.iter() {
+                collect_type_params(elem, params, used);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Wraps the serialization `block` of the field in its `skip_serializing_if` condition.
+pub fn create_skip_serializing_if(
+    f: &StructField,
+    block: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let Some(skip_serializing_if) = f.skip_serializing_if.as_ref() else {
+        return block;
+    };
+    let ident = f.original.ident.as_ref().unwrap();
+    quote! {
+        if !#skip_serializing_if(&self.#ident) {
+            #block
+        }
+    }
+}
+
+/// The name of the field in facet violations, its element or attribute name if it has one.
+pub fn get_field_name(f: &StructField) -> String {
+    f.name
+        .as_ref()
+        .map(|name| String::from_utf8(name.value()).unwrap())
+        .unwrap_or_else(|| f.original.ident.as_ref().unwrap().to_string())
+}
+
+/// Checks the facets of all fields and the `validate` function of the struct `value`.
+pub fn create_constraint_checks(
+    container: &Container,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let fields = container
+        .struct_fields
+        .iter()
+        .filter(|f| !f.facets.is_empty() && !matches!(f.ty, EleType::Skip))
+        .map(|f| {
+            let ident = f.original.ident.as_ref().unwrap();
+            let name = get_field_name(f);
+            let facets = &f.facets;
+            let mut checks = Vec::<proc_macro2::TokenStream>::new();
+            if let Some(pattern) = facets.pattern.as_ref() {
+                checks.push(quote! {
+                    static PATTERN: ::std::sync::OnceLock<_raxb::facet::Pattern> = ::std::sync::OnceLock::new();
+                    PATTERN.get_or_init(|| _raxb::facet::Pattern::new(#pattern)).check(#name, item)?;
+                });
+            }
+            if let Some(min_length) = facets.min_length.as_ref() {
+                checks.push(quote! {
+                    _raxb::facet::check_min_length(#name, item, #min_length)?;
+                });
+            }
+            if let Some(max_length) = facets.max_length.as_ref() {
+                checks.push(quote! {
+                    _raxb::facet::check_max_length(#name, item, #max_length)?;
+                });
+            }
+            if let Some(min) = facets.min.as_ref() {
+                checks.push(quote! {
+                    _raxb::facet::check_min(#name, item, &(#min))?;
+                });
+            }
+            if let Some(max) = facets.max.as_ref() {
+                checks.push(quote! {
+                    _raxb::facet::check_max(#name, item, &(#max))?;
+                });
+            }
+            if let Some(values) = facets.enumeration.as_ref() {
+                checks.push(quote! {
+                    _raxb::facet::check_enumeration(#name, item, &[#(#values),*])?;
+                });
+            }
+            let items = match f.generic {
+                Generic::Vec(_) | Generic::Opt(_) => quote! { #value.#ident.iter() },
+                Generic::Map(..) => quote! { #value.#ident.values() },
+                Generic::None => quote! { ::core::iter::once(&#value.#ident) },
+            };
+            let mut occurs = Vec::<proc_macro2::TokenStream>::new();
+            if let Some(min_occurs) = facets.min_occurs.as_ref() {
+                occurs.push(quote! {
+                    _raxb::facet::check_min_occurs(#name, #items.count(), #min_occurs)?;
+                });
+            }
+            if let Some(max_occurs) = facets.max_occurs.as_ref() {
+                occurs.push(quote! {
+                    _raxb::facet::check_max_occurs(#name, #items.count(), #max_occurs)?;
+                });
+            }
+            let checks = (!checks.is_empty()).then(|| {
+                quote! {
+                    for item in #items {
+                        #(#checks)*
+                    }
+                }
+            });
+            quote! {
+                #(#occurs)*
+                #checks
+            }
+        });
+    let validate = container.validate.as_ref().map(|validate| {
+        quote! {
+            #validate(&#value).map_err(_raxb::facet::Violation::custom)?;
+        }
+    });
+    quote! {
+        #(#fields)*
+        #validate
+    }
+}
+
 pub fn get_built_in_type(ty: &syn::Type) -> BuiltInType {
     if let syn::Type::Path(p) = ty {
         if let Some(ty_ident) = p.path.get_ident() {
@@ -38,22 +304,68 @@ pub fn get_built_in_const_type(ty: &syn::Type) -> BuiltInConstType {
     BuiltInConstType::Unknown
 }
 
+/// `QName` tag fields keep the prefix and namespace of the element name.
+pub fn is_qname(ty: &syn::Type) -> bool {
+    matches!(
+        ty,
+        syn::Type::Path(p) if p.path.segments.last().is_some_and(|seg| seg.ident == "QName")
+    )
+}
+
 pub fn create_tns_impl(container: &Container) -> proc_macro2::TokenStream {
+    let to_tokens = |ns_val: &NsValue| match ns_val {
+        NsValue::LitByte(lit) => quote! { #lit },
+        NsValue::ExprPath(path) => quote! { #path },
+    };
     if let Some((_, ns_val)) = container.tns.as_ref() {
-        let ns_token = match ns_val {
-            NsValue::LitByte(lit) => quote! { #lit },
-            NsValue::ExprPath(path) => quote! { #path },
+        let ns_token = to_tokens(ns_val);
+        let aliases_impl = if container.tns_aliases.is_empty() {
+            quote! {}
+        } else {
+            let aliases = container.tns_aliases.iter().map(to_tokens);
+            quote! {
+                fn target_ns_aliases() -> &'static [_raxb::ty::XmlTargetNs] {
+                    &[#(#aliases),*]
+                }
+            }
         };
         quote! {
             fn target_ns() -> Option<_raxb::ty::XmlTargetNs> {
                 Some(#ns_token)
             }
+
+            #aliases_impl
         }
     } else {
         quote! {}
     }
 }
 
+/// Match pattern of a name and its aliases, e.g. `b"OrderReference" | b"OrderRef"`.
+pub fn create_name_pattern(
+    name: &syn::LitByteStr,
+    aliases: &[syn::LitByteStr],
+) -> proc_macro2::TokenStream {
+    quote! { #name #(| #aliases)* }
+}
+
+/// The static name, `name` or one of its `aliases`, matching the local name of the start `event`.
+pub fn create_name_value(
+    name: &syn::LitByteStr,
+    aliases: &[syn::LitByteStr],
+    event: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if aliases.is_empty() {
+        return quote! { #name };
+    }
+    quote! {
+        match #event.local_name().as_ref() {
+            #(#aliases => #aliases,)*
+            _ => #name,
+        }
+    }
+}
+
 pub fn create_root_impl(container: &Container) -> proc_macro2::TokenStream {
     if let Some(root) = container.root.as_ref() {
         quote! {
@@ -0,0 +1,318 @@
+#![doc = include_str!("../README.md")]
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use raxb::{
+    de::{XmlDeserialize, XmlDeserializeError},
+    quick_xml::{
+        events::Event,
+        name::{Namespace, ResolveResult},
+        NsReader, Reader,
+    },
+    ser::{
+        canonicalize_str_with_options, C14nMode, C14nOptions, ElementSelector, XmlSerialize,
+        XmlSerializeError,
+    },
+};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+mod key;
+pub mod model;
+
+pub use key::{SigningKey, VerifyingKey};
+pub use model::{Signature, SignedInfo};
+
+use model::{
+    CanonicalizationMethod, DigestMethod, InclusiveNamespaces, Reference, SignatureMethod,
+    Transform, Transforms,
+};
+
+pub const DS_NS: &str = "http://www.w3.org/2000/09/xmldsig#";
+pub const ENVELOPED_SIGNATURE: &str = "http://www.w3.org/2000/09/xmldsig#enveloped-signature";
+pub const SHA256: &str = "http://www.w3.org/2001/04/xmlenc#sha256";
+pub const RSA_SHA256: &str = "http://www.w3.org/2001/04/xmldsig-more#rsa-sha256";
+pub const ECDSA_SHA256: &str = "http://www.w3.org/2001/04/xmldsig-more#ecdsa-sha256";
+
+#[derive(Debug, Error)]
+pub enum DsigError {
+    #[error(transparent)]
+    Xml(#[from] raxb::quick_xml::Error),
+    #[error(transparent)]
+    Deserialize(#[from] XmlDeserializeError),
+    #[error(transparent)]
+    Serialize(#[from] XmlSerializeError),
+    #[error(transparent)]
+    Base64(#[from] base64::DecodeError),
+    #[error("invalid key: {0}")]
+    Key(String),
+    #[error("unsupported algorithm '{0}'")]
+    UnsupportedAlgorithm(String),
+    #[error("unsupported reference URI '{0}'")]
+    UnsupportedReference(String),
+    #[error("missing root element")]
+    MissingRoot,
+    #[error("missing signature")]
+    MissingSignature,
+    #[error("more than one '{0}' element")]
+    DuplicateElement(String),
+    #[error("no element for reference '{0}'")]
+    MissingReference(String),
+    #[error("digest of reference '{0}' does not match")]
+    DigestMismatch(String),
+    #[error("invalid signature value")]
+    InvalidSignature,
+}
+
+pub type DsigResult<T> = Result<T, DsigError>;
+
+/// A verified signature with the data it signs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifiedSignature {
+    pub signature: Signature,
+    /// The canonicalized data of each reference of the `SignedInfo`, in the same order. Only this
+    /// data is covered by the signature, so it should be read instead of the verified document.
+    pub references: Vec<Vec<u8>>,
+}
+
+/// Serializes the value and signs it, see [`sign_document`].
+pub fn sign<T>(value: &T, key: &SigningKey) -> DsigResult<String>
+where
+    T: XmlSerialize,
+{
+    sign_document(&raxb::ser::to_string(value)?, key)
+}
+
+/// Adds an enveloped signature of the whole document as last child of the root element. The
+/// document and the `SignedInfo` are canonicalized with exclusive canonicalization.
+pub fn sign_document(xml: &str, key: &SigningKey) -> DsigResult<String> {
+    let mode = C14nMode::Exclusive;
+    let options = C14nOptions {
+        exclude: Some(signature_selector()),
+        ..Default::default()
+    };
+    let digest = Sha256::digest(canonicalize_str_with_options(xml, mode, &options)?);
+    let mut signature = Signature::new(SignedInfo {
+        canonicalization_method: CanonicalizationMethod {
+            algorithm: mode.algorithm().to_string(),
+            ..Default::default()
+        },
+        signature_method: SignatureMethod {
+            algorithm: key.algorithm().to_string(),
+        },
+        references: vec![Reference {
+            uri: Some(String::new()),
+            transforms: Some(Transforms {
+                transforms: vec![
+                    Transform {
+                        algorithm: ENVELOPED_SIGNATURE.to_string(),
+                        ..Default::default()
+                    },
+                    Transform {
+                        algorithm: mode.algorithm().to_string(),
+                        ..Default::default()
+                    },
+                ],
+            }),
+            digest_method: DigestMethod {
+                algorithm: SHA256.to_string(),
+            },
+            digest_value: BASE64.encode(digest),
+            ..Default::default()
+        }],
+        ..Default::default()
+    });
+    let signed_info = canonicalize_str_with_options(
+        &raxb::ser::to_string(&signature)?,
+        mode,
+        &C14nOptions {
+            subtree: Some(signed_info_selector()),
+            ..Default::default()
+        },
+    )?;
+    signature.signature_value = BASE64.encode(key.sign(&signed_info));
+    insert_signature(xml, &raxb::ser::to_string(&signature)?)
+}
+
+/// Verifies the `ds:Signature` of the document: the digests of all references and the
+/// signature value of the `SignedInfo`. Returns the verified signature and the signed data.
+///
+/// References to the whole document (`URI=""`) and to elements by their `Id`, `ID` or `id`
+/// attribute (`URI="#id"`) are supported. Documents with more than one `ds:Signature` or
+/// `ds:SignedInfo` element or more than one element with a referenced id are rejected, so that
+/// the verified elements can't be confused with others.
+pub fn verify(xml: &str, key: &VerifyingKey) -> DsigResult<VerifiedSignature> {
+    let signature = read_signature(xml)?;
+    let signed_info = &signature.signed_info;
+    let references = signed_info
+        .references
+        .iter()
+        .map(|reference| verify_reference(xml, reference))
+        .collect::<DsigResult<Vec<_>>>()?;
+    let method = &signed_info.canonicalization_method;
+    let mode = C14nMode::from_algorithm(&method.algorithm)
+        .ok_or_else(|| DsigError::UnsupportedAlgorithm(method.algorithm.clone()))?;
+    let options = C14nOptions {
+        subtree: Some(signed_info_selector()),
+        inclusive_prefixes: inclusive_prefixes(method.inclusive_namespaces.as_ref()),
+        ..Default::default()
+    };
+    let canonical = canonicalize_str_with_options(xml, mode, &options)?;
+    let value = decode_base64(&signature.signature_value)?;
+    key.verify(&signed_info.signature_method.algorithm, &canonical, &value)?;
+    Ok(VerifiedSignature {
+        signature,
+        references,
+    })
+}
+
+/// Reads the `ds:Signature` of the document without verifying it. Documents with more than one
+/// `ds:Signature` or `ds:SignedInfo` element are rejected.
+pub fn read_signature(xml: &str) -> DsigResult<Signature> {
+    check_single_signature(xml)?;
+    let mut reader = NsReader::from_str(xml);
+    reader.config_mut().trim_text(true);
+    loop {
+        let (ns, event) = reader.read_resolved_event()?;
+        let is_signature =
+            matches!(ns, ResolveResult::Bound(Namespace(ns)) if ns == DS_NS.as_bytes());
+        match event {
+            Event::Start(e) if is_signature && e.local_name().as_ref() == b"Signature" => {
+                return Ok(Signature::xml_deserialize(
+                    &mut reader,
+                    DS_NS.as_bytes(),
+                    b"Signature",
+                    e.attributes(),
+                    false,
+                )?);
+            }
+            Event::Eof => return Err(DsigError::MissingSignature),
+            _ => {}
+        }
+    }
+}
+
+/// The selectors of the signature and its `SignedInfo` match the first element of the name, the
+/// only one after this check.
+fn check_single_signature(xml: &str) -> DsigResult<()> {
+    let mut reader = NsReader::from_str(xml);
+    let mut signatures = 0usize;
+    let mut signed_infos = 0usize;
+    loop {
+        let (ns, event) = reader.read_resolved_event()?;
+        let is_dsig = matches!(ns, ResolveResult::Bound(Namespace(ns)) if ns == DS_NS.as_bytes());
+        match event {
+            Event::Start(e) | Event::Empty(e) if is_dsig => {
+                let count = match e.local_name().as_ref() {
+                    b"Signature" => &mut signatures,
+                    b"SignedInfo" => &mut signed_infos,
+                    _ => continue,
+                };
+                *count += 1;
+                if *count > 1 {
+                    let name = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                    return Err(DsigError::DuplicateElement(name));
+                }
+            }
+            Event::Eof => return Ok(()),
+            _ => {}
+        }
+    }
+}
+
+/// Verifies the digest of the reference and returns the canonicalized data.
+fn verify_reference(xml: &str, reference: &Reference) -> DsigResult<Vec<u8>> {
+    let uri = reference.uri.as_deref().unwrap_or_default();
+    let subtree = if uri.is_empty() {
+        None
+    } else {
+        let id = uri
+            .strip_prefix('#')
+            .ok_or_else(|| DsigError::UnsupportedReference(uri.to_string()))?;
+        Some(ElementSelector::Id(id.to_string()))
+    };
+    let mut mode = C14nMode::Inclusive;
+    let mut options = C14nOptions {
+        subtree,
+        ..Default::default()
+    };
+    for transform in reference.transforms() {
+        if transform.algorithm == ENVELOPED_SIGNATURE {
+            // the enclosing signature, as there is only one
+            options.exclude = Some(signature_selector());
+        } else {
+            mode = C14nMode::from_algorithm(&transform.algorithm)
+                .ok_or_else(|| DsigError::UnsupportedAlgorithm(transform.algorithm.clone()))?;
+            options.inclusive_prefixes =
+                inclusive_prefixes(transform.inclusive_namespaces.as_ref());
+        }
+    }
+    let digest_method = &reference.digest_method.algorithm;
+    if digest_method != SHA256 {
+        return Err(DsigError::UnsupportedAlgorithm(digest_method.clone()));
+    }
+    let canonical =
+        canonicalize_str_with_options(xml, mode, &options).map_err(|err| match err {
+            XmlSerializeError::MissingSubtree(_) => DsigError::MissingReference(uri.to_string()),
+            err => err.into(),
+        })?;
+    let digest = Sha256::digest(&canonical);
+    if decode_base64(&reference.digest_value)?[..] == digest[..] {
+        Ok(canonical)
+    } else {
+        Err(DsigError::DigestMismatch(uri.to_string()))
+    }
+}
+
+fn insert_signature(xml: &str, signature: &str) -> DsigResult<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut depth = 0usize;
+    loop {
+        let position = reader.buffer_position() as usize;
+        match reader.read_event()? {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok([&xml[..position], signature, &xml[position..]].concat());
+                }
+            }
+            Event::Empty(e) if depth == 0 => {
+                // `<root/>` becomes `<root>signature</root>`
+                let end = reader.buffer_position() as usize;
+                let start_tag = xml[position..end].trim_end_matches("/>");
+                let name =
+                    std::str::from_utf8(e.name().into_inner()).map_err(XmlSerializeError::from)?;
+                return Ok(format!(
+                    "{}{start_tag}>{signature}</{name}>{}",
+                    &xml[..position],
+                    &xml[end..]
+                ));
+            }
+            Event::Eof => return Err(DsigError::MissingRoot),
+            _ => {}
+        }
+    }
+}
+
+fn signature_selector() -> ElementSelector {
+    ElementSelector::Name {
+        namespace: DS_NS.to_string(),
+        local_name: "Signature".to_string(),
+    }
+}
+
+fn signed_info_selector() -> ElementSelector {
+    ElementSelector::Name {
+        namespace: DS_NS.to_string(),
+        local_name: "SignedInfo".to_string(),
+    }
+}
+
+fn inclusive_prefixes(inclusive_namespaces: Option<&InclusiveNamespaces>) -> Vec<String> {
+    inclusive_namespaces.map_or_else(Vec::new, InclusiveNamespaces::prefixes)
+}
+
+fn decode_base64(value: &str) -> DsigResult<Vec<u8>> {
+    let value: String = value.split_ascii_whitespace().collect();
+    Ok(BASE64.decode(value)?)
+}
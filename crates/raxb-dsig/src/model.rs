@@ -0,0 +1,159 @@
+use raxb::{value::ConstStr, XmlDeserialize, XmlSerialize};
+
+/// `ds:Signature`
+#[derive(Debug, Clone, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"Signature", tns(b"ds", b"http://www.w3.org/2000/09/xmldsig#"))]
+pub struct Signature {
+    #[raxb(
+        default,
+        ns = b"xmlns",
+        name = b"ds",
+        ty = "attr",
+        value = "http://www.w3.org/2000/09/xmldsig#"
+    )]
+    _xmlns: ConstStr,
+    #[raxb(name = b"Id", ty = "attr")]
+    pub id: Option<String>,
+    #[raxb(ns = b"ds", name = b"SignedInfo", ty = "child")]
+    pub signed_info: SignedInfo,
+    #[raxb(ns = b"ds", name = b"SignatureValue", ty = "child")]
+    pub signature_value: String,
+    #[raxb(ns = b"ds", name = b"KeyInfo", ty = "child")]
+    pub key_info: Option<KeyInfo>,
+}
+
+/// `ds:SignedInfo`, the part of the signature that is signed.
+#[derive(Debug, Clone, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(tns(b"ds", b"http://www.w3.org/2000/09/xmldsig#"))]
+pub struct SignedInfo {
+    #[raxb(name = b"Id", ty = "attr")]
+    pub id: Option<String>,
+    #[raxb(ns = b"ds", name = b"CanonicalizationMethod", ty = "child")]
+    pub canonicalization_method: CanonicalizationMethod,
+    #[raxb(ns = b"ds", name = b"SignatureMethod", ty = "child")]
+    pub signature_method: SignatureMethod,
+    #[raxb(ns = b"ds", name = b"Reference", ty = "child")]
+    pub references: Vec<Reference>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(tns(b"ds", b"http://www.w3.org/2000/09/xmldsig#"))]
+pub struct CanonicalizationMethod {
+    #[raxb(name = b"Algorithm", ty = "attr")]
+    pub algorithm: String,
+    #[raxb(ns = b"ec", name = b"InclusiveNamespaces", ty = "child")]
+    pub inclusive_namespaces: Option<InclusiveNamespaces>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(tns(b"ds", b"http://www.w3.org/2000/09/xmldsig#"))]
+pub struct SignatureMethod {
+    #[raxb(name = b"Algorithm", ty = "attr")]
+    pub algorithm: String,
+}
+
+/// `ds:Reference`, the digest of a signed document or element.
+#[derive(Debug, Clone, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(tns(b"ds", b"http://www.w3.org/2000/09/xmldsig#"))]
+pub struct Reference {
+    #[raxb(name = b"Id", ty = "attr")]
+    pub id: Option<String>,
+    #[raxb(name = b"URI", ty = "attr")]
+    pub uri: Option<String>,
+    #[raxb(ns = b"ds", name = b"Transforms", ty = "child")]
+    pub transforms: Option<Transforms>,
+    #[raxb(ns = b"ds", name = b"DigestMethod", ty = "child")]
+    pub digest_method: DigestMethod,
+    #[raxb(ns = b"ds", name = b"DigestValue", ty = "child")]
+    pub digest_value: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(tns(b"ds", b"http://www.w3.org/2000/09/xmldsig#"))]
+pub struct Transforms {
+    #[raxb(ns = b"ds", name = b"Transform", ty = "child")]
+    pub transforms: Vec<Transform>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(tns(b"ds", b"http://www.w3.org/2000/09/xmldsig#"))]
+pub struct Transform {
+    #[raxb(name = b"Algorithm", ty = "attr")]
+    pub algorithm: String,
+    #[raxb(ns = b"ec", name = b"InclusiveNamespaces", ty = "child")]
+    pub inclusive_namespaces: Option<InclusiveNamespaces>,
+}
+
+/// `ec:InclusiveNamespaces` of the exclusive canonicalization algorithms.
+#[derive(Debug, Clone, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(tns(b"ec", b"http://www.w3.org/2001/10/xml-exc-c14n#"))]
+pub struct InclusiveNamespaces {
+    #[raxb(
+        default,
+        ns = b"xmlns",
+        name = b"ec",
+        ty = "attr",
+        value = "http://www.w3.org/2001/10/xml-exc-c14n#"
+    )]
+    _xmlns: ConstStr,
+    #[raxb(name = b"PrefixList", ty = "attr")]
+    pub prefix_list: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(tns(b"ds", b"http://www.w3.org/2000/09/xmldsig#"))]
+pub struct DigestMethod {
+    #[raxb(name = b"Algorithm", ty = "attr")]
+    pub algorithm: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(tns(b"ds", b"http://www.w3.org/2000/09/xmldsig#"))]
+pub struct KeyInfo {
+    #[raxb(ns = b"ds", name = b"KeyName", ty = "child")]
+    pub key_name: Option<String>,
+    #[raxb(ns = b"ds", name = b"X509Data", ty = "child")]
+    pub x509_data: Option<X509Data>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(tns(b"ds", b"http://www.w3.org/2000/09/xmldsig#"))]
+pub struct X509Data {
+    /// Base64 encoded DER certificates.
+    #[raxb(ns = b"ds", name = b"X509Certificate", ty = "child")]
+    pub certificates: Vec<String>,
+}
+
+impl Signature {
+    /// An unsigned signature, without `SignatureValue`.
+    pub fn new(signed_info: SignedInfo) -> Self {
+        Self {
+            signed_info,
+            ..Default::default()
+        }
+    }
+}
+
+impl InclusiveNamespaces {
+    pub fn new(prefix_list: impl Into<String>) -> Self {
+        Self {
+            prefix_list: prefix_list.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn prefixes(&self) -> Vec<String> {
+        self.prefix_list
+            .split_ascii_whitespace()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+impl Reference {
+    pub fn transforms(&self) -> &[Transform] {
+        self.transforms
+            .as_ref()
+            .map_or(&[], |transforms| transforms.transforms.as_slice())
+    }
+}
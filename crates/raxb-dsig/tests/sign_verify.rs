@@ -0,0 +1,352 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use raxb::{
+    ser::{
+        canonicalize_str, canonicalize_str_with_options, C14nMode, C14nOptions, ElementSelector,
+        XmlSerializeError,
+    },
+    XmlDeserialize, XmlSerialize,
+};
+use raxb_dsig::{
+    model::{DigestMethod, InclusiveNamespaces, Reference, SignatureMethod, Transform, Transforms},
+    DsigError, Signature, SignedInfo, SigningKey, VerifyingKey, ECDSA_SHA256, ENVELOPED_SIGNATURE,
+    RSA_SHA256, SHA256,
+};
+use sha2::{Digest, Sha256};
+
+const RSA_KEY: &str = include_str!("keys/rsa.pem");
+const RSA_PUBLIC_KEY: &str = include_str!("keys/rsa.pub.pem");
+const EC_KEY: &str = include_str!("keys/ec.pem");
+const EC_PUBLIC_KEY: &str = include_str!("keys/ec.pub.pem");
+const EC_OTHER_KEY: &str = include_str!("keys/ec-other.pem");
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Line {
+    #[raxb(name = b"sku", ty = "attr")]
+    pub sku: String,
+    #[raxb(name = b"amount", ty = "child")]
+    pub amount: u32,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"invoice")]
+pub struct Invoice {
+    #[raxb(name = b"number", ty = "attr")]
+    pub number: String,
+    #[raxb(name = b"line", ty = "child")]
+    pub lines: Vec<Line>,
+}
+
+fn invoice() -> Invoice {
+    Invoice {
+        number: "2024-001".to_string(),
+        lines: vec![
+            Line {
+                sku: "A-1".to_string(),
+                amount: 2,
+            },
+            Line {
+                sku: "B-2".to_string(),
+                amount: 5,
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_sign_and_verify_rsa() -> anyhow::Result<()> {
+    let key = SigningKey::from_pem(RSA_KEY)?;
+    let xml = raxb_dsig::sign(&invoice(), &key)?;
+    assert!(xml.starts_with(r#"<invoice number="2024-001"><line sku="A-1">"#));
+    assert!(xml.ends_with("</ds:Signature></invoice>"));
+
+    let verified = raxb_dsig::verify(&xml, &VerifyingKey::from_pem(RSA_PUBLIC_KEY)?)?;
+    let signed_info = &verified.signature.signed_info;
+    assert_eq!(signed_info.signature_method.algorithm, RSA_SHA256);
+    assert_eq!(
+        signed_info.canonicalization_method.algorithm,
+        C14nMode::Exclusive.algorithm()
+    );
+    assert_eq!(signed_info.references.len(), 1);
+    let reference = &signed_info.references[0];
+    assert_eq!(reference.uri.as_deref(), Some(""));
+    assert_eq!(reference.transforms()[0].algorithm, ENVELOPED_SIGNATURE);
+    assert_eq!(reference.digest_method.algorithm, SHA256);
+    assert_eq!(
+        verified.references,
+        vec![canonicalize_str(
+            &raxb::ser::to_string(&invoice())?,
+            C14nMode::Exclusive
+        )?]
+    );
+
+    // the signed document can still be read
+    let invoice: Invoice = raxb::de::from_str(&xml)?;
+    assert_eq!(invoice, self::invoice());
+    Ok(())
+}
+
+#[test]
+fn test_sign_and_verify_ecdsa() -> anyhow::Result<()> {
+    let key = SigningKey::from_pem(EC_KEY)?;
+    let xml = raxb_dsig::sign(&invoice(), &key)?;
+    let verified = raxb_dsig::verify(&xml, &VerifyingKey::from_pem(EC_PUBLIC_KEY)?)?;
+    assert_eq!(
+        verified.signature.signed_info.signature_method.algorithm,
+        ECDSA_SHA256
+    );
+    raxb_dsig::verify(&xml, &key.verifying_key())?;
+    Ok(())
+}
+
+#[test]
+fn test_sign_empty_root() -> anyhow::Result<()> {
+    let key = SigningKey::from_pem(EC_KEY)?;
+    let xml = raxb_dsig::sign_document(r#"<doc id="1" />"#, &key)?;
+    assert!(xml.starts_with(r#"<doc id="1" ><ds:Signature"#));
+    raxb_dsig::verify(&xml, &key.verifying_key())?;
+    Ok(())
+}
+
+#[test]
+fn test_verify_canonicalized_document() -> anyhow::Result<()> {
+    let key = SigningKey::from_pem(RSA_KEY)?;
+    let xml = raxb_dsig::sign(&invoice(), &key)?;
+    let canonical = String::from_utf8(canonicalize_str(&xml, C14nMode::Exclusive)?)?;
+    assert_ne!(canonical, xml);
+    raxb_dsig::verify(&canonical, &key.verifying_key())?;
+    Ok(())
+}
+
+#[test]
+fn test_tampered_document() -> anyhow::Result<()> {
+    let key = SigningKey::from_pem(RSA_KEY)?;
+    let xml = raxb_dsig::sign(&invoice(), &key)?;
+    let tampered = xml.replace("<amount>5</amount>", "<amount>50</amount>");
+    assert!(matches!(
+        raxb_dsig::verify(&tampered, &key.verifying_key()),
+        Err(DsigError::DigestMismatch(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_wrong_key() -> anyhow::Result<()> {
+    let xml = raxb_dsig::sign(&invoice(), &SigningKey::from_pem(EC_KEY)?)?;
+    let other_key = SigningKey::from_pem(EC_OTHER_KEY)?;
+    assert!(matches!(
+        raxb_dsig::verify(&xml, &other_key.verifying_key()),
+        Err(DsigError::InvalidSignature)
+    ));
+    assert!(matches!(
+        raxb_dsig::verify(&xml, &VerifyingKey::from_pem(RSA_PUBLIC_KEY)?),
+        Err(DsigError::UnsupportedAlgorithm(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_missing_signature() {
+    let key = VerifyingKey::from_pem(RSA_PUBLIC_KEY).unwrap();
+    assert!(matches!(
+        raxb_dsig::verify("<doc/>", &key),
+        Err(DsigError::MissingSignature)
+    ));
+}
+
+#[test]
+fn test_verify_reference_by_id() -> anyhow::Result<()> {
+    use p256::ecdsa::signature::Signer;
+
+    let document = r#"<doc xmlns="urn:doc" xmlns:x="urn:x"><data Id="d1" x:kind="a"><v>1</v></data><other/></doc>"#;
+    let mode = C14nMode::Exclusive;
+    let data = canonicalize_str_with_options(
+        document,
+        mode,
+        &C14nOptions {
+            subtree: Some(ElementSelector::Id("d1".to_string())),
+            inclusive_prefixes: vec!["#default".to_string()],
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(
+        String::from_utf8(data.clone())?,
+        r#"<data xmlns="urn:doc" xmlns:x="urn:x" Id="d1" x:kind="a"><v>1</v></data>"#
+    );
+    let mut signature = Signature::new(SignedInfo {
+        canonicalization_method: raxb_dsig::model::CanonicalizationMethod {
+            algorithm: mode.algorithm().to_string(),
+            inclusive_namespaces: None,
+        },
+        signature_method: SignatureMethod {
+            algorithm: ECDSA_SHA256.to_string(),
+        },
+        references: vec![Reference {
+            uri: Some("#d1".to_string()),
+            transforms: Some(Transforms {
+                transforms: vec![Transform {
+                    algorithm: mode.algorithm().to_string(),
+                    inclusive_namespaces: Some(InclusiveNamespaces::new("#default")),
+                }],
+            }),
+            digest_method: DigestMethod {
+                algorithm: SHA256.to_string(),
+            },
+            digest_value: BASE64.encode(Sha256::digest(&data)),
+            ..Default::default()
+        }],
+        ..Default::default()
+    });
+    let signed_info = canonicalize_str_with_options(
+        &raxb::ser::to_string(&signature)?,
+        mode,
+        &C14nOptions {
+            subtree: Some(ElementSelector::Name {
+                namespace: raxb_dsig::DS_NS.to_string(),
+                local_name: "SignedInfo".to_string(),
+            }),
+            ..Default::default()
+        },
+    )?;
+    let key = p256::ecdsa::SigningKey::from(p256::SecretKey::from_sec1_pem(EC_KEY)?);
+    let value: p256::ecdsa::Signature = key.sign(&signed_info);
+    signature.signature_value = BASE64.encode(value.to_bytes());
+    let xml = document.replace(
+        "</doc>",
+        &format!("{}</doc>", raxb::ser::to_string(&signature)?),
+    );
+
+    let verified = raxb_dsig::verify(&xml, &VerifyingKey::from_pem(EC_PUBLIC_KEY)?)?;
+    assert_eq!(verified.signature, signature);
+    assert_eq!(verified.references, vec![data]);
+    assert!(matches!(
+        raxb_dsig::verify(
+            &xml.replace("<v>1</v>", "<v>2</v>"),
+            &VerifyingKey::from_pem(EC_PUBLIC_KEY)?
+        ),
+        Err(DsigError::DigestMismatch(uri)) if uri == "#d1"
+    ));
+    // a second element with the id must not be taken for the signed one
+    let wrapped = xml.replace(
+        "<other/>",
+        r#"<other><data Id="d1" x:kind="a"><v>2</v></data></other>"#,
+    );
+    assert!(matches!(
+        raxb_dsig::verify(&wrapped, &VerifyingKey::from_pem(EC_PUBLIC_KEY)?),
+        Err(DsigError::Serialize(XmlSerializeError::DuplicateId(id))) if id == "d1"
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_reference_to_missing_id() -> anyhow::Result<()> {
+    use p256::ecdsa::signature::Signer;
+
+    let mode = C14nMode::Exclusive;
+    // the digest of the empty canonical form of a missing element
+    let mut signature = Signature::new(SignedInfo {
+        canonicalization_method: raxb_dsig::model::CanonicalizationMethod {
+            algorithm: mode.algorithm().to_string(),
+            inclusive_namespaces: None,
+        },
+        signature_method: SignatureMethod {
+            algorithm: ECDSA_SHA256.to_string(),
+        },
+        references: vec![Reference {
+            uri: Some("#missing".to_string()),
+            digest_method: DigestMethod {
+                algorithm: SHA256.to_string(),
+            },
+            digest_value: BASE64.encode(Sha256::digest(b"")),
+            ..Default::default()
+        }],
+        ..Default::default()
+    });
+    let signed_info = canonicalize_str_with_options(
+        &raxb::ser::to_string(&signature)?,
+        mode,
+        &C14nOptions {
+            subtree: Some(ElementSelector::Name {
+                namespace: raxb_dsig::DS_NS.to_string(),
+                local_name: "SignedInfo".to_string(),
+            }),
+            ..Default::default()
+        },
+    )?;
+    let key = p256::ecdsa::SigningKey::from(p256::SecretKey::from_sec1_pem(EC_KEY)?);
+    let value: p256::ecdsa::Signature = key.sign(&signed_info);
+    signature.signature_value = BASE64.encode(value.to_bytes());
+    let xml = format!(
+        r#"<doc><data Id="d1"/>{}</doc>"#,
+        raxb::ser::to_string(&signature)?
+    );
+    assert!(matches!(
+        raxb_dsig::verify(&xml, &VerifyingKey::from_pem(EC_PUBLIC_KEY)?),
+        Err(DsigError::MissingReference(uri)) if uri == "#missing"
+    ));
+    Ok(())
+}
+
+fn between<'a>(s: &'a str, start: &str, end: &str) -> &'a str {
+    let from = s.find(start).unwrap();
+    let to = from + s[from..].find(end).unwrap() + end.len();
+    &s[from..to]
+}
+
+#[test]
+fn test_signed_info_wrapping() -> anyhow::Result<()> {
+    let key = SigningKey::from_pem(RSA_KEY)?;
+    let xml = raxb_dsig::sign(&invoice(), &key)?;
+    let signature = between(&xml, "<ds:Signature", "</ds:Signature>");
+    let signed_info = between(&xml, "<ds:SignedInfo", "</ds:SignedInfo>").replacen(
+        "<ds:SignedInfo",
+        &format!(r#"<ds:SignedInfo xmlns:ds="{}""#, raxb_dsig::DS_NS),
+        1,
+    );
+    // the original SignedInfo matches the signature value, the signature itself references the
+    // tampered document
+    let tampered = xml
+        .replace(signature, "")
+        .replace("<amount>5</amount>", "<amount>50</amount>")
+        .replacen(
+            "<line ",
+            &format!("<extension>{signed_info}</extension><line "),
+            1,
+        );
+    let digest = BASE64.encode(Sha256::digest(canonicalize_str(
+        &tampered,
+        C14nMode::Exclusive,
+    )?));
+    let digest_value = between(signature, "<ds:DigestValue>", "</ds:DigestValue>");
+    let forged = tampered.replace(
+        "</invoice>",
+        &format!(
+            "{}</invoice>",
+            signature.replace(
+                digest_value,
+                &format!("<ds:DigestValue>{digest}</ds:DigestValue>")
+            )
+        ),
+    );
+    assert!(matches!(
+        raxb_dsig::verify(&forged, &key.verifying_key()),
+        Err(DsigError::DuplicateElement(name)) if name == "SignedInfo"
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_duplicate_signature() -> anyhow::Result<()> {
+    let key = SigningKey::from_pem(RSA_KEY)?;
+    let xml = raxb_dsig::sign(&invoice(), &key)?;
+    let signature = between(&xml, "<ds:Signature", "</ds:Signature>");
+    let twice = xml.replace("</invoice>", &format!("{signature}</invoice>"));
+    assert!(matches!(
+        raxb_dsig::verify(&twice, &key.verifying_key()),
+        Err(DsigError::DuplicateElement(name)) if name == "Signature"
+    ));
+    assert!(matches!(
+        raxb_dsig::read_signature(&twice),
+        Err(DsigError::DuplicateElement(_))
+    ));
+    Ok(())
+}
@@ -0,0 +1,480 @@
+//! Canonical XML ([C14N 1.0](https://www.w3.org/TR/xml-c14n) and
+//! [Exclusive C14N](https://www.w3.org/TR/xml-exc-c14n/)) of documents, single elements or
+//! documents without an element.
+//!
+//! Document type declarations are dropped and not processed, so default attributes and
+//! entities declared in a DTD are not supported.
+
+use std::collections::BTreeMap;
+
+use quick_xml::{
+    escape::unescape,
+    events::{BytesStart, Event},
+    Reader,
+};
+
+use crate::ser::{XmlSerializeError, XmlSerializeResult};
+
+const XML_PREFIX: &str = "xml";
+const XML_NS: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// The canonicalization algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum C14nMode {
+    /// Canonical XML 1.0, comments are removed.
+    Inclusive,
+    /// Exclusive canonical XML 1.0, comments are removed.
+    Exclusive,
+    /// Canonical XML 1.0 with comments.
+    WithComments,
+    /// Exclusive canonical XML 1.0 with comments.
+    ExclusiveWithComments,
+}
+
+impl C14nMode {
+    pub fn is_exclusive(&self) -> bool {
+        matches!(self, Self::Exclusive | Self::ExclusiveWithComments)
+    }
+
+    pub fn with_comments(&self) -> bool {
+        matches!(self, Self::WithComments | Self::ExclusiveWithComments)
+    }
+
+    /// The algorithm identifier used by XML signatures.
+    pub fn algorithm(&self) -> &'static str {
+        match self {
+            Self::Inclusive => "http://www.w3.org/TR/2001/REC-xml-c14n-20010315",
+            Self::Exclusive => "http://www.w3.org/2001/10/xml-exc-c14n#",
+            Self::WithComments => "http://www.w3.org/TR/2001/REC-xml-c14n-20010315#WithComments",
+            Self::ExclusiveWithComments => "http://www.w3.org/2001/10/xml-exc-c14n#WithComments",
+        }
+    }
+
+    pub fn from_algorithm(algorithm: &str) -> Option<Self> {
+        [
+            Self::Inclusive,
+            Self::Exclusive,
+            Self::WithComments,
+            Self::ExclusiveWithComments,
+        ]
+        .into_iter()
+        .find(|mode| mode.algorithm() == algorithm)
+    }
+}
+
+/// Selects an element of a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ElementSelector {
+    /// The first element with the namespace and local name.
+    Name {
+        namespace: String,
+        local_name: String,
+    },
+    /// The element with an `Id`, `ID` or `id` attribute of the value. Selecting an id that more
+    /// than one element has is an error.
+    Id(String),
+}
+
+/// Selects the part of a document to canonicalize.
+#[derive(Debug, Clone, Default)]
+pub struct C14nOptions {
+    /// Canonicalize only the selected element and its descendants instead of the document.
+    /// Selecting no element is an error.
+    pub subtree: Option<ElementSelector>,
+    /// Omit the selected element and its descendants, e.g. an enveloped signature.
+    pub exclude: Option<ElementSelector>,
+    /// Prefixes that exclusive canonicalization handles like inclusive canonicalization
+    /// (`InclusiveNamespaces PrefixList`), `#default` is the default namespace.
+    pub inclusive_prefixes: Vec<String>,
+}
+
+/// Canonicalizes an XML document. The input must be UTF-8, unless the `encoding` feature is
+/// enabled.
+pub fn canonicalize(bytes: &[u8], mode: C14nMode) -> XmlSerializeResult<Vec<u8>> {
+    canonicalize_with_options(bytes, mode, &C14nOptions::default())
+}
+
+/// Canonicalizes the part of an XML document selected by `options`, see [`canonicalize`].
+pub fn canonicalize_with_options(
+    bytes: &[u8],
+    mode: C14nMode,
+    options: &C14nOptions,
+) -> XmlSerializeResult<Vec<u8>> {
+    #[cfg(feature = "encoding")]
+    {
+        use crate::de::XmlDeserializeError;
+        let s = crate::encoding::decode(bytes).map_err(|err| match err {
+            XmlDeserializeError::UnsupportedEncoding(label) => {
+                XmlSerializeError::UnsupportedEncoding(label)
+            }
+            err => XmlSerializeError::Decoding(err.to_string()),
+        })?;
+        canonicalize_str_with_options(&s, mode, options)
+    }
+    #[cfg(not(feature = "encoding"))]
+    {
+        canonicalize_str_with_options(std::str::from_utf8(bytes)?, mode, options)
+    }
+}
+
+/// Canonicalizes an XML document.
+pub fn canonicalize_str(s: &str, mode: C14nMode) -> XmlSerializeResult<Vec<u8>> {
+    canonicalize_str_with_options(s, mode, &C14nOptions::default())
+}
+
+/// Canonicalizes the part of an XML document selected by `options`.
+pub fn canonicalize_str_with_options(
+    s: &str,
+    mode: C14nMode,
+    options: &C14nOptions,
+) -> XmlSerializeResult<Vec<u8>> {
+    let mut reader = Reader::from_str(s);
+    reader.config_mut().trim_text(false);
+    reader.config_mut().expand_empty_elements = true;
+    let mut output = String::with_capacity(s.len());
+    // in-scope and rendered namespaces of the open elements, keyed by prefix
+    let mut scopes = vec![Scope::default()];
+    let mut seen_root = false;
+    // depths of the selected subtree and the excluded element
+    let mut subtree_depth = None;
+    let mut subtree_found = false;
+    let mut excluded_depth = None;
+    loop {
+        let event = reader.read_event()?;
+        let depth = scopes.len();
+        let outside_root = depth == 1;
+        let render =
+            (options.subtree.is_none() || subtree_depth.is_some()) && excluded_depth.is_none();
+        match event {
+            Event::Start(e) => {
+                let element = Element::parse(&e, scopes.last().unwrap())?;
+                if excluded_depth.is_none()
+                    && options.exclude.as_ref().is_some_and(|s| element.matches(s))
+                {
+                    excluded_depth = Some(depth);
+                } else if let Some(subtree) =
+                    options.subtree.as_ref().filter(|s| element.matches(s))
+                {
+                    if !subtree_found {
+                        subtree_depth = Some(depth);
+                        subtree_found = true;
+                    } else if let ElementSelector::Id(id) = subtree {
+                        return Err(XmlSerializeError::DuplicateId(id.clone()));
+                    }
+                }
+                let render = (options.subtree.is_none() || subtree_depth.is_some())
+                    && excluded_depth.is_none();
+                let scope = if render {
+                    let is_apex = subtree_depth == Some(depth);
+                    element.render(scopes.last().unwrap(), mode, options, is_apex, &mut output)?
+                } else {
+                    element.scope
+                };
+                scopes.push(scope);
+                seen_root = true;
+            }
+            Event::End(e) => {
+                if render {
+                    output.push_str("</");
+                    output.push_str(std::str::from_utf8(e.name().as_ref())?);
+                    output.push('>');
+                }
+                scopes.pop();
+                if excluded_depth == Some(depth - 1) {
+                    excluded_depth = None;
+                } else if subtree_depth == Some(depth - 1) {
+                    if !matches!(options.subtree, Some(ElementSelector::Id(_))) {
+                        break;
+                    }
+                    // the rest of the document is only checked for duplicate ids
+                    subtree_depth = None;
+                }
+            }
+            Event::Text(t) if render && !outside_root => {
+                let text = normalize_line_endings(std::str::from_utf8(&t)?);
+                escape_text(
+                    &unescape(&text).map_err(quick_xml::Error::from)?,
+                    &mut output,
+                );
+            }
+            Event::CData(t) if render => {
+                escape_text(
+                    &normalize_line_endings(std::str::from_utf8(&t)?),
+                    &mut output,
+                );
+            }
+            Event::Comment(t) if render && mode.with_comments() => {
+                let comment = format!(
+                    "<!--{}-->",
+                    normalize_line_endings(std::str::from_utf8(&t)?)
+                );
+                push_node(&comment, outside_root, seen_root, &mut output);
+            }
+            Event::PI(t) if render => {
+                let content = normalize_line_endings(std::str::from_utf8(&t)?);
+                let (target, data) = content
+                    .split_once(|c: char| c.is_ascii_whitespace())
+                    .map_or((content.as_ref(), ""), |(target, data)| {
+                        (target, data.trim_start())
+                    });
+                let pi = if data.is_empty() {
+                    format!("<?{target}?>")
+                } else {
+                    format!("<?{target} {data}?>")
+                };
+                push_node(&pi, outside_root, seen_root, &mut output);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    match options.subtree.as_ref() {
+        Some(ElementSelector::Id(id)) if !subtree_found => {
+            Err(XmlSerializeError::MissingSubtree(id.clone()))
+        }
+        Some(ElementSelector::Name { local_name, .. }) if !subtree_found => {
+            Err(XmlSerializeError::MissingSubtree(local_name.clone()))
+        }
+        _ => Ok(output.into_bytes()),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Scope {
+    in_scope: BTreeMap<String, String>,
+    rendered: BTreeMap<String, String>,
+    /// `xml:*` attributes of the element and its ancestors
+    xml_attributes: BTreeMap<String, String>,
+}
+
+impl Scope {
+    fn resolve(&self, prefix: &str) -> XmlSerializeResult<&str> {
+        match prefix {
+            XML_PREFIX => Ok(XML_NS),
+            "" => Ok(self.in_scope.get("").map_or("", String::as_str)),
+            prefix => self
+                .in_scope
+                .get(prefix)
+                .map(String::as_str)
+                .ok_or_else(|| XmlSerializeError::UnboundPrefix(prefix.to_string())),
+        }
+    }
+
+    /// Whether the namespace declaration is already rendered by an output ancestor. An empty
+    /// default namespace is implicitly rendered.
+    fn is_rendered(&self, prefix: &str, uri: &str) -> bool {
+        match self.rendered.get(prefix) {
+            Some(rendered) => rendered == uri,
+            None => prefix.is_empty() && uri.is_empty(),
+        }
+    }
+}
+
+struct Element {
+    name: String,
+    namespace: String,
+    attributes: Vec<(String, String)>,
+    scope: Scope,
+}
+
+impl Element {
+    fn parse(e: &BytesStart, parent: &Scope) -> XmlSerializeResult<Self> {
+        let name = std::str::from_utf8(e.name().as_ref())?.to_string();
+        let mut scope = parent.clone();
+        let mut attributes = Vec::new();
+        for attr in e.attributes() {
+            let attr = attr.map_err(quick_xml::Error::from)?;
+            let key = std::str::from_utf8(attr.key.as_ref())?;
+            let value = normalize_attribute_value(std::str::from_utf8(&attr.value)?)?;
+            if key == "xmlns" {
+                scope.in_scope.insert(String::new(), value);
+            } else if let Some(prefix) = key.strip_prefix("xmlns:") {
+                if prefix != XML_PREFIX {
+                    scope.in_scope.insert(prefix.to_string(), value);
+                }
+            } else {
+                if prefix_of(key) == XML_PREFIX {
+                    scope.xml_attributes.insert(key.to_string(), value.clone());
+                }
+                attributes.push((key.to_string(), value));
+            }
+        }
+        let namespace = scope.resolve(prefix_of(&name))?.to_string();
+        Ok(Self {
+            name,
+            namespace,
+            attributes,
+            scope,
+        })
+    }
+
+    fn local_name(&self) -> &str {
+        self.name
+            .split_once(':')
+            .map_or(self.name.as_str(), |(_, local_name)| local_name)
+    }
+
+    fn matches(&self, selector: &ElementSelector) -> bool {
+        match selector {
+            ElementSelector::Name {
+                namespace,
+                local_name,
+            } => self.namespace == *namespace && self.local_name() == local_name,
+            ElementSelector::Id(id) => self
+                .attributes
+                .iter()
+                .any(|(key, value)| matches!(key.as_str(), "Id" | "ID" | "id") && value == id),
+        }
+    }
+
+    /// Writes the start tag, `is_apex` is set for the first element of a document subset.
+    fn render(
+        self,
+        parent: &Scope,
+        mode: C14nMode,
+        options: &C14nOptions,
+        is_apex: bool,
+        output: &mut String,
+    ) -> XmlSerializeResult<Scope> {
+        let Self {
+            name,
+            mut attributes,
+            mut scope,
+            ..
+        } = self;
+        if is_apex && !mode.is_exclusive() {
+            // inclusive canonicalization inherits xml:* attributes of omitted ancestors
+            for (key, value) in &parent.xml_attributes {
+                if !attributes.iter().any(|(k, _)| k == key) {
+                    attributes.push((key.clone(), value.clone()));
+                }
+            }
+        }
+        let candidates: Vec<String> = if mode.is_exclusive() {
+            // visibly utilized prefixes
+            let mut prefixes = vec![prefix_of(&name)];
+            prefixes.extend(
+                attributes
+                    .iter()
+                    .map(|(key, _)| prefix_of(key))
+                    .filter(|prefix| !prefix.is_empty()),
+            );
+            prefixes.extend(
+                options
+                    .inclusive_prefixes
+                    .iter()
+                    .map(|prefix| match prefix.as_str() {
+                        "#default" => "",
+                        prefix => prefix,
+                    })
+                    .filter(|prefix| prefix.is_empty() || scope.in_scope.contains_key(*prefix)),
+            );
+            prefixes
+                .into_iter()
+                .filter(|prefix| *prefix != XML_PREFIX)
+                .map(str::to_string)
+                .collect()
+        } else {
+            scope.in_scope.keys().cloned().collect()
+        };
+        let mut declarations = BTreeMap::new();
+        for prefix in candidates {
+            let uri = scope.resolve(&prefix)?.to_string();
+            if !parent.is_rendered(&prefix, &uri) {
+                declarations.insert(prefix, uri);
+            }
+        }
+        scope.rendered.extend(declarations.clone());
+
+        let mut sorted_attributes = Vec::with_capacity(attributes.len());
+        for (key, value) in attributes {
+            let (uri, local_name) = match key.split_once(':') {
+                Some((prefix, local_name)) => (scope.resolve(prefix)?, local_name),
+                None => ("", key.as_str()),
+            };
+            sorted_attributes.push(((uri.to_string(), local_name.to_string()), key, value));
+        }
+        sorted_attributes.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+
+        output.push('<');
+        output.push_str(&name);
+        for (prefix, uri) in declarations {
+            if prefix.is_empty() {
+                output.push_str(" xmlns=\"");
+            } else {
+                output.push_str(" xmlns:");
+                output.push_str(&prefix);
+                output.push_str("=\"");
+            }
+            escape_attribute(&uri, output);
+            output.push('"');
+        }
+        for (_, key, value) in sorted_attributes {
+            output.push(' ');
+            output.push_str(&key);
+            output.push_str("=\"");
+            escape_attribute(&value, output);
+            output.push('"');
+        }
+        output.push('>');
+        Ok(scope)
+    }
+}
+
+fn prefix_of(qname: &str) -> &str {
+    qname.split_once(':').map_or("", |(prefix, _)| prefix)
+}
+
+/// Comments and processing instructions outside of the document element are separated
+/// from it by a line feed.
+fn push_node(node: &str, outside_root: bool, seen_root: bool, output: &mut String) {
+    if outside_root && seen_root {
+        output.push('\n');
+    }
+    output.push_str(node);
+    if outside_root && !seen_root {
+        output.push('\n');
+    }
+}
+
+fn normalize_line_endings(s: &str) -> std::borrow::Cow<'_, str> {
+    if s.contains('\r') {
+        s.replace("\r\n", "\n").replace('\r', "\n").into()
+    } else {
+        s.into()
+    }
+}
+
+/// Attribute-value normalization for attributes of type CDATA.
+fn normalize_attribute_value(raw: &str) -> XmlSerializeResult<String> {
+    let normalized = normalize_line_endings(raw).replace(['\t', '\n'], " ");
+    Ok(unescape(&normalized)
+        .map_err(quick_xml::Error::from)?
+        .into_owned())
+}
+
+fn escape_text(s: &str, output: &mut String) {
+    for c in s.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '\r' => output.push_str("&#xD;"),
+            c => output.push(c),
+        }
+    }
+}
+
+fn escape_attribute(s: &str, output: &mut String) {
+    for c in s.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '"' => output.push_str("&quot;"),
+            '\t' => output.push_str("&#x9;"),
+            '\n' => output.push_str("&#xA;"),
+            '\r' => output.push_str("&#xD;"),
+            c => output.push(c),
+        }
+    }
+}
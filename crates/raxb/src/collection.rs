@@ -0,0 +1,143 @@
+//! Collections of repeated elements, e.g. `#[raxb(name = b"line", ty = "child")] lines: Vec<Line>`.
+//!
+//! The derived implementations read the items of a repeated field into a `Vec` in document order
+//! and build the field with [`XmlCollection::from_items`]. They are written by iterating over
+//! `.iter()` of the collection. Attributes not bound to another field are collected with
+//! [`XmlAnyAttributes`].
+//!
+//! The derive recognizes repeated fields by the last segment of the field type as written:
+//! `Vec`, `VecDeque`, `BTreeSet`, `HashSet`, `IndexSet`, `SmallVec` and arrays `[T; N]`, maps by
+//! `BTreeMap`, `HashMap` and `IndexMap`. Type aliases, renamed imports and other implementations
+//! of [`XmlCollection`] are not recognized, while unrelated types of one of these names are taken
+//! for collections.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet, VecDeque},
+    hash::{BuildHasher, Hash},
+};
+
+use crate::{facet::Facet, value::QName};
+
+/// A collection of the items of a repeated element.
+pub trait XmlCollection: Sized {
+    type Item;
+
+    /// Builds the collection from the items in document order, fails with the violated
+    /// occurrence facet if the collection cannot hold that number of items.
+    fn from_items(items: Vec<Self::Item>) -> Result<Self, Facet>;
+}
+
+impl<T> XmlCollection for Vec<T> {
+    type Item = T;
+
+    fn from_items(items: Vec<T>) -> Result<Self, Facet> {
+        Ok(items)
+    }
+}
+
+impl<T> XmlCollection for VecDeque<T> {
+    type Item = T;
+
+    fn from_items(items: Vec<T>) -> Result<Self, Facet> {
+        Ok(items.into())
+    }
+}
+
+impl<T: Ord> XmlCollection for BTreeSet<T> {
+    type Item = T;
+
+    fn from_items(items: Vec<T>) -> Result<Self, Facet> {
+        Ok(items.into_iter().collect())
+    }
+}
+
+impl<T, S> XmlCollection for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    type Item = T;
+
+    fn from_items(items: Vec<T>) -> Result<Self, Facet> {
+        Ok(items.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<T, S> XmlCollection for indexmap::IndexSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    type Item = T;
+
+    fn from_items(items: Vec<T>) -> Result<Self, Facet> {
+        Ok(items.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> XmlCollection for smallvec::SmallVec<A> {
+    type Item = A::Item;
+
+    fn from_items(items: Vec<A::Item>) -> Result<Self, Facet> {
+        Ok(smallvec::SmallVec::from_vec(items))
+    }
+}
+
+/// Arrays need exactly `N` items.
+impl<T, const N: usize> XmlCollection for [T; N] {
+    type Item = T;
+
+    fn from_items(items: Vec<T>) -> Result<Self, Facet> {
+        items.try_into().map_err(|items: Vec<T>| {
+            if items.len() < N {
+                Facet::MinOccurs(N)
+            } else {
+                Facet::MaxOccurs(N)
+            }
+        })
+    }
+}
+
+/// Attributes not bound to another field, e.g. `#[raxb(ty = "any_attr")] extra: Vec<(QName, String)>`.
+///
+/// Namespace declarations are kept like other attributes, so the prefixes of the names still
+/// resolve when the attributes are written back.
+pub trait XmlAnyAttributes {
+    fn push_attribute(&mut self, name: QName, value: String);
+
+    /// The attributes in the order they are written.
+    fn iter_attributes(&self) -> impl Iterator<Item = (&QName, &str)>;
+}
+
+impl XmlAnyAttributes for Vec<(QName, String)> {
+    fn push_attribute(&mut self, name: QName, value: String) {
+        self.push((name, value));
+    }
+
+    fn iter_attributes(&self) -> impl Iterator<Item = (&QName, &str)> {
+        self.iter().map(|(name, value)| (name, value.as_str()))
+    }
+}
+
+impl XmlAnyAttributes for BTreeMap<QName, String> {
+    fn push_attribute(&mut self, name: QName, value: String) {
+        self.insert(name, value);
+    }
+
+    fn iter_attributes(&self) -> impl Iterator<Item = (&QName, &str)> {
+        self.iter().map(|(name, value)| (name, value.as_str()))
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<S: BuildHasher> XmlAnyAttributes for indexmap::IndexMap<QName, String, S> {
+    fn push_attribute(&mut self, name: QName, value: String) {
+        self.insert(name, value);
+    }
+
+    fn iter_attributes(&self) -> impl Iterator<Item = (&QName, &str)> {
+        self.iter().map(|(name, value)| (name, value.as_str()))
+    }
+}
@@ -1,17 +1,25 @@
 use quick_xml::events::attributes::Attributes;
-use quick_xml::events::Event;
-use quick_xml::name::ResolveResult;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::{QName, ResolveResult};
 use quick_xml::NsReader;
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::marker::PhantomData;
 use std::num::ParseIntError;
-use std::str::ParseBoolError;
+use std::rc::Rc;
+use std::str::{FromStr, ParseBoolError, Utf8Error};
 use std::string::FromUtf8Error;
+use std::sync::Arc;
 use std::{io::BufRead, num::ParseFloatError};
 use thiserror::Error;
 
-use crate::ty::{XmlTag, XmlTargetNs, S};
+use crate::ty::{XmlTag, XmlTargetNs, S, XSI_NS};
 
 pub type XmlDeserializeResult<T> = Result<T, XmlDeserializeError>;
 
+/// Resolved namespace (if bound) and local name of a QName.
+pub type ResolvedName = (Option<Vec<u8>>, Vec<u8>);
+
 #[derive(Error, Debug)]
 pub enum XmlDeserializeError {
     #[error(transparent)]
@@ -25,6 +33,8 @@ pub enum XmlDeserializeError {
     #[error(transparent)]
     Utf8String(#[from] FromUtf8Error),
     #[error(transparent)]
+    Utf8(#[from] Utf8Error),
+    #[error(transparent)]
     Bool(#[from] ParseBoolError),
     #[error("empty element, try to add #[raxb(default)] attribute")]
     EmptyNode,
@@ -38,6 +48,25 @@ pub enum XmlDeserializeError {
     MissingElement(S),
     #[error("missing attribute '{0}'")]
     MissingAttribute(S),
+    #[error("unexpected end of document before the end tag of an element")]
+    UnexpectedEof,
+    #[error("unsupported encoding '{0}'")]
+    UnsupportedEncoding(String),
+    #[error("malformed input for encoding '{0}'")]
+    Decoding(String),
+    #[error("limit exceeded: {0}")]
+    LimitExceeded(Limit),
+    #[error("{0}")]
+    Custom(String),
+    #[error("constraint violated: {0}")]
+    Constraint(#[from] crate::facet::Violation),
+}
+
+impl XmlDeserializeError {
+    /// Error of a custom conversion, e.g. a `deserialize_with` function.
+    pub fn custom(err: impl std::fmt::Display) -> Self {
+        Self::Custom(err.to_string())
+    }
 }
 
 pub trait XmlDeserialize {
@@ -53,6 +82,11 @@ pub trait XmlDeserialize {
         None
     }
 
+    /// Former namespaces still accepted in place of [`XmlDeserialize::target_ns`].
+    fn target_ns_aliases() -> &'static [XmlTargetNs] {
+        &[]
+    }
+
     fn xml_deserialize<R>(
         reader: &mut NsReader<R>,
         target_ns: XmlTag,
@@ -63,21 +97,602 @@ pub trait XmlDeserialize {
     where
         Self: Sized,
         R: BufRead;
+
+    /// Deserializes the element opened by `start` like [`XmlDeserialize::xml_deserialize`].
+    /// Types that need the original start tag, e.g. [`RawXml`](crate::value::RawXml), override it.
+    /// An empty `tag` stands for the name of `start` if it is not known statically, derived
+    /// structs then read up to the end of `start`.
+    fn xml_deserialize_with_start<R>(
+        reader: &mut NsReader<R>,
+        target_ns: XmlTag,
+        tag: XmlTargetNs,
+        start: &BytesStart,
+        is_empty: bool,
+    ) -> XmlDeserializeResult<Self>
+    where
+        Self: Sized,
+        R: BufRead,
+    {
+        Self::xml_deserialize(reader, target_ns, tag, start.attributes(), is_empty)
+    }
+}
+
+/// Whitespace handling of a text value, matching the XSD `whiteSpace` facet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WhiteSpace {
+    /// Keep the value as it is.
+    Preserve,
+    /// Replace every tab, line feed and carriage return with a space.
+    Replace,
+    /// Like [`WhiteSpace::Replace`], then collapse runs of spaces and trim the value.
+    Collapse,
+}
+
+impl WhiteSpace {
+    pub fn normalize<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        let is_space = |c: char| matches!(c, ' ' | '\t' | '\n' | '\r');
+        match self {
+            Self::Preserve => Cow::Borrowed(value),
+            Self::Replace if value.contains(['\t', '\n', '\r']) => {
+                Cow::Owned(value.replace(['\t', '\n', '\r'], " "))
+            }
+            Self::Replace => Cow::Borrowed(value),
+            Self::Collapse => {
+                let mut result = String::with_capacity(value.len());
+                for part in value.split(is_space).filter(|part| !part.is_empty()) {
+                    if !result.is_empty() {
+                        result.push(' ');
+                    }
+                    result.push_str(part);
+                }
+                if result == value {
+                    Cow::Borrowed(value)
+                } else {
+                    Cow::Owned(result)
+                }
+            }
+        }
+    }
+}
+
+/// Options for [`deserialize_with_options`].
+#[derive(Debug, Clone)]
+pub struct DeserializeOptions {
+    /// Trim leading and trailing whitespace of text content, unless a field sets its own
+    /// `whitespace` handling or the element is marked with `xml:space="preserve"`. Inside such an
+    /// element, `xml:space="default"` restores this setting. Defaults to `true`.
+    pub trim_text: bool,
+    /// Resource limits, unlimited by default.
+    pub limits: DeserializeLimits,
+}
+
+impl Default for DeserializeOptions {
+    fn default() -> Self {
+        Self {
+            trim_text: true,
+            limits: DeserializeLimits::default(),
+        }
+    }
+}
+
+/// Resource limits protecting against hostile documents. `None` means unlimited.
+///
+/// The limits apply for the duration of [`deserialize_with_options`] and the `*_with_options`
+/// functions built on it. Calling [`XmlDeserialize::xml_deserialize`] directly is unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeserializeLimits {
+    /// Maximum nesting depth of deserialized structs and enums.
+    pub max_depth: Option<usize>,
+    /// Maximum number of elements in the document.
+    pub max_elements: Option<usize>,
+    /// Maximum length in bytes of a single text node or attribute value.
+    pub max_text_length: Option<usize>,
+    /// Maximum number of items of a single repeated field.
+    pub max_vec_length: Option<usize>,
+    /// Maximum size of the input document in bytes.
+    pub max_input_bytes: Option<usize>,
+}
+
+impl DeserializeLimits {
+    /// Limits suitable for documents from untrusted sources.
+    pub const fn safe() -> Self {
+        Self {
+            max_depth: Some(64),
+            max_elements: Some(100_000),
+            max_text_length: Some(1024 * 1024),
+            max_vec_length: Some(65_536),
+            max_input_bytes: Some(2 * 1024 * 1024),
+        }
+    }
+}
+
+/// The limit that was exceeded, with its configured maximum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    Depth(usize),
+    Elements(usize),
+    TextLength(usize),
+    VecLength(usize),
+    InputBytes(usize),
+}
+
+impl std::fmt::Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Depth(max) => write!(f, "nesting depth exceeds {max}"),
+            Self::Elements(max) => write!(f, "number of elements exceeds {max}"),
+            Self::TextLength(max) => write!(f, "text length exceeds {max} bytes"),
+            Self::VecLength(max) => write!(f, "number of repeated elements exceeds {max}"),
+            Self::InputBytes(max) => write!(f, "input size exceeds {max} bytes"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct LimitsState {
+    limits: DeserializeLimits,
+    depth: usize,
+    elements: usize,
+}
+
+thread_local! {
+    static LIMITS: Cell<LimitsState> = Cell::new(LimitsState::default());
+    /// The configured trimming, restored by `xml:space="default"`.
+    static TRIM_TEXT: Cell<bool> = const { Cell::new(true) };
+}
+
+/// Activates the options for the current thread until dropped.
+struct OptionsScope(LimitsState, bool);
+
+impl OptionsScope {
+    fn enter(options: &DeserializeOptions) -> Self {
+        Self(
+            LIMITS.replace(LimitsState {
+                limits: options.limits,
+                ..Default::default()
+            }),
+            TRIM_TEXT.replace(options.trim_text),
+        )
+    }
+}
+
+impl Drop for OptionsScope {
+    fn drop(&mut self) {
+        LIMITS.set(self.0);
+        TRIM_TEXT.set(self.1);
+    }
+}
+
+fn check_limit(
+    value: usize,
+    max: Option<usize>,
+    limit: fn(usize) -> Limit,
+) -> XmlDeserializeResult<()> {
+    match max {
+        Some(max) if value > max => Err(XmlDeserializeError::LimitExceeded(limit(max))),
+        _ => Ok(()),
+    }
+}
+
+/// Tracks the nesting depth of a deserialized element, see [`enter_element`].
+pub struct DepthGuard(());
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        LIMITS.set(LimitsState {
+            depth: LIMITS.get().depth.saturating_sub(1),
+            ..LIMITS.get()
+        });
+    }
+}
+
+/// Called when a struct starts to deserialize an element. Checks the depth limit, the depth is
+/// decreased again when the returned guard is dropped.
+pub fn enter_element() -> XmlDeserializeResult<DepthGuard> {
+    let mut state = LIMITS.get();
+    state.depth += 1;
+    LIMITS.set(state);
+    let guard = DepthGuard(());
+    check_limit(state.depth, state.limits.max_depth, Limit::Depth)?;
+    Ok(guard)
+}
+
+/// Checks the element and text length limits for an event read from the document.
+pub fn check_event(event: &Event) -> XmlDeserializeResult<()> {
+    match event {
+        Event::Start(_) | Event::Empty(_) => {
+            let mut state = LIMITS.get();
+            state.elements += 1;
+            LIMITS.set(state);
+            check_limit(state.elements, state.limits.max_elements, Limit::Elements)
+        }
+        Event::Text(t) => check_text_length(t.len()),
+        Event::CData(t) => check_text_length(t.len()),
+        _ => Ok(()),
+    }
+}
+
+/// Checks the length of a text node or attribute value.
+pub fn check_text_length(length: usize) -> XmlDeserializeResult<()> {
+    check_limit(
+        length,
+        LIMITS.get().limits.max_text_length,
+        Limit::TextLength,
+    )
+}
+
+/// Checks the number of items of a repeated field before another one is added.
+pub fn check_vec_length(length: usize) -> XmlDeserializeResult<()> {
+    check_limit(
+        length + 1,
+        LIMITS.get().limits.max_vec_length,
+        Limit::VecLength,
+    )
+}
+
+/// Parses attribute values of types that are not built in. Types whose `FromStr::Err` is
+/// [`XmlDeserializeError`], e.g. simple enums, report invalid values, other types fall back to
+/// their default value. The implementation is selected by the derived code via
+/// `(&ParseAttribute::<T>::new()).parse_attribute(value)`, which prefers
+/// [`ParseAttributeOrError`] over [`ParseAttributeOrDefault`].
+#[doc(hidden)]
+pub struct ParseAttribute<T>(PhantomData<T>);
+
+impl<T> ParseAttribute<T> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait ParseAttributeOrError<T> {
+    fn parse_attribute(&self, value: &str) -> XmlDeserializeResult<T>;
+}
+
+impl<T: FromStr<Err = XmlDeserializeError>> ParseAttributeOrError<T> for ParseAttribute<T> {
+    fn parse_attribute(&self, value: &str) -> XmlDeserializeResult<T> {
+        value.parse()
+    }
+}
+
+#[doc(hidden)]
+pub trait ParseAttributeOrDefault<T> {
+    fn parse_attribute(&self, value: &str) -> XmlDeserializeResult<T>;
+}
+
+impl<T: FromStr + Default> ParseAttributeOrDefault<T> for &ParseAttribute<T> {
+    fn parse_attribute(&self, value: &str) -> XmlDeserializeResult<T> {
+        Ok(value.parse().unwrap_or_default())
+    }
+}
+
+/// Enables or disables trimming of text events and returns the previous setting.
+pub fn set_trim_text<R>(reader: &mut NsReader<R>, trim: bool) -> bool {
+    let previous = reader.config().trim_text_start;
+    reader.config_mut().trim_text(trim);
+    previous
+}
+
+/// Returns whether text inside an element with `attributes` is trimmed: `xml:space="preserve"`
+/// disables trimming, `xml:space="default"` restores the configured
+/// [`trim_text`](DeserializeOptions::trim_text), otherwise the current setting is inherited.
+pub fn is_text_trimmed<R>(reader: &NsReader<R>, attributes: Attributes) -> bool {
+    for attr in attributes.flatten() {
+        if attr.key.as_ref() == b"xml:space" {
+            match attr.value.as_ref() {
+                b"preserve" => return false,
+                b"default" => return TRIM_TEXT.get(),
+                _ => {}
+            }
+        }
+    }
+    reader.config().trim_text_start
+}
+
+/// Returns `true` if the element attributes contain `xsi:nil="true"`.
+pub fn is_nil<R>(reader: &NsReader<R>, attributes: Attributes) -> XmlDeserializeResult<bool> {
+    for attr in attributes.flatten() {
+        if let (ResolveResult::Bound(ns), local_name) = reader.resolve_attribute(attr.key) {
+            if ns.as_ref() == XSI_NS && local_name.as_ref() == b"nil" {
+                let value = attr.unescape_value()?;
+                return Ok(matches!(value.trim(), "true" | "1"));
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Namespace of the prefix of an attribute field (`ns = b"xsi"`): `namespace`, the target
+/// namespace if the prefix is that of the container, the xsi namespace for `xsi`, or else the
+/// binding of the prefix in the scope of the current element.
+pub fn attribute_namespace<R>(
+    reader: &NsReader<R>,
+    prefix: &[u8],
+    namespace: Option<&[u8]>,
+) -> Option<Vec<u8>> {
+    if let Some(namespace) = namespace.or((prefix == b"xsi").then_some(XSI_NS)) {
+        return Some(namespace.to_vec());
+    }
+    let name = [prefix, b":_"].concat();
+    match reader.resolve_attribute(QName(&name)).0 {
+        ResolveResult::Bound(ns) => Some(ns.as_ref().to_vec()),
+        _ => None,
+    }
+}
+
+/// Whether the attribute `key` is in `namespace`, see [`attribute_namespace`]. An attribute
+/// with an unbound prefix matches by the prefix.
+pub fn is_attribute_in<R>(
+    reader: &NsReader<R>,
+    key: QName,
+    prefix: &[u8],
+    namespace: Option<&[u8]>,
+) -> bool {
+    match reader.resolve_attribute(key).0 {
+        ResolveResult::Bound(ns) => namespace == Some(ns.as_ref()),
+        ResolveResult::Unknown(unbound) => unbound == prefix,
+        ResolveResult::Unbound => false,
+    }
+}
+
+/// Reads the name of the current element into a [`QName`](crate::value::QName) with the
+/// namespace its prefix is bound to.
+pub fn element_qname<R>(
+    reader: &NsReader<R>,
+    name: &[u8],
+) -> XmlDeserializeResult<crate::value::QName> {
+    let mut qname: crate::value::QName = std::str::from_utf8(name)?.parse()?;
+    if let (ResolveResult::Bound(ns), _) = reader.resolve_element(QName(name)) {
+        qname.namespace = Some(std::str::from_utf8(ns.as_ref())?.to_string());
+    }
+    Ok(qname)
+}
+
+/// Returns the resolved namespace and local name of the `xsi:type` attribute, if present.
+///
+/// The attribute value is a QName, its prefix is resolved against the namespace bindings in
+/// scope of the current element.
+pub fn xsi_type<R>(
+    reader: &NsReader<R>,
+    attributes: Attributes,
+) -> XmlDeserializeResult<Option<ResolvedName>> {
+    for attr in attributes.flatten() {
+        if let (ResolveResult::Bound(ns), local_name) = reader.resolve_attribute(attr.key) {
+            if ns.as_ref() == XSI_NS && local_name.as_ref() == b"type" {
+                let value = attr.unescape_value()?;
+                let (ns, local_name) = reader.resolve_element(QName(value.trim().as_bytes()));
+                let ns = match ns {
+                    ResolveResult::Bound(ns) => Some(ns.as_ref().to_vec()),
+                    _ => None,
+                };
+                return Ok(Some((ns, local_name.as_ref().to_vec())));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Target namespace of `T` for the element `start`, or for an element in `target_ns` without
+/// `start`. That is the namespace of the element if it is one of the
+/// [aliases](XmlDeserialize::target_ns_aliases) of `T`, else `T::target_ns()` or the inherited
+/// `target_ns`.
+pub fn resolve_target_ns<T, R>(
+    reader: &NsReader<R>,
+    target_ns: XmlTag,
+    start: Option<&BytesStart>,
+) -> XmlTag
+where
+    T: XmlDeserialize,
+{
+    let aliases = T::target_ns_aliases();
+    let alias = match start {
+        _ if aliases.is_empty() => None,
+        Some(start) => match reader.resolve_element(start.name()).0 {
+            ResolveResult::Bound(ns) => aliases.iter().find(|alias| **alias == ns.as_ref()),
+            _ => None,
+        },
+        None => aliases.iter().find(|alias| **alias == target_ns),
+    };
+    alias.copied().or_else(T::target_ns).unwrap_or(target_ns)
+}
+
+/// Skips the remaining content of the current element, including its end tag. The skipped
+/// elements count towards the [limits](DeserializeLimits) like deserialized ones.
+pub fn skip_element<R>(reader: &mut NsReader<R>, is_empty: bool) -> XmlDeserializeResult<()>
+where
+    R: BufRead,
+{
+    if is_empty {
+        return Ok(());
+    }
+    let _depth = enter_element()?;
+    let mut buf = Vec::<u8>::new();
+    let mut depth = Vec::<DepthGuard>::new();
+    loop {
+        let event = reader.read_event_into(&mut buf)?;
+        check_event(&event)?;
+        match event {
+            Event::Start(_) => depth.push(enter_element()?),
+            Event::End(_) if depth.pop().is_none() => break,
+            Event::Eof => return Err(XmlDeserializeError::UnexpectedEof),
+            _ => {}
+        }
+        buf.clear();
+    }
+    Ok(())
+}
+
+/// Reads the text content of the current element up to and including its end tag.
+pub fn read_text_content<R>(
+    reader: &mut NsReader<R>,
+    is_empty: bool,
+) -> XmlDeserializeResult<String>
+where
+    R: BufRead,
+{
+    let mut value = String::new();
+    if is_empty {
+        return Ok(value);
+    }
+    let mut buf = Vec::<u8>::new();
+    loop {
+        let event = reader.read_event_into(&mut buf)?;
+        check_event(&event)?;
+        match event {
+            Event::Text(t) => value.push_str(&t.unescape()?),
+            Event::CData(t) => value.push_str(std::str::from_utf8(&t)?),
+            Event::Start(_) => skip_element(reader, false)?,
+            Event::End(_) => break,
+            Event::Eof => return Err(XmlDeserializeError::UnexpectedEof),
+            _ => {}
+        }
+        buf.clear();
+    }
+    Ok(value)
+}
+
+impl XmlDeserialize for String {
+    fn xml_deserialize<R>(
+        reader: &mut NsReader<R>,
+        _target_ns: XmlTag,
+        _tag: XmlTargetNs,
+        _attributes: Attributes,
+        is_empty: bool,
+    ) -> XmlDeserializeResult<Self>
+    where
+        R: BufRead,
+    {
+        read_text_content(reader, is_empty)
+    }
+}
+
+macro_rules! impl_xml_deserialize_from_str {
+    ($($ty:ty),*) => {
+        $(
+            impl XmlDeserialize for $ty {
+                fn xml_deserialize<R>(
+                    reader: &mut NsReader<R>,
+                    _target_ns: XmlTag,
+                    _tag: XmlTargetNs,
+                    _attributes: Attributes,
+                    is_empty: bool,
+                ) -> XmlDeserializeResult<Self>
+                where
+                    R: BufRead,
+                {
+                    if is_empty {
+                        return Err(XmlDeserializeError::EmptyNode);
+                    }
+                    Ok(read_text_content(reader, is_empty)?.trim().parse()?)
+                }
+            }
+        )*
+    };
+}
+
+impl_xml_deserialize_from_str!(bool, f32, f64, u8, u16, u32, u64, i8, i16, i32, i64);
+
+/// Smart pointers are deserialized like their inner type, e.g. `Option<Box<Node>>` for recursive
+/// elements.
+macro_rules! impl_xml_deserialize_pointer {
+    ($($ty:ident),*) => {
+        $(
+            impl<T: XmlDeserialize> XmlDeserialize for $ty<T> {
+                fn is_enum() -> bool {
+                    T::is_enum()
+                }
+
+                fn root() -> Option<XmlTag> {
+                    T::root()
+                }
+
+                fn target_ns() -> Option<XmlTargetNs> {
+                    T::target_ns()
+                }
+
+                fn target_ns_aliases() -> &'static [XmlTargetNs] {
+                    T::target_ns_aliases()
+                }
+
+                fn xml_deserialize<R>(
+                    reader: &mut NsReader<R>,
+                    target_ns: XmlTag,
+                    tag: XmlTargetNs,
+                    attributes: Attributes,
+                    is_empty: bool,
+                ) -> XmlDeserializeResult<Self>
+                where
+                    R: BufRead,
+                {
+                    T::xml_deserialize(reader, target_ns, tag, attributes, is_empty).map($ty::new)
+                }
+
+                fn xml_deserialize_with_start<R>(
+                    reader: &mut NsReader<R>,
+                    target_ns: XmlTag,
+                    tag: XmlTargetNs,
+                    start: &BytesStart,
+                    is_empty: bool,
+                ) -> XmlDeserializeResult<Self>
+                where
+                    R: BufRead,
+                {
+                    T::xml_deserialize_with_start(reader, target_ns, tag, start, is_empty)
+                        .map($ty::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_xml_deserialize_pointer!(Box, Rc, Arc);
+
+pub fn deserialize_with_reader<T, R>(rdr: NsReader<R>) -> XmlDeserializeResult<T>
+where
+    T: XmlDeserialize,
+    R: BufRead,
+{
+    deserialize_with_options(rdr, &DeserializeOptions::default())
 }
 
-pub fn deserialize_with_reader<T, R>(mut rdr: NsReader<R>) -> XmlDeserializeResult<T>
+pub fn deserialize_with_options<T, R>(
+    mut rdr: NsReader<R>,
+    options: &DeserializeOptions,
+) -> XmlDeserializeResult<T>
 where
     T: XmlDeserialize,
     R: BufRead,
 {
-    rdr.config_mut().trim_text(true);
+    rdr.config_mut().trim_text(options.trim_text);
     rdr.config_mut().check_comments = false;
     rdr.config_mut().expand_empty_elements = false;
+    let _scope = OptionsScope::enter(options);
+    let result = deserialize_document(&mut rdr);
+    // a truncated input usually fails to parse, report the exceeded limit instead
+    check_limit(
+        rdr.buffer_position() as usize,
+        options.limits.max_input_bytes,
+        Limit::InputBytes,
+    )?;
+    result
+}
+
+/// Whether `ns` is `target_ns` or one of the [aliases](XmlDeserialize::target_ns_aliases) of `T`.
+pub fn is_target_ns<T: XmlDeserialize>(target_ns: XmlTargetNs, ns: &[u8]) -> bool {
+    ns == target_ns || T::target_ns_aliases().contains(&ns)
+}
+
+fn deserialize_document<T, R>(rdr: &mut NsReader<R>) -> XmlDeserializeResult<T>
+where
+    T: XmlDeserialize,
+    R: BufRead,
+{
     if T::is_enum() {
         if let Some(target_ns) = T::target_ns() {
-            return T::xml_deserialize(&mut rdr, target_ns, &[], Attributes::new("", 0), false);
+            return T::xml_deserialize(rdr, target_ns, &[], Attributes::new("", 0), false);
         } else {
-            return T::xml_deserialize(&mut rdr, &[], &[], Attributes::new("", 0), false);
+            return T::xml_deserialize(rdr, &[], &[], Attributes::new("", 0), false);
         }
     }
     let mut buf = Vec::<u8>::new();
@@ -85,31 +700,26 @@ where
     let root = T::root().ok_or(XmlDeserializeError::MissingRoot)?;
     if let Some(target_ns) = T::target_ns() {
         loop {
-            match rdr.read_resolved_event_into(&mut buf)? {
+            let event = rdr.read_resolved_event_into(&mut buf)?;
+            check_event(&event.1)?;
+            match event {
                 (ResolveResult::Bound(tns), Event::Start(e)) => {
-                    if e.local_name().as_ref() == root && tns.as_ref() == target_ns {
-                        result = Some(T::xml_deserialize(
-                            &mut rdr,
-                            target_ns,
-                            root,
-                            e.attributes(),
-                            false,
+                    if e.local_name().as_ref() == root && is_target_ns::<T>(target_ns, tns.as_ref())
+                    {
+                        result = Some(T::xml_deserialize_with_start(
+                            rdr, target_ns, root, &e, false,
                         )?);
                     } else {
-                        let mut buf = Vec::<u8>::new();
-                        rdr.read_to_end_into(e.name(), &mut buf)?;
+                        skip_element(rdr, false)?;
                     }
                 }
-                (ResolveResult::Bound(tns), Event::Empty(e)) => {
-                    if e.local_name().as_ref() == root && tns.as_ref() == target_ns {
-                        result = Some(T::xml_deserialize(
-                            &mut rdr,
-                            target_ns,
-                            root,
-                            e.attributes(),
-                            true,
-                        )?);
-                    }
+                (ResolveResult::Bound(tns), Event::Empty(e))
+                    if e.local_name().as_ref() == root
+                        && is_target_ns::<T>(target_ns, tns.as_ref()) =>
+                {
+                    result = Some(T::xml_deserialize_with_start(
+                        rdr, target_ns, root, &e, true,
+                    )?);
                 }
                 (_, Event::Eof) => {
                     break;
@@ -119,31 +729,18 @@ where
         }
     } else {
         loop {
-            match rdr.read_resolved_event_into(&mut buf)? {
+            let event = rdr.read_resolved_event_into(&mut buf)?;
+            check_event(&event.1)?;
+            match event {
                 (ResolveResult::Unbound, Event::Start(e)) => {
                     if e.local_name().as_ref() == root {
-                        result = Some(T::xml_deserialize(
-                            &mut rdr,
-                            &[],
-                            root,
-                            e.attributes(),
-                            false,
-                        )?);
+                        result = Some(T::xml_deserialize_with_start(rdr, &[], root, &e, false)?);
                     } else {
-                        let mut buf = Vec::<u8>::new();
-                        rdr.read_to_end_into(e.name(), &mut buf)?;
+                        skip_element(rdr, false)?;
                     }
                 }
-                (ResolveResult::Unbound, Event::Empty(e)) => {
-                    if e.local_name().as_ref() == root {
-                        result = Some(T::xml_deserialize(
-                            &mut rdr,
-                            &[],
-                            root,
-                            e.attributes(),
-                            true,
-                        )?);
-                    }
+                (ResolveResult::Unbound, Event::Empty(e)) if e.local_name().as_ref() == root => {
+                    result = Some(T::xml_deserialize_with_start(rdr, &[], root, &e, true)?);
                 }
                 (_, Event::Eof) => {
                     break;
@@ -162,10 +759,76 @@ where
     deserialize_with_reader(quick_xml::NsReader::from_str(s))
 }
 
+/// Deserializes a document from a reader. With the `encoding` feature the whole document is
+/// read and decoded like in [`from_slice`], otherwise it is parsed while reading and must be
+/// UTF-8.
 pub fn from_reader<R, T>(s: R) -> XmlDeserializeResult<T>
 where
     R: BufRead,
     T: XmlDeserialize,
 {
-    deserialize_with_reader(quick_xml::NsReader::<R>::from_reader(s))
+    from_reader_with_options(s, &DeserializeOptions::default())
+}
+
+pub fn from_str_with_options<T>(s: &str, options: &DeserializeOptions) -> XmlDeserializeResult<T>
+where
+    T: XmlDeserialize,
+{
+    check_limit(s.len(), options.limits.max_input_bytes, Limit::InputBytes)?;
+    deserialize_with_options(quick_xml::NsReader::from_str(s), options)
+}
+
+/// Like [`from_reader`], with options.
+pub fn from_reader_with_options<R, T>(s: R, options: &DeserializeOptions) -> XmlDeserializeResult<T>
+where
+    R: BufRead,
+    T: XmlDeserialize,
+{
+    // read at most one byte more than allowed, so that the limit check can detect it
+    let limit = options
+        .limits
+        .max_input_bytes
+        .map_or(u64::MAX, |max| max as u64 + 1);
+    #[cfg(feature = "encoding")]
+    {
+        let mut bytes = Vec::new();
+        std::io::Read::read_to_end(&mut s.take(limit), &mut bytes)
+            .map_err(quick_xml::Error::from)?;
+        from_slice_with_options(&bytes, options)
+    }
+    #[cfg(not(feature = "encoding"))]
+    {
+        deserialize_with_options(quick_xml::NsReader::from_reader(s.take(limit)), options)
+    }
+}
+
+/// Deserializes a document from bytes. With the `encoding` feature the document is decoded
+/// according to its byte order mark or XML declaration first, otherwise it must be UTF-8.
+pub fn from_slice<T>(bytes: &[u8]) -> XmlDeserializeResult<T>
+where
+    T: XmlDeserialize,
+{
+    from_slice_with_options(bytes, &DeserializeOptions::default())
+}
+
+pub fn from_slice_with_options<T>(
+    bytes: &[u8],
+    options: &DeserializeOptions,
+) -> XmlDeserializeResult<T>
+where
+    T: XmlDeserialize,
+{
+    check_limit(
+        bytes.len(),
+        options.limits.max_input_bytes,
+        Limit::InputBytes,
+    )?;
+    #[cfg(feature = "encoding")]
+    {
+        from_str_with_options(&crate::encoding::decode(bytes)?, options)
+    }
+    #[cfg(not(feature = "encoding"))]
+    {
+        deserialize_with_options(quick_xml::NsReader::from_reader(bytes), options)
+    }
 }
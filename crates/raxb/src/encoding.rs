@@ -0,0 +1,79 @@
+use std::borrow::Cow;
+
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+
+use crate::de::{XmlDeserializeError, XmlDeserializeResult};
+
+/// Detects the encoding of an XML document from its byte order mark or, if there is none,
+/// from the `encoding` of its XML declaration. Falls back to UTF-8.
+///
+/// Returns the encoding and the length of the byte order mark.
+///
+/// Labels are resolved as specified by the WHATWG Encoding Standard, so `ISO-8859-1`
+/// is decoded as `windows-1252` and a declared `UTF-16` without byte order mark as UTF-8.
+pub fn detect(bytes: &[u8]) -> XmlDeserializeResult<(&'static Encoding, usize)> {
+    if let Some(bom) = Encoding::for_bom(bytes) {
+        return Ok(bom);
+    }
+    match bytes {
+        [b'<', 0, b'?', 0, ..] => return Ok((UTF_16LE, 0)),
+        [0, b'<', 0, b'?', ..] => return Ok((UTF_16BE, 0)),
+        _ => {}
+    }
+    match declared_encoding(bytes) {
+        // the declaration was read as ASCII, so a declared UTF-16 is stale, e.g. kept after
+        // transcoding the file to UTF-8
+        Some(label) => Encoding::for_label(label)
+            .map(|encoding| match encoding {
+                encoding if encoding == UTF_16LE || encoding == UTF_16BE => (UTF_8, 0),
+                encoding => (encoding, 0),
+            })
+            .ok_or_else(|| {
+                XmlDeserializeError::UnsupportedEncoding(String::from_utf8_lossy(label).to_string())
+            }),
+        None => Ok((UTF_8, 0)),
+    }
+}
+
+/// Decodes an XML document into a string, see [`detect`].
+pub fn decode(bytes: &[u8]) -> XmlDeserializeResult<Cow<'_, str>> {
+    let (encoding, bom_length) = detect(bytes)?;
+    encoding
+        .decode_without_bom_handling_and_without_replacement(&bytes[bom_length..])
+        .ok_or_else(|| XmlDeserializeError::Decoding(encoding.name().to_string()))
+}
+
+/// Encodes a serialized XML document. Characters that can't be represented in `encoding`
+/// are written as numeric character references. UTF-16 output starts with a byte order mark.
+pub fn encode(s: &str, encoding: &'static Encoding) -> Vec<u8> {
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        let mut bytes = Vec::with_capacity(2 + s.len() * 2);
+        for unit in std::iter::once(0xFEFF).chain(s.encode_utf16()) {
+            if encoding == UTF_16LE {
+                bytes.extend_from_slice(&unit.to_le_bytes());
+            } else {
+                bytes.extend_from_slice(&unit.to_be_bytes());
+            }
+        }
+        return bytes;
+    }
+    let (bytes, _, _) = encoding.encode(s);
+    bytes.into_owned()
+}
+
+/// Returns the value of the `encoding` pseudo-attribute of an ASCII-compatible XML declaration.
+fn declared_encoding(bytes: &[u8]) -> Option<&[u8]> {
+    let decl = bytes.strip_prefix(b"<?xml")?;
+    let decl = &decl[..decl.windows(2).position(|w| w == b"?>")?];
+    let start = decl.windows(8).position(|w| w == b"encoding")? + 8;
+    let rest = decl[start..]
+        .trim_ascii_start()
+        .strip_prefix(b"=")?
+        .trim_ascii_start();
+    let (&quote, rest) = rest.split_first()?;
+    if quote != b'"' && quote != b'\'' {
+        return None;
+    }
+    let end = rest.iter().position(|&b| b == quote)?;
+    Some(&rest[..end])
+}
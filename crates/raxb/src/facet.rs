@@ -0,0 +1,206 @@
+//! Facet checks of field values, e.g. `#[raxb(min_length = 1, max_length = 35)]`.
+//!
+//! The derived implementations check the facets of every field after deserialization and before
+//! serialization. A violation is reported as [`Violation`] in
+//! [`XmlDeserializeError::Constraint`](crate::de::XmlDeserializeError::Constraint) or
+//! [`XmlSerializeError::Constraint`](crate::ser::XmlSerializeError::Constraint).
+//!
+//! The `pattern` facet needs the `regex` feature, see `Pattern`.
+
+use std::fmt::Display;
+
+/// A value that violates a facet of its field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Name of the field, `None` for the `validate` function of a struct.
+    pub field: Option<String>,
+    pub facet: Facet,
+}
+
+/// The violated facet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Facet {
+    Pattern(String),
+    MinLength(usize),
+    MaxLength(usize),
+    /// Minimum (inclusive).
+    Min(String),
+    /// Maximum (inclusive).
+    Max(String),
+    Enumeration(Vec<String>),
+    /// Minimum number of items of a repeated element.
+    MinOccurs(usize),
+    /// Maximum number of items of a repeated element.
+    MaxOccurs(usize),
+    /// Error of a `validate` function.
+    Custom(String),
+}
+
+impl Violation {
+    pub fn new(field: &str, facet: Facet) -> Self {
+        Self {
+            field: Some(field.to_string()),
+            facet,
+        }
+    }
+
+    /// Error of a `validate` function.
+    pub fn custom(err: impl Display) -> Self {
+        Self {
+            field: None,
+            facet: Facet::Custom(err.to_string()),
+        }
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(field) = self.field.as_ref() {
+            write!(f, "value of '{field}' ")?;
+        }
+        match &self.facet {
+            Facet::Pattern(pattern) => write!(f, "does not match the pattern '{pattern}'"),
+            Facet::MinLength(min) => write!(f, "is shorter than {min}"),
+            Facet::MaxLength(max) => write!(f, "is longer than {max}"),
+            Facet::Min(min) => write!(f, "is less than {min}"),
+            Facet::Max(max) => write!(f, "is greater than {max}"),
+            Facet::Enumeration(values) => write!(f, "is not one of {}", values.join(", ")),
+            Facet::MinOccurs(min) => write!(f, "occurs less than {min} times"),
+            Facet::MaxOccurs(max) => write!(f, "occurs more than {max} times"),
+            Facet::Custom(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl std::error::Error for Violation {}
+
+/// Length of a value for the `min_length` and `max_length` facets: the number of characters of
+/// a string or the number of items of a list.
+pub trait Length {
+    fn length(&self) -> usize;
+}
+
+impl Length for str {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl Length for String {
+    fn length(&self) -> usize {
+        self.as_str().length()
+    }
+}
+
+impl<T> Length for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+pub fn check_min_length<T>(field: &str, value: &T, min: usize) -> Result<(), Violation>
+where
+    T: Length + ?Sized,
+{
+    if value.length() < min {
+        return Err(Violation::new(field, Facet::MinLength(min)));
+    }
+    Ok(())
+}
+
+pub fn check_max_length<T>(field: &str, value: &T, max: usize) -> Result<(), Violation>
+where
+    T: Length + ?Sized,
+{
+    if value.length() > max {
+        return Err(Violation::new(field, Facet::MaxLength(max)));
+    }
+    Ok(())
+}
+
+pub fn check_min<T>(field: &str, value: &T, min: &T) -> Result<(), Violation>
+where
+    T: PartialOrd + Display,
+{
+    if value < min {
+        return Err(Violation::new(field, Facet::Min(min.to_string())));
+    }
+    Ok(())
+}
+
+pub fn check_max<T>(field: &str, value: &T, max: &T) -> Result<(), Violation>
+where
+    T: PartialOrd + Display,
+{
+    if value > max {
+        return Err(Violation::new(field, Facet::Max(max.to_string())));
+    }
+    Ok(())
+}
+
+/// Checks the number of items of a repeated element, `count` is 0 or 1 for an `Option`.
+pub fn check_min_occurs(field: &str, count: usize, min: usize) -> Result<(), Violation> {
+    if count < min {
+        return Err(Violation::new(field, Facet::MinOccurs(min)));
+    }
+    Ok(())
+}
+
+pub fn check_max_occurs(field: &str, count: usize, max: usize) -> Result<(), Violation> {
+    if count > max {
+        return Err(Violation::new(field, Facet::MaxOccurs(max)));
+    }
+    Ok(())
+}
+
+/// Checks that the text of the value is one of `values`.
+pub fn check_enumeration<T>(field: &str, value: &T, values: &[&str]) -> Result<(), Violation>
+where
+    T: Display + ?Sized,
+{
+    let value = value.to_string();
+    if !values.contains(&value.as_str()) {
+        return Err(Violation::new(
+            field,
+            Facet::Enumeration(values.iter().map(|v| v.to_string()).collect()),
+        ));
+    }
+    Ok(())
+}
+
+/// A `pattern` facet, matched against the whole text of the value like in XML Schema. The
+/// pattern uses the syntax of the [`regex`](https://docs.rs/regex) crate, not the XML Schema
+/// regular expression syntax, e.g. character class subtraction `[a-z-[aeiou]]` and the
+/// `\i` and `\c` escapes are not supported.
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    pattern: &'static str,
+    regex: regex::Regex,
+}
+
+#[cfg(feature = "regex")]
+impl Pattern {
+    /// # Panics
+    ///
+    /// Panics if the pattern is not a valid regular expression. Patterns of derived types are
+    /// checked at compile time.
+    pub fn new(pattern: &'static str) -> Self {
+        let regex = regex::Regex::new(&format!("^(?:{pattern})$"))
+            .unwrap_or_else(|err| panic!("invalid pattern '{pattern}': {err}"));
+        Self { pattern, regex }
+    }
+
+    pub fn check<T>(&self, field: &str, value: &T) -> Result<(), Violation>
+    where
+        T: Display + ?Sized,
+    {
+        if !self.regex.is_match(&value.to_string()) {
+            return Err(Violation::new(
+                field,
+                Facet::Pattern(self.pattern.to_string()),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -16,12 +16,26 @@
 pub use raxb_derive::XmlDeserialize;
 pub use raxb_derive::XmlSerialize;
 
+pub mod c14n;
+pub mod collection;
 pub mod de;
+#[cfg(feature = "encoding")]
+pub mod encoding;
+pub mod facet;
 pub mod ser;
 pub mod ty;
 pub mod value;
 
 pub use quick_xml;
 
+#[cfg(feature = "encoding")]
+pub use encoding_rs;
+#[cfg(feature = "indexmap")]
+pub use indexmap;
+#[cfg(feature = "regex")]
+pub use regex;
+#[cfg(feature = "smallvec")]
+pub use smallvec;
+
 #[cfg(feature = "trace")]
 pub use tracing;
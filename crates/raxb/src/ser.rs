@@ -1,8 +1,16 @@
-use std::{io::Cursor, str::Utf8Error, string::FromUtf8Error};
+use std::{io::Cursor, rc::Rc, str::Utf8Error, string::FromUtf8Error, sync::Arc};
 
-use quick_xml::{events::BytesDecl, Writer};
+use quick_xml::{
+    escape::escape,
+    events::{BytesDecl, BytesText},
+    Writer,
+};
 use thiserror::Error;
 
+pub use crate::c14n::{
+    canonicalize, canonicalize_str, canonicalize_str_with_options, canonicalize_with_options,
+    C14nMode, C14nOptions, ElementSelector,
+};
 use crate::ty::XmlTag;
 
 #[derive(Error, Debug)]
@@ -16,6 +24,31 @@ pub enum XmlSerializeError {
     Utf8Error(#[from] Utf8Error),
     #[error("missing root element name, try to implement 'fn root() -> XmlTag {{ b\"my-root-element-name\" }}'")]
     MissingRoot,
+    #[error("unsupported encoding '{0}'")]
+    UnsupportedEncoding(String),
+    #[error("malformed input for encoding '{0}'")]
+    Decoding(String),
+    #[error("namespace prefix '{0}' is not bound")]
+    UnboundPrefix(String),
+    #[error("element '{0}' does not support additional attributes")]
+    UnsupportedAttributes(String),
+    #[error("more than one element with the id '{0}'")]
+    DuplicateId(String),
+    #[error("no element matches '{0}'")]
+    MissingSubtree(String),
+    #[error("'{0}' is not a valid element name")]
+    InvalidName(String),
+    #[error("{0}")]
+    Custom(String),
+    #[error("constraint violated: {0}")]
+    Constraint(#[from] crate::facet::Violation),
+}
+
+impl XmlSerializeError {
+    /// Error of a custom conversion, e.g. a `serialize_with` function.
+    pub fn custom(err: impl std::fmt::Display) -> Self {
+        Self::Custom(err.to_string())
+    }
 }
 
 pub type XmlSerializeResult<T> = Result<T, XmlSerializeError>;
@@ -34,8 +67,154 @@ pub trait XmlSerialize {
         tag: &str,
         writer: &mut Writer<W>,
     ) -> XmlSerializeResult<()>;
+
+    /// Serializes the value like [`XmlSerialize::xml_serialize`] and adds `attributes` to the
+    /// start tag of the element, e.g. `xsi:type`, namespace declarations or the key of a map
+    /// item. Implementations write their own attributes first and skip the ones with the same
+    /// name, see [`merge_attributes`]. The default implementation cannot add attributes and
+    /// fails with [`XmlSerializeError::UnsupportedAttributes`] unless `attributes` is empty.
+    fn xml_serialize_with_attributes<W: std::io::Write>(
+        &self,
+        tag: &str,
+        attributes: &[(&str, &str)],
+        writer: &mut Writer<W>,
+    ) -> XmlSerializeResult<()> {
+        if !attributes.is_empty() {
+            return Err(XmlSerializeError::UnsupportedAttributes(tag.to_string()));
+        }
+        self.xml_serialize(tag, writer)
+    }
+}
+
+/// The `own` attributes of an element followed by the given `attributes` whose names are not
+/// already among them.
+pub fn merge_attributes<'a>(
+    own: &[(&'a str, &'a str)],
+    attributes: &[(&'a str, &'a str)],
+) -> Vec<(&'a str, &'a str)> {
+    let mut merged = own.to_vec();
+    merged.extend(
+        attributes
+            .iter()
+            .filter(|(key, _)| !own.iter().any(|(own_key, _)| own_key == key)),
+    );
+    merged
+}
+
+/// Checks that `name` is a valid element name without prefix (`NCName`), e.g. a map key written
+/// as element name.
+pub fn check_name(name: &str) -> XmlSerializeResult<()> {
+    fn is_name_start_char(c: char) -> bool {
+        matches!(c,
+            'A'..='Z' | '_' | 'a'..='z' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}'
+            | '\u{F8}'..='\u{2FF}' | '\u{370}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}'
+            | '\u{200C}'..='\u{200D}' | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}'
+            | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}'
+            | '\u{10000}'..='\u{EFFFF}')
+    }
+    fn is_name_char(c: char) -> bool {
+        is_name_start_char(c)
+            || matches!(c,
+                '-' | '.' | '0'..='9' | '\u{B7}' | '\u{300}'..='\u{36F}' | '\u{203F}'..='\u{2040}')
+    }
+    let mut chars = name.chars();
+    if chars.next().is_some_and(is_name_start_char) && chars.all(is_name_char) {
+        Ok(())
+    } else {
+        Err(XmlSerializeError::InvalidName(name.to_string()))
+    }
 }
 
+impl XmlSerialize for String {
+    fn xml_serialize<W: std::io::Write>(
+        &self,
+        tag: &str,
+        writer: &mut Writer<W>,
+    ) -> XmlSerializeResult<()> {
+        self.xml_serialize_with_attributes(tag, &[], writer)
+    }
+
+    fn xml_serialize_with_attributes<W: std::io::Write>(
+        &self,
+        tag: &str,
+        attributes: &[(&str, &str)],
+        writer: &mut Writer<W>,
+    ) -> XmlSerializeResult<()> {
+        writer
+            .create_element(tag)
+            .with_attributes(attributes.iter().copied())
+            .write_text_content(BytesText::from_escaped(escape(self)))?;
+        Ok(())
+    }
+}
+
+macro_rules! impl_xml_serialize_to_string {
+    ($($ty:ty),*) => {
+        $(
+            impl XmlSerialize for $ty {
+                fn xml_serialize<W: std::io::Write>(
+                    &self,
+                    tag: &str,
+                    writer: &mut Writer<W>,
+                ) -> XmlSerializeResult<()> {
+                    self.xml_serialize_with_attributes(tag, &[], writer)
+                }
+
+                fn xml_serialize_with_attributes<W: std::io::Write>(
+                    &self,
+                    tag: &str,
+                    attributes: &[(&str, &str)],
+                    writer: &mut Writer<W>,
+                ) -> XmlSerializeResult<()> {
+                    writer
+                        .create_element(tag)
+                        .with_attributes(attributes.iter().copied())
+                        .write_text_content(BytesText::new(&self.to_string()))?;
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_xml_serialize_to_string!(bool, f32, f64, u8, u16, u32, u64, i8, i16, i32, i64);
+
+/// Smart pointers are serialized like their inner type.
+macro_rules! impl_xml_serialize_pointer {
+    ($($ty:ident),*) => {
+        $(
+            impl<T: XmlSerialize + ?Sized> XmlSerialize for $ty<T> {
+                fn is_enum() -> bool {
+                    T::is_enum()
+                }
+
+                fn root() -> Option<XmlTag> {
+                    T::root()
+                }
+
+                fn xml_serialize<W: std::io::Write>(
+                    &self,
+                    tag: &str,
+                    writer: &mut Writer<W>,
+                ) -> XmlSerializeResult<()> {
+                    (**self).xml_serialize(tag, writer)
+                }
+
+                fn xml_serialize_with_attributes<W: std::io::Write>(
+                    &self,
+                    tag: &str,
+                    attributes: &[(&str, &str)],
+                    writer: &mut Writer<W>,
+                ) -> XmlSerializeResult<()> {
+                    (**self).xml_serialize_with_attributes(tag, attributes, writer)
+                }
+            }
+        )*
+    };
+}
+
+impl_xml_serialize_pointer!(Box, Rc, Arc);
+
 pub fn to_string<T>(value: &T) -> XmlSerializeResult<String>
 where
     T: XmlSerialize,
@@ -50,6 +229,14 @@ where
     Ok(String::from_utf8(writer.into_inner().into_inner())?)
 }
 
+/// Serializes the value into its canonical form, see [`canonicalize`].
+pub fn to_canonical<T>(value: &T, mode: C14nMode) -> XmlSerializeResult<Vec<u8>>
+where
+    T: XmlSerialize,
+{
+    canonicalize_str(&to_string(value)?, mode)
+}
+
 pub fn to_string_with_decl<T>(value: &T) -> XmlSerializeResult<String>
 where
     T: XmlSerialize,
@@ -103,3 +290,28 @@ where
     value.xml_serialize(name, &mut writer)?;
     Ok(String::from_utf8(writer.into_inner().into_inner())?)
 }
+
+/// Serializes the value with an XML declaration naming the encoding `label`
+/// (e.g. `ISO-8859-1` or `UTF-16`) and encodes the document accordingly.
+#[cfg(feature = "encoding")]
+pub fn to_vec_with_encoding<T>(value: &T, label: &str) -> XmlSerializeResult<Vec<u8>>
+where
+    T: XmlSerialize,
+{
+    let encoding = encoding_rs::Encoding::for_label(label.as_bytes())
+        .ok_or_else(|| XmlSerializeError::UnsupportedEncoding(label.to_string()))?;
+    let mut writer = Writer::new(Cursor::new(Vec::new()));
+    writer.write_event(quick_xml::events::Event::Decl(BytesDecl::new(
+        "1.0",
+        Some(label),
+        Some("yes"),
+    )))?;
+    let name = if T::is_enum() {
+        ""
+    } else {
+        std::str::from_utf8(T::root().ok_or(XmlSerializeError::MissingRoot)?)?
+    };
+    value.xml_serialize(name, &mut writer)?;
+    let xml = String::from_utf8(writer.into_inner().into_inner())?;
+    Ok(crate::encoding::encode(&xml, encoding))
+}
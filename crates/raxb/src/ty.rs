@@ -2,6 +2,9 @@ pub type XmlTag = &'static [u8];
 pub type XmlTargetNs = &'static [u8];
 pub type XmlTargetNsPrefix = &'static [u8];
 
+/// Namespace of the `xsi:*` attributes (`xsi:nil`, `xsi:type`, ...).
+pub const XSI_NS: XmlTargetNs = b"http://www.w3.org/2001/XMLSchema-instance";
+
 #[derive(Clone)]
 pub struct S(pub XmlTag);
 
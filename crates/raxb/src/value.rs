@@ -1,6 +1,16 @@
-use std::str::FromStr;
+use std::{io::BufRead, str::FromStr};
 
-use crate::de::XmlDeserializeError;
+use quick_xml::{
+    events::{attributes::Attributes, BytesStart, Event},
+    name::PrefixDeclaration,
+    NsReader, Writer,
+};
+
+use crate::{
+    de::{XmlDeserialize, XmlDeserializeError, XmlDeserializeResult},
+    ser::{merge_attributes, XmlSerialize, XmlSerializeResult},
+    ty::{XmlTag, XmlTargetNs},
+};
 
 #[derive(Debug, Clone, Default, Eq, PartialOrd, Ord)]
 pub struct ConstStr {
@@ -47,3 +57,440 @@ impl FromStr for ConstStr {
         })
     }
 }
+
+/// Qualified name of an element or attribute as written in the document, e.g. `ex:Order`.
+///
+/// The prefix is kept as is, so the name is written back unchanged. The namespace is only
+/// resolved for `tag` fields; a tag with a namespace is written with the declaration of its
+/// prefix (or the default namespace), so the element stays bound wherever it is written.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QName {
+    pub prefix: Option<String>,
+    pub local_name: String,
+    pub namespace: Option<String>,
+}
+
+impl QName {
+    pub fn new(local_name: impl Into<String>) -> Self {
+        Self {
+            prefix: None,
+            local_name: local_name.into(),
+            namespace: None,
+        }
+    }
+
+    pub fn with_prefix(prefix: impl Into<String>, local_name: impl Into<String>) -> Self {
+        Self {
+            prefix: Some(prefix.into()),
+            local_name: local_name.into(),
+            namespace: None,
+        }
+    }
+
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// The `xmlns` attribute binding the prefix of the name to its namespace, if it has one.
+    pub fn namespace_declaration(&self) -> Option<(String, &str)> {
+        let namespace = self.namespace.as_deref()?;
+        Some(match self.prefix.as_ref() {
+            Some(prefix) => (format!("xmlns:{prefix}"), namespace),
+            None => ("xmlns".to_string(), namespace),
+        })
+    }
+}
+
+impl std::fmt::Display for QName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.prefix.as_ref() {
+            Some(prefix) => write!(f, "{prefix}:{}", self.local_name),
+            None => self.local_name.fmt(f),
+        }
+    }
+}
+
+impl FromStr for QName {
+    type Err = XmlDeserializeError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.split_once(':') {
+            Some((prefix, local_name)) => Self::with_prefix(prefix, local_name),
+            None => Self::new(s),
+        })
+    }
+}
+
+/// Value of a nillable element (`nillable="true"` in the schema).
+///
+/// `<amount xsi:nil="true"/>` is read as [`Nillable::Nil`], any other content is read
+/// into [`Nillable::Value`]. Wrap it into an `Option` to tell a nil element apart
+/// from a missing one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Nillable<T> {
+    #[default]
+    Nil,
+    Value(T),
+}
+
+impl<T> Nillable<T> {
+    pub fn is_nil(&self) -> bool {
+        matches!(self, Self::Nil)
+    }
+
+    pub fn as_value(&self) -> Option<&T> {
+        match self {
+            Self::Nil => None,
+            Self::Value(v) => Some(v),
+        }
+    }
+
+    pub fn into_value(self) -> Option<T> {
+        match self {
+            Self::Nil => None,
+            Self::Value(v) => Some(v),
+        }
+    }
+}
+
+impl<T> From<T> for Nillable<T> {
+    fn from(value: T) -> Self {
+        Self::Value(value)
+    }
+}
+
+impl<T> XmlDeserialize for Nillable<T>
+where
+    T: XmlDeserialize,
+{
+    fn xml_deserialize<R>(
+        reader: &mut NsReader<R>,
+        target_ns: XmlTag,
+        tag: XmlTargetNs,
+        attributes: Attributes,
+        is_empty: bool,
+    ) -> XmlDeserializeResult<Self>
+    where
+        R: BufRead,
+    {
+        if crate::de::is_nil(reader, attributes.clone())? {
+            crate::de::skip_element(reader, is_empty)?;
+            Ok(Self::Nil)
+        } else {
+            T::xml_deserialize(reader, target_ns, tag, attributes, is_empty).map(Self::Value)
+        }
+    }
+
+    fn xml_deserialize_with_start<R>(
+        reader: &mut NsReader<R>,
+        target_ns: XmlTag,
+        tag: XmlTargetNs,
+        start: &BytesStart,
+        is_empty: bool,
+    ) -> XmlDeserializeResult<Self>
+    where
+        R: BufRead,
+    {
+        if crate::de::is_nil(reader, start.attributes())? {
+            crate::de::skip_element(reader, is_empty)?;
+            Ok(Self::Nil)
+        } else {
+            T::xml_deserialize_with_start(reader, target_ns, tag, start, is_empty).map(Self::Value)
+        }
+    }
+}
+
+impl<T> XmlSerialize for Nillable<T>
+where
+    T: XmlSerialize,
+{
+    fn xml_serialize<W: std::io::Write>(
+        &self,
+        tag: &str,
+        writer: &mut Writer<W>,
+    ) -> XmlSerializeResult<()> {
+        self.xml_serialize_with_attributes(tag, &[], writer)
+    }
+
+    fn xml_serialize_with_attributes<W: std::io::Write>(
+        &self,
+        tag: &str,
+        attributes: &[(&str, &str)],
+        writer: &mut Writer<W>,
+    ) -> XmlSerializeResult<()> {
+        match self {
+            Self::Nil => {
+                let own = [
+                    ("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"),
+                    ("xsi:nil", "true"),
+                ];
+                writer
+                    .create_element(tag)
+                    .with_attributes(merge_attributes(&own, attributes))
+                    .write_empty()?;
+                Ok(())
+            }
+            Self::Value(v) => v.xml_serialize_with_attributes(tag, attributes, writer),
+        }
+    }
+}
+
+/// The original markup of an element, e.g. a signed payload that must be forwarded untouched.
+///
+/// Deserialization captures the exact source of the element and the namespaces it inherits from
+/// its ancestors. Serialization writes the captured source with the inherited namespaces declared
+/// on the element, like [`RawXml::to_standalone`], the field name is ignored.
+/// Use [`RawXml::into_typed`] to parse it later.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RawXml {
+    xml: String,
+    namespaces: Vec<(String, String)>,
+}
+
+impl RawXml {
+    /// Wraps the markup of an element, which must not depend on inherited namespaces.
+    pub fn new(xml: impl Into<String>) -> Self {
+        Self {
+            xml: xml.into(),
+            namespaces: Vec::new(),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.xml
+    }
+
+    /// Namespaces inherited from the ancestors of the element as `(prefix, namespace)`,
+    /// the default namespace has an empty prefix.
+    pub fn namespaces(&self) -> &[(String, String)] {
+        &self.namespaces
+    }
+
+    /// The markup with the inherited namespaces declared on the element, so it can be parsed on
+    /// its own.
+    pub fn to_standalone(&self) -> String {
+        let declarations = self.namespace_declarations();
+        let declarations = declarations
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+        self.with_attributes(&declarations)
+    }
+
+    fn namespace_declarations(&self) -> Vec<(String, String)> {
+        self.namespaces
+            .iter()
+            .map(|(prefix, namespace)| {
+                let key = if prefix.is_empty() {
+                    "xmlns".to_string()
+                } else {
+                    format!("xmlns:{prefix}")
+                };
+                (key, namespace.clone())
+            })
+            .collect()
+    }
+
+    /// The markup with `attributes` added to the start tag of the element, except the ones the
+    /// element already has.
+    fn with_attributes(&self, attributes: &[(&str, &str)]) -> String {
+        let existing = match quick_xml::Reader::from_str(&self.xml).read_event() {
+            Ok(Event::Start(e) | Event::Empty(e)) => e
+                .attributes()
+                .with_checks(false)
+                .flatten()
+                .map(|attr| attr.key.as_ref().to_vec())
+                .collect(),
+            _ => Vec::new(),
+        };
+        let attributes = attributes
+            .iter()
+            .filter(|(key, _)| !existing.iter().any(|existing| existing == key.as_bytes()))
+            .collect::<Vec<_>>();
+        if attributes.is_empty() {
+            return self.xml.clone();
+        }
+        let name_end = self
+            .xml
+            .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+            .unwrap_or(self.xml.len());
+        let mut xml = String::with_capacity(self.xml.len() + 64);
+        xml.push_str(&self.xml[..name_end]);
+        for (key, value) in attributes {
+            xml.push_str(&format!(" {key}=\"{}\"", quick_xml::escape::escape(value)));
+        }
+        xml.push_str(&self.xml[name_end..]);
+        xml
+    }
+
+    /// Deserializes the element as `T`.
+    pub fn into_typed<T>(self) -> XmlDeserializeResult<T>
+    where
+        T: XmlDeserialize,
+    {
+        let xml = self.to_standalone();
+        let mut reader = NsReader::from_str(&xml);
+        reader.config_mut().trim_text(true);
+        let target_ns = T::target_ns().unwrap_or_default();
+        let tag = T::root().unwrap_or_default();
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) => {
+                    return T::xml_deserialize_with_start(&mut reader, target_ns, tag, &e, false)
+                }
+                Event::Empty(e) => {
+                    return T::xml_deserialize_with_start(&mut reader, target_ns, tag, &e, true)
+                }
+                Event::Eof => return Err(XmlDeserializeError::MissingElement(tag.into())),
+                _ => {}
+            }
+        }
+    }
+
+    fn read<R: BufRead>(
+        reader: &mut NsReader<R>,
+        start: &BytesStart,
+        is_empty: bool,
+    ) -> XmlDeserializeResult<Self> {
+        let declared: Vec<Vec<u8>> = start
+            .attributes()
+            .with_checks(false)
+            .flatten()
+            .filter_map(|attr| match attr.key.as_ref() {
+                b"xmlns" => Some(Vec::new()),
+                key => key.strip_prefix(b"xmlns:").map(<[u8]>::to_vec),
+            })
+            .collect();
+        let mut namespaces = Vec::new();
+        for (prefix, namespace) in reader.prefixes() {
+            let prefix = match prefix {
+                PrefixDeclaration::Default => &[][..],
+                PrefixDeclaration::Named(prefix) => prefix,
+            };
+            if !declared.iter().any(|declared| declared == prefix) {
+                namespaces.push((
+                    std::str::from_utf8(prefix)?.to_string(),
+                    std::str::from_utf8(namespace.as_ref())?.to_string(),
+                ));
+            }
+        }
+        let mut xml = Vec::new();
+        xml.push(b'<');
+        xml.extend_from_slice(start);
+        if is_empty {
+            xml.extend_from_slice(b"/>");
+        } else {
+            xml.push(b'>');
+            let trim = crate::de::set_trim_text(reader, false);
+            let result = Self::read_content(reader, &mut xml);
+            crate::de::set_trim_text(reader, trim);
+            result?;
+        }
+        Ok(Self {
+            xml: String::from_utf8(xml)?,
+            namespaces,
+        })
+    }
+
+    fn read_content<R: BufRead>(
+        reader: &mut NsReader<R>,
+        xml: &mut Vec<u8>,
+    ) -> XmlDeserializeResult<()> {
+        let mut buf = Vec::new();
+        let mut depth = 0usize;
+        loop {
+            let event = reader.read_event_into(&mut buf)?;
+            crate::de::check_event(&event)?;
+            let (open, content, close): (&[u8], &[u8], &[u8]) = match &event {
+                Event::Start(e) => {
+                    depth += 1;
+                    (b"<", e, b">")
+                }
+                Event::Empty(e) => (b"<", e, b"/>"),
+                Event::End(e) => {
+                    if depth == 0 {
+                        xml.extend_from_slice(b"</");
+                        xml.extend_from_slice(e);
+                        xml.push(b'>');
+                        return Ok(());
+                    }
+                    depth -= 1;
+                    (b"</", e, b">")
+                }
+                Event::Text(e) => (b"", e, b""),
+                Event::CData(e) => (b"<![CDATA[", e, b"]]>"),
+                Event::Comment(e) => (b"<!--", e, b"-->"),
+                Event::PI(e) => (b"<?", e, b"?>"),
+                Event::Decl(_) | Event::DocType(_) => (b"", b"", b""),
+                Event::Eof => return Err(XmlDeserializeError::UnexpectedEof),
+            };
+            xml.extend_from_slice(open);
+            xml.extend_from_slice(content);
+            xml.extend_from_slice(close);
+            buf.clear();
+        }
+    }
+}
+
+impl XmlDeserialize for RawXml {
+    fn xml_deserialize<R>(
+        reader: &mut NsReader<R>,
+        _target_ns: XmlTag,
+        tag: XmlTargetNs,
+        mut attributes: Attributes,
+        is_empty: bool,
+    ) -> XmlDeserializeResult<Self>
+    where
+        R: BufRead,
+    {
+        // without the original start tag it is rebuilt from the name and the raw attributes, the
+        // prefix is only kept if `tag` is the qualified name
+        let mut start = BytesStart::new(std::str::from_utf8(tag)?);
+        for attr in attributes.with_checks(false).flatten() {
+            start.push_attribute(attr);
+        }
+        Self::read(reader, &start, is_empty)
+    }
+
+    fn xml_deserialize_with_start<R>(
+        reader: &mut NsReader<R>,
+        _target_ns: XmlTag,
+        _tag: XmlTargetNs,
+        start: &BytesStart,
+        is_empty: bool,
+    ) -> XmlDeserializeResult<Self>
+    where
+        R: BufRead,
+    {
+        Self::read(reader, start, is_empty)
+    }
+}
+
+impl XmlSerialize for RawXml {
+    fn xml_serialize<W: std::io::Write>(
+        &self,
+        tag: &str,
+        writer: &mut Writer<W>,
+    ) -> XmlSerializeResult<()> {
+        self.xml_serialize_with_attributes(tag, &[], writer)
+    }
+
+    fn xml_serialize_with_attributes<W: std::io::Write>(
+        &self,
+        _tag: &str,
+        attributes: &[(&str, &str)],
+        writer: &mut Writer<W>,
+    ) -> XmlSerializeResult<()> {
+        let declarations = self.namespace_declarations();
+        let declarations = declarations
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+        let attributes = merge_attributes(attributes, &declarations);
+        writer
+            .get_mut()
+            .write_all(self.with_attributes(&attributes).as_bytes())
+            .map_err(quick_xml::Error::from)?;
+        Ok(())
+    }
+}
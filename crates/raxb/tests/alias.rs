@@ -0,0 +1,123 @@
+use raxb::{value::ConstStr, XmlDeserialize, XmlSerialize};
+
+const ORDERS_V1: &[u8] = b"https://orders.example.org/v1";
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"Order")]
+#[raxb(tns(b"o", b"https://orders.example.org/v2", ORDERS_V1))]
+pub struct Order {
+    #[raxb(
+        default,
+        ns = b"xmlns",
+        name = b"o",
+        ty = "attr",
+        value = "https://orders.example.org/v2"
+    )]
+    _xmlns: ConstStr,
+    #[raxb(name = b"Currency", alias = b"Curr", alias = b"Cur", ty = "attr")]
+    pub currency: String,
+    #[raxb(name = b"OrderReference", alias = b"OrderRef", ns = b"o", ty = "child")]
+    pub reference: String,
+    #[raxb(name = b"Line", alias = b"Item", ns = b"o", ty = "child")]
+    pub lines: Vec<Line>,
+    #[raxb(name = b"Payment", ns = b"o", ty = "child")]
+    pub payment: Payment,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(tns(b"o", b"https://orders.example.org/v2", ORDERS_V1))]
+pub struct Line {
+    #[raxb(name = b"Sku", alias = b"ArticleNumber", ns = b"o", ty = "child")]
+    pub sku: String,
+    #[raxb(default, name = b"Express", alias = b"Urgent", ns = b"o", ty = "sfc")]
+    pub express: bool,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub enum Payment {
+    #[raxb(name = b"Invoice", alias = b"Bill", ns = b"o")]
+    Invoice(Invoice),
+    #[raxb(name = b"Card", ns = b"o")]
+    Card,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(tns(b"o", b"https://orders.example.org/v2", ORDERS_V1))]
+pub struct Invoice {
+    #[raxb(name = b"Days", ns = b"o", ty = "child")]
+    pub days: u32,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"Catalog")]
+pub struct Catalog {
+    #[raxb(
+        name = b"Entry",
+        alias = b"Product",
+        wrapper = b"Entries",
+        ty = "child"
+    )]
+    pub entries: Vec<String>,
+}
+
+fn order() -> Order {
+    Order {
+        _xmlns: ConstStr::default(),
+        currency: "EUR".to_string(),
+        reference: "A-1".to_string(),
+        lines: vec![
+            Line {
+                sku: "s1".to_string(),
+                express: false,
+            },
+            Line {
+                sku: "s2".to_string(),
+                express: true,
+            },
+        ],
+        payment: Payment::Invoice(Invoice { days: 30 }),
+    }
+}
+
+#[test]
+fn test_alias_serializes_primary_names() -> anyhow::Result<()> {
+    let xml = raxb::ser::to_string(&order())?;
+    assert_eq!(
+        xml,
+        r#"<o:Order xmlns:o="https://orders.example.org/v2" Currency="EUR"><o:OrderReference>A-1</o:OrderReference><o:Line><o:Sku>s1</o:Sku></o:Line><o:Line><o:Sku>s2</o:Sku><o:Express/></o:Line><o:Payment><o:Invoice><o:Days>30</o:Days></o:Invoice></o:Payment></o:Order>"#
+    );
+    assert_eq!(raxb::de::from_str::<Order>(&xml)?, order());
+    Ok(())
+}
+
+#[test]
+fn test_alias_deserializes_former_names() -> anyhow::Result<()> {
+    let xml = r#"<o:Order xmlns:o="https://orders.example.org/v2" Cur="EUR"><o:OrderRef>A-1</o:OrderRef><o:Item><o:ArticleNumber>s1</o:ArticleNumber></o:Item><o:Line><o:Sku>s2</o:Sku><o:Urgent/></o:Line><o:Payment><o:Bill><o:Days>30</o:Days></o:Bill></o:Payment></o:Order>"#;
+    assert_eq!(raxb::de::from_str::<Order>(xml)?, order());
+    Ok(())
+}
+
+#[test]
+fn test_alias_deserializes_former_namespace() -> anyhow::Result<()> {
+    let xml = r#"<Order xmlns="https://orders.example.org/v1" Curr="EUR"><OrderRef>A-1</OrderRef><Line><Sku>s1</Sku></Line><Item><Sku>s2</Sku><Express/></Item><Payment><Invoice><Days>30</Days></Invoice></Payment></Order>"#;
+    assert_eq!(raxb::de::from_str::<Order>(xml)?, order());
+    Ok(())
+}
+
+#[test]
+fn test_alias_ignores_other_namespaces() {
+    let xml = r#"<o:Order xmlns:o="https://orders.example.org/v0" Currency="EUR"/>"#;
+    assert!(raxb::de::from_str::<Order>(xml).is_err());
+}
+
+#[test]
+fn test_alias_in_wrapper() -> anyhow::Result<()> {
+    let xml = r#"<Catalog><Entries><Product>a</Product><Entry>b</Entry></Entries></Catalog>"#;
+    let catalog: Catalog = raxb::de::from_str(xml)?;
+    assert_eq!(catalog.entries, vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(
+        raxb::ser::to_string(&catalog)?,
+        r#"<Catalog><Entries><Entry>a</Entry><Entry>b</Entry></Entries></Catalog>"#
+    );
+    Ok(())
+}
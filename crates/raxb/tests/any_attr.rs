@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+
+use raxb::{value::QName, XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"order")]
+pub struct Order {
+    #[raxb(name = b"id", ty = "attr")]
+    pub id: u32,
+    #[raxb(ty = "any_attr")]
+    pub extra: Vec<(QName, String)>,
+    #[raxb(name = b"item", ty = "child")]
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Item {
+    #[raxb(ty = "any_attr")]
+    pub attributes: BTreeMap<QName, String>,
+    #[raxb(ty = "text")]
+    pub content: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+#[raxb(root = b"doc")]
+pub struct Located {
+    #[raxb(name = b"schemaLocation", ty = "attr")]
+    pub location: Option<String>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+#[raxb(root = b"doc", tns(b"x", b"urn:x"))]
+pub struct Qualified {
+    #[raxb(ns = b"xsi", name = b"schemaLocation", ty = "attr")]
+    pub location: Option<String>,
+    #[raxb(ns = b"x", name = b"kind", ty = "attr")]
+    pub kind: Option<String>,
+    #[raxb(ty = "any_attr")]
+    pub extra: Vec<(QName, String)>,
+}
+
+#[test]
+fn test_any_attr() -> anyhow::Result<()> {
+    let xml = r#"<order xmlns:x="urn:x" x:channel="web" id="1" note="a &amp; b"><item sku="s1" x:qty="2">pen</item></order>"#;
+    let order: Order = raxb::de::from_str(xml)?;
+    assert_eq!(
+        order,
+        Order {
+            id: 1,
+            extra: vec![
+                (QName::with_prefix("xmlns", "x"), "urn:x".to_string()),
+                (QName::with_prefix("x", "channel"), "web".to_string()),
+                (QName::new("note"), "a & b".to_string()),
+            ],
+            items: vec![Item {
+                attributes: BTreeMap::from([
+                    (QName::new("sku"), "s1".to_string()),
+                    (QName::with_prefix("x", "qty"), "2".to_string()),
+                ]),
+                content: "pen".to_string(),
+            }],
+        }
+    );
+    assert_eq!(
+        raxb::ser::to_string(&order)?,
+        r#"<order id="1" xmlns:x="urn:x" x:channel="web" note="a &amp; b"><item sku="s1" x:qty="2">pen</item></order>"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_any_attr_of_other_namespaces() -> anyhow::Result<()> {
+    let xml = r#"<order x:id="2" id="1" xmlns:x="urn:x"></order>"#;
+    let order: Order = raxb::de::from_str(xml)?;
+    assert_eq!(order.id, 1);
+    assert_eq!(
+        order.extra,
+        vec![
+            (QName::with_prefix("x", "id"), "2".to_string()),
+            (QName::with_prefix("xmlns", "x"), "urn:x".to_string()),
+        ]
+    );
+    assert_eq!(
+        raxb::ser::to_string(&order)?,
+        r#"<order id="1" x:id="2" xmlns:x="urn:x"></order>"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_any_attr_skips_own_attributes() -> anyhow::Result<()> {
+    let order = Order {
+        id: 1,
+        extra: vec![
+            (QName::new("id"), "2".to_string()),
+            (QName::new("note"), "a".to_string()),
+        ],
+        items: vec![],
+    };
+    assert_eq!(
+        raxb::ser::to_string(&order)?,
+        r#"<order id="1" note="a"></order>"#
+    );
+    Ok(())
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn test_any_attr_index_map() -> anyhow::Result<()> {
+    use raxb::indexmap::IndexMap;
+
+    #[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+    #[raxb(root = b"line")]
+    pub struct Line {
+        #[raxb(ty = "any_attr")]
+        pub attributes: IndexMap<QName, String>,
+    }
+
+    let xml = r#"<line b="2" a="1"/>"#;
+    let line: Line = raxb::de::from_str(xml)?;
+    assert_eq!(
+        line.attributes.keys().collect::<Vec<_>>(),
+        [&QName::new("b"), &QName::new("a")]
+    );
+    assert_eq!(raxb::ser::to_string(&line)?, xml);
+    Ok(())
+}
+
+#[test]
+fn test_prefixed_attribute_of_unqualified_field() -> anyhow::Result<()> {
+    let xml =
+        r#"<doc xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="x"/>"#;
+    let located: Located = raxb::de::from_str(xml)?;
+    assert_eq!(located.location.as_deref(), Some("x"));
+    Ok(())
+}
+
+#[test]
+fn test_attributes_by_namespace() -> anyhow::Result<()> {
+    let xml = r#"<y:doc xmlns:i="http://www.w3.org/2001/XMLSchema-instance" xmlns:y="urn:x" i:schemaLocation="a" y:kind="b"/>"#;
+    let qualified: Qualified = raxb::de::from_str(xml)?;
+    assert_eq!(qualified.location.as_deref(), Some("a"));
+    assert_eq!(qualified.kind.as_deref(), Some("b"));
+
+    let xml = r#"<x:doc xmlns:xsi="urn:other" xmlns:x="urn:x" xsi:schemaLocation="a" kind="b"/>"#;
+    let qualified: Qualified = raxb::de::from_str(xml)?;
+    assert_eq!(qualified.location, None);
+    assert_eq!(qualified.kind, None);
+    assert!(qualified
+        .extra
+        .contains(&(QName::with_prefix("xsi", "schemaLocation"), "a".to_string())));
+    assert!(qualified
+        .extra
+        .contains(&(QName::new("kind"), "b".to_string())));
+    Ok(())
+}
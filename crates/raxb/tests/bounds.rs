@@ -0,0 +1,135 @@
+use std::marker::PhantomData;
+
+use raxb::{XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"envelope")]
+pub struct Envelope<T> {
+    #[raxb(name = b"body", ty = "child")]
+    pub body: T,
+    #[raxb(name = b"attachment", ty = "child")]
+    pub attachments: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Ping {
+    #[raxb(name = b"id", ty = "attr")]
+    pub id: u32,
+}
+
+/// Only written, it does not implement `XmlDeserialize`.
+#[derive(Debug, XmlSerialize)]
+pub struct Report {
+    #[raxb(name = b"total", ty = "attr")]
+    pub total: u32,
+}
+
+/// Not part of the xml.
+#[derive(Debug, Default, PartialEq)]
+pub struct Marker;
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"message")]
+pub struct Message<T, K> {
+    #[raxb(name = b"payload", ty = "child")]
+    pub payload: Option<T>,
+    #[raxb(skip)]
+    pub kind: PhantomData<K>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub enum Either<A, B> {
+    #[raxb(name = b"left")]
+    Left(A),
+    #[raxb(name = b"right")]
+    Right(B),
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(
+    root = b"measure",
+    bound = "T: std::str::FromStr + Default + std::fmt::Display"
+)]
+pub struct Measure<T> {
+    #[raxb(name = b"amount", ty = "attr")]
+    pub amount: T,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(
+    root = b"quantity",
+    bound = "T: std::str::FromStr + std::fmt::Display, raxb::de::XmlDeserializeError: From<T::Err>"
+)]
+pub struct Quantity<T> {
+    #[raxb(name = b"unit", ty = "attr")]
+    pub unit: String,
+    #[raxb(ty = "text")]
+    pub amount: T,
+}
+
+#[test]
+fn test_inferred_bounds() -> anyhow::Result<()> {
+    let envelope = Envelope {
+        body: Ping { id: 1 },
+        attachments: vec![Ping { id: 2 }],
+    };
+    let xml = raxb::ser::to_string(&envelope)?;
+    assert_eq!(
+        xml,
+        r#"<envelope><body id="1"/><attachment id="2"/></envelope>"#
+    );
+    assert_eq!(raxb::de::from_str::<Envelope<Ping>>(&xml)?, envelope);
+
+    let message = Message::<Either<Ping, Ping>, Marker> {
+        payload: Some(Either::Right(Ping { id: 3 })),
+        kind: PhantomData,
+    };
+    let xml = raxb::ser::to_string(&message)?;
+    assert_eq!(
+        xml,
+        r#"<message><payload><right id="3"/></payload></message>"#
+    );
+    assert_eq!(raxb::de::from_str::<Message<_, Marker>>(&xml)?, message);
+    Ok(())
+}
+
+#[test]
+fn test_serialize_only_parameter() -> anyhow::Result<()> {
+    let envelope = Envelope {
+        body: Report { total: 5 },
+        attachments: vec![],
+    };
+    assert_eq!(
+        raxb::ser::to_string(&envelope)?,
+        r#"<envelope><body total="5"/></envelope>"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_bound_override() -> anyhow::Result<()> {
+    let measure: Measure<f32> = raxb::de::from_str(r#"<measure amount="1.5"/>"#)?;
+    assert_eq!(measure, Measure { amount: 1.5 });
+    assert_eq!(
+        raxb::ser::to_string(&measure)?,
+        r#"<measure amount="1.5"/>"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_bound_override_of_text() -> anyhow::Result<()> {
+    let quantity: Quantity<u32> = raxb::de::from_str(r#"<quantity unit="kg">3</quantity>"#)?;
+    assert_eq!(
+        quantity,
+        Quantity {
+            unit: "kg".to_string(),
+            amount: 3
+        }
+    );
+    assert_eq!(
+        raxb::ser::to_string(&quantity)?,
+        r#"<quantity unit="kg">3</quantity>"#
+    );
+    Ok(())
+}
@@ -0,0 +1,363 @@
+use raxb::{
+    ser::{
+        canonicalize, canonicalize_str_with_options, C14nMode, C14nOptions, ElementSelector,
+        XmlSerializeError,
+    },
+    XmlSerialize,
+};
+
+fn c14n(xml: &str, mode: C14nMode) -> String {
+    String::from_utf8(canonicalize(xml.as_bytes(), mode).unwrap()).unwrap()
+}
+
+// W3C Canonical XML 1.0, example 3.1
+const PIS_AND_COMMENTS: &str = r#"<?xml version="1.0"?>
+
+<?xml-stylesheet   href="doc.xsl"
+   type="text/xsl"   ?>
+
+<!DOCTYPE doc SYSTEM "doc.dtd">
+
+<doc>Hello, world!<!-- Comment 1 --></doc>
+
+<?pi-without-data     ?>
+
+<!-- Comment 2 -->
+
+<!-- Comment 3 -->"#;
+
+#[test]
+fn test_pis_and_comments() {
+    assert_eq!(
+        c14n(PIS_AND_COMMENTS, C14nMode::Inclusive),
+        r#"<?xml-stylesheet href="doc.xsl"
+   type="text/xsl"   ?>
+<doc>Hello, world!</doc>
+<?pi-without-data?>"#
+    );
+    assert_eq!(
+        c14n(PIS_AND_COMMENTS, C14nMode::WithComments),
+        r#"<?xml-stylesheet href="doc.xsl"
+   type="text/xsl"   ?>
+<doc>Hello, world!<!-- Comment 1 --></doc>
+<?pi-without-data?>
+<!-- Comment 2 -->
+<!-- Comment 3 -->"#
+    );
+}
+
+// W3C Canonical XML 1.0, example 3.2
+#[test]
+fn test_whitespace_in_content() {
+    let xml = "<doc>
+   <clean>   </clean>
+   <dirty>   A   B   </dirty>
+   <mixed>
+      A
+      <clean>   </clean>
+      B
+      <dirty>   A   B   </dirty>
+      C
+   </mixed>
+</doc>";
+    assert_eq!(c14n(xml, C14nMode::Inclusive), xml);
+}
+
+// W3C Canonical XML 1.0, example 3.3 without the default attribute declared in the DTD
+#[test]
+fn test_start_and_end_tags() {
+    let xml = r#"<!DOCTYPE doc [<!ATTLIST e9 attr CDATA "default">]>
+<doc>
+   <e1   />
+   <e2   ></e2>
+   <e3   name = "elem3"   id="elem3"   />
+   <e4   name="elem4"   id="elem4"   ></e4>
+   <e5 a:attr="out" b:attr="sorted" attr2="all" attr="I'm"
+      xmlns:b="http://www.ietf.org"
+      xmlns:a="http://www.w3.org"
+      xmlns="http://example.org"/>
+   <e6 xmlns="" xmlns:a="http://www.w3.org">
+      <e7 xmlns="http://www.ietf.org">
+         <e8 xmlns="" xmlns:a="http://www.w3.org">
+            <e9 xmlns="" xmlns:a="http://www.ietf.org"/>
+         </e8>
+      </e7>
+   </e6>
+</doc>"#;
+    assert_eq!(
+        c14n(xml, C14nMode::Inclusive),
+        r#"<doc>
+   <e1></e1>
+   <e2></e2>
+   <e3 id="elem3" name="elem3"></e3>
+   <e4 id="elem4" name="elem4"></e4>
+   <e5 xmlns="http://example.org" xmlns:a="http://www.w3.org" xmlns:b="http://www.ietf.org" attr="I'm" attr2="all" b:attr="sorted" a:attr="out"></e5>
+   <e6 xmlns:a="http://www.w3.org">
+      <e7 xmlns="http://www.ietf.org">
+         <e8 xmlns="">
+            <e9 xmlns:a="http://www.ietf.org"></e9>
+         </e8>
+      </e7>
+   </e6>
+</doc>"#
+    );
+}
+
+// W3C Canonical XML 1.0, example 3.4 without the attributes typed by the DTD
+#[test]
+fn test_character_modifications() {
+    let xml = r#"<doc>
+   <text>First line&#x0d;&#10;Second line</text>
+   <value>&#x32;</value>
+   <compute><![CDATA[value>"0" && value<"10" ?"valid":"error"]]></compute>
+   <compute expr='value>"0" &amp;&amp; value&lt;"10" ?"valid":"error"'>valid</compute>
+   <norm attr=' &apos;   &#x20;&#13;&#xa;&#9;   &apos; '/>
+</doc>"#;
+    assert_eq!(
+        c14n(xml, C14nMode::Inclusive),
+        r#"<doc>
+   <text>First line&#xD;
+Second line</text>
+   <value>2</value>
+   <compute>value&gt;"0" &amp;&amp; value&lt;"10" ?"valid":"error"</compute>
+   <compute expr="value>&quot;0&quot; &amp;&amp; value&lt;&quot;10&quot; ?&quot;valid&quot;:&quot;error&quot;">valid</compute>
+   <norm attr=" '    &#xD;&#xA;&#x9;   ' "></norm>
+</doc>"#
+    );
+}
+
+#[test]
+fn test_line_endings() {
+    assert_eq!(
+        c14n("<doc a=\"x\r\ny\">1\r\n2\r3</doc>", C14nMode::Inclusive),
+        "<doc a=\"x y\">1\n2\n3</doc>"
+    );
+}
+
+// W3C Canonical XML 1.0, example 3.6 with a character reference
+#[test]
+fn test_utf8() {
+    assert_eq!(
+        canonicalize(
+            b"<?xml version=\"1.0\"?><doc>&#169;</doc>",
+            C14nMode::Inclusive
+        )
+        .unwrap(),
+        "<doc>\u{a9}</doc>".as_bytes()
+    );
+}
+
+// W3C Exclusive XML Canonicalization 1.0, section 2.2
+const NAMESPACES: &str = r#"<n0:local xmlns:n0="foo:bar" xmlns:n3="ftp://example.org">
+  <n1:elem2 xmlns:n1="http://example.net" xml:lang="en">
+    <n3:stuff xmlns:n3="ftp://example.org"/>
+  </n1:elem2>
+</n0:local>"#;
+
+#[test]
+fn test_inclusive_namespaces() {
+    assert_eq!(
+        c14n(NAMESPACES, C14nMode::Inclusive),
+        r#"<n0:local xmlns:n0="foo:bar" xmlns:n3="ftp://example.org">
+  <n1:elem2 xmlns:n1="http://example.net" xml:lang="en">
+    <n3:stuff></n3:stuff>
+  </n1:elem2>
+</n0:local>"#
+    );
+}
+
+#[test]
+fn test_exclusive_namespaces() {
+    assert_eq!(
+        c14n(NAMESPACES, C14nMode::Exclusive),
+        r#"<n0:local xmlns:n0="foo:bar">
+  <n1:elem2 xmlns:n1="http://example.net" xml:lang="en">
+    <n3:stuff xmlns:n3="ftp://example.org"></n3:stuff>
+  </n1:elem2>
+</n0:local>"#
+    );
+}
+
+fn c14n_with_options(xml: &str, mode: C14nMode, options: &C14nOptions) -> String {
+    String::from_utf8(canonicalize_str_with_options(xml, mode, options).unwrap()).unwrap()
+}
+
+fn elem2() -> Option<ElementSelector> {
+    Some(ElementSelector::Name {
+        namespace: "http://example.net".to_string(),
+        local_name: "elem2".to_string(),
+    })
+}
+
+// W3C Exclusive XML Canonicalization 1.0, section 2.2, document subset of n1:elem2
+#[test]
+fn test_inclusive_subtree() {
+    let options = C14nOptions {
+        subtree: elem2(),
+        ..Default::default()
+    };
+    assert_eq!(
+        c14n_with_options(NAMESPACES, C14nMode::Inclusive, &options),
+        r#"<n1:elem2 xmlns:n0="foo:bar" xmlns:n1="http://example.net" xmlns:n3="ftp://example.org" xml:lang="en">
+    <n3:stuff></n3:stuff>
+  </n1:elem2>"#
+    );
+}
+
+#[test]
+fn test_exclusive_subtree() {
+    let options = C14nOptions {
+        subtree: elem2(),
+        ..Default::default()
+    };
+    assert_eq!(
+        c14n_with_options(NAMESPACES, C14nMode::Exclusive, &options),
+        r#"<n1:elem2 xmlns:n1="http://example.net" xml:lang="en">
+    <n3:stuff xmlns:n3="ftp://example.org"></n3:stuff>
+  </n1:elem2>"#
+    );
+    let options = C14nOptions {
+        subtree: elem2(),
+        inclusive_prefixes: vec!["n0".to_string(), "#default".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(
+        c14n_with_options(NAMESPACES, C14nMode::Exclusive, &options),
+        r#"<n1:elem2 xmlns:n0="foo:bar" xmlns:n1="http://example.net" xml:lang="en">
+    <n3:stuff xmlns:n3="ftp://example.org"></n3:stuff>
+  </n1:elem2>"#
+    );
+}
+
+#[test]
+fn test_inherited_xml_attributes() {
+    let xml = r#"<a xml:lang="en" xml:space="preserve"><b xml:lang="de"><c/></b></a>"#;
+    let options = C14nOptions {
+        subtree: Some(ElementSelector::Name {
+            namespace: String::new(),
+            local_name: "b".to_string(),
+        }),
+        ..Default::default()
+    };
+    assert_eq!(
+        c14n_with_options(xml, C14nMode::Inclusive, &options),
+        r#"<b xml:lang="de" xml:space="preserve"><c></c></b>"#
+    );
+    assert_eq!(
+        c14n_with_options(xml, C14nMode::Exclusive, &options),
+        r#"<b xml:lang="de"><c></c></b>"#
+    );
+}
+
+#[test]
+fn test_exclude_and_select_by_id() {
+    let xml = r#"<doc><data Id="d1"><v>1</v></data><sig:Signature xmlns:sig="urn:sig"><x/></sig:Signature></doc>"#;
+    let options = C14nOptions {
+        exclude: Some(ElementSelector::Name {
+            namespace: "urn:sig".to_string(),
+            local_name: "Signature".to_string(),
+        }),
+        ..Default::default()
+    };
+    assert_eq!(
+        c14n_with_options(xml, C14nMode::Exclusive, &options),
+        r#"<doc><data Id="d1"><v>1</v></data></doc>"#
+    );
+    let options = C14nOptions {
+        subtree: Some(ElementSelector::Id("d1".to_string())),
+        ..Default::default()
+    };
+    assert_eq!(
+        c14n_with_options(xml, C14nMode::Exclusive, &options),
+        r#"<data Id="d1"><v>1</v></data>"#
+    );
+}
+
+#[test]
+fn test_duplicate_id() {
+    let options = C14nOptions {
+        subtree: Some(ElementSelector::Id("d1".to_string())),
+        ..Default::default()
+    };
+    for xml in [
+        r#"<doc><data Id="d1"/><copy><data ID="d1"/></copy></doc>"#,
+        r#"<doc><data Id="d1"><data id="d1"/></data></doc>"#,
+    ] {
+        assert!(matches!(
+            canonicalize_str_with_options(xml, C14nMode::Exclusive, &options),
+            Err(XmlSerializeError::DuplicateId(id)) if id == "d1"
+        ));
+    }
+}
+
+#[test]
+fn test_missing_subtree() {
+    let options = C14nOptions {
+        subtree: Some(ElementSelector::Id("missing".to_string())),
+        ..Default::default()
+    };
+    assert!(matches!(
+        canonicalize_str_with_options(r#"<doc><data Id="d1"/></doc>"#, C14nMode::Exclusive, &options),
+        Err(XmlSerializeError::MissingSubtree(id)) if id == "missing"
+    ));
+}
+
+#[test]
+fn test_algorithm() {
+    for mode in [
+        C14nMode::Inclusive,
+        C14nMode::Exclusive,
+        C14nMode::WithComments,
+        C14nMode::ExclusiveWithComments,
+    ] {
+        assert_eq!(C14nMode::from_algorithm(mode.algorithm()), Some(mode));
+    }
+    assert_eq!(C14nMode::from_algorithm("urn:unknown"), None);
+}
+
+#[test]
+fn test_exclusive_default_namespace() {
+    let xml = r#"<a xmlns="urn:a" xmlns:unused="urn:unused"><b xmlns=""><c:d xmlns:c="urn:c" c:e="1" f="2"/></b><!-- x --></a>"#;
+    assert_eq!(
+        c14n(xml, C14nMode::Exclusive),
+        r#"<a xmlns="urn:a"><b xmlns=""><c:d xmlns:c="urn:c" f="2" c:e="1"></c:d></b></a>"#
+    );
+    assert_eq!(
+        c14n(xml, C14nMode::ExclusiveWithComments),
+        r#"<a xmlns="urn:a"><b xmlns=""><c:d xmlns:c="urn:c" f="2" c:e="1"></c:d></b><!-- x --></a>"#
+    );
+}
+
+#[test]
+fn test_unbound_prefix() {
+    assert!(canonicalize(b"<a:doc/>", C14nMode::Exclusive).is_err());
+}
+
+#[derive(Debug, XmlSerialize)]
+#[raxb(root = b"order")]
+pub struct Order {
+    #[raxb(name = b"status", ty = "attr")]
+    pub status: String,
+    #[raxb(name = b"id", ty = "attr")]
+    pub id: u32,
+    #[raxb(name = b"note", ty = "child")]
+    pub note: Option<String>,
+    #[raxb(name = b"gift", ty = "sfc")]
+    pub gift: bool,
+}
+
+#[test]
+fn test_to_canonical() -> anyhow::Result<()> {
+    let order = Order {
+        status: "open & paid".to_string(),
+        id: 7,
+        note: Some(r#"say "hi" > bye"#.to_string()),
+        gift: true,
+    };
+    let canonical = raxb::ser::to_canonical(&order, C14nMode::Exclusive)?;
+    assert_eq!(
+        String::from_utf8(canonical)?,
+        r#"<order id="7" status="open &amp; paid"><note>say "hi" &gt; bye</note><gift></gift></order>"#
+    );
+    Ok(())
+}
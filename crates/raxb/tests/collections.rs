@@ -0,0 +1,159 @@
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+use raxb::{
+    de::XmlDeserializeError,
+    facet::{Facet, Violation},
+    ser::XmlSerializeError,
+    XmlDeserialize, XmlSerialize,
+};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"route")]
+pub struct Route {
+    #[raxb(name = b"stop", ty = "child")]
+    pub stops: VecDeque<String>,
+    #[raxb(name = b"tag", ty = "child")]
+    pub tags: BTreeSet<String>,
+    #[raxb(name = b"zone", ty = "child")]
+    pub zones: HashSet<u32>,
+    #[raxb(name = b"point", ty = "child")]
+    pub points: [Point; 2],
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Point {
+    #[raxb(name = b"x", ty = "attr")]
+    pub x: i32,
+    #[raxb(name = b"y", ty = "attr")]
+    pub y: i32,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"team")]
+pub struct Team {
+    #[raxb(name = b"member", ty = "child", min_occurs = 1, max_occurs = 3)]
+    pub members: Vec<String>,
+    #[raxb(name = b"coach", ty = "child", min_occurs = 1)]
+    pub coach: Option<String>,
+}
+
+fn route() -> Route {
+    Route {
+        stops: VecDeque::from(["b".to_string(), "a".to_string()]),
+        tags: BTreeSet::from(["x".to_string(), "y".to_string()]),
+        zones: HashSet::from([1]),
+        points: [Point { x: 0, y: 1 }, Point { x: 2, y: 3 }],
+    }
+}
+
+fn deserialize_error<T: raxb::de::XmlDeserialize + std::fmt::Debug>(xml: &str) -> Violation {
+    match raxb::de::from_str::<T>(xml) {
+        Err(XmlDeserializeError::Constraint(violation)) => violation,
+        result => panic!("expected a constraint violation, got {result:?}"),
+    }
+}
+
+#[test]
+fn test_collections() -> anyhow::Result<()> {
+    let xml = raxb::ser::to_string(&route())?;
+    assert_eq!(
+        xml,
+        r#"<route><stop>b</stop><stop>a</stop><tag>x</tag><tag>y</tag><zone>1</zone><point x="0" y="1"/><point x="2" y="3"/></route>"#
+    );
+    assert_eq!(raxb::de::from_str::<Route>(&xml)?, route());
+    Ok(())
+}
+
+#[test]
+fn test_sets_deduplicate() -> anyhow::Result<()> {
+    let xml = r#"<route><stop>a</stop><tag>y</tag><tag>x</tag><tag>y</tag><zone>2</zone><zone>2</zone><point x="0" y="0"/><point x="0" y="0"/></route>"#;
+    let route: Route = raxb::de::from_str(xml)?;
+    assert_eq!(
+        route.tags.into_iter().collect::<Vec<_>>(),
+        vec!["x".to_string(), "y".to_string()]
+    );
+    assert_eq!(route.zones, HashSet::from([2]));
+    Ok(())
+}
+
+#[test]
+fn test_array_length() {
+    let violation = deserialize_error::<Route>(r#"<route><point x="0" y="0"/></route>"#);
+    assert_eq!(violation, Violation::new("point", Facet::MinOccurs(2)));
+    let violation = deserialize_error::<Route>(
+        r#"<route><point x="0" y="0"/><point x="0" y="0"/><point x="0" y="0"/></route>"#,
+    );
+    assert_eq!(violation, Violation::new("point", Facet::MaxOccurs(2)));
+}
+
+#[test]
+fn test_occurs() -> anyhow::Result<()> {
+    let team: Team = raxb::de::from_str(r#"<team><member>a</member><coach>b</coach></team>"#)?;
+    assert_eq!(team.members, vec!["a".to_string()]);
+
+    let violation = deserialize_error::<Team>(r#"<team><coach>b</coach></team>"#);
+    assert_eq!(violation, Violation::new("member", Facet::MinOccurs(1)));
+    let violation = deserialize_error::<Team>(
+        r#"<team><member>a</member><member>b</member><member>c</member><member>d</member><coach>b</coach></team>"#,
+    );
+    assert_eq!(violation, Violation::new("member", Facet::MaxOccurs(3)));
+    let violation = deserialize_error::<Team>(r#"<team><member>a</member></team>"#);
+    assert_eq!(violation, Violation::new("coach", Facet::MinOccurs(1)));
+    assert_eq!(
+        violation.to_string(),
+        "value of 'coach' occurs less than 1 times"
+    );
+
+    let team = Team {
+        members: vec![],
+        coach: Some("b".to_string()),
+    };
+    assert!(matches!(
+        raxb::ser::to_string(&team),
+        Err(XmlSerializeError::Constraint(violation)) if violation == Violation::new("member", Facet::MinOccurs(1))
+    ));
+    Ok(())
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn test_index_set() -> anyhow::Result<()> {
+    use raxb::indexmap::IndexSet;
+
+    #[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+    #[raxb(root = b"tags")]
+    pub struct Tags {
+        #[raxb(name = b"tag", ty = "child")]
+        pub tags: IndexSet<String>,
+    }
+
+    let tags: Tags = raxb::de::from_str(r#"<tags><tag>b</tag><tag>a</tag><tag>b</tag></tags>"#)?;
+    assert_eq!(
+        tags.tags.iter().collect::<Vec<_>>(),
+        vec![&"b".to_string(), &"a".to_string()]
+    );
+    assert_eq!(
+        raxb::ser::to_string(&tags)?,
+        r#"<tags><tag>b</tag><tag>a</tag></tags>"#
+    );
+    Ok(())
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn test_small_vec() -> anyhow::Result<()> {
+    use raxb::smallvec::SmallVec;
+
+    #[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+    #[raxb(root = b"tags")]
+    pub struct Tags {
+        #[raxb(name = b"tag", ty = "child")]
+        pub tags: SmallVec<[String; 2]>,
+    }
+
+    let xml = r#"<tags><tag>a</tag><tag>b</tag><tag>c</tag></tags>"#;
+    let tags: Tags = raxb::de::from_str(xml)?;
+    assert_eq!(tags.tags.as_slice(), ["a", "b", "c"]);
+    assert_eq!(raxb::ser::to_string(&tags)?, xml);
+    Ok(())
+}
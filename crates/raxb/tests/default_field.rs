@@ -33,3 +33,164 @@ fn test_default_field_serde() -> anyhow::Result<()> {
     assert_eq!(a1, a2);
     Ok(())
 }
+
+fn default_port() -> u16 {
+    8080
+}
+
+fn default_host() -> String {
+    "localhost".to_string()
+}
+
+fn default_mode() -> Option<String> {
+    Some("passive".to_string())
+}
+
+fn default_paths() -> Vec<String> {
+    vec!["/".to_string()]
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+#[derive(Debug, XmlSerialize, XmlDeserialize, PartialEq)]
+#[xml(root = b"server")]
+pub struct Server {
+    #[xml(name = b"port", ty = "attr", default = "default_port")]
+    pub port: u16,
+    #[xml(name = b"host", ty = "child", default = "default_host")]
+    pub host: String,
+    #[xml(name = b"mode", ty = "child", default = "default_mode")]
+    pub mode: Option<String>,
+    #[xml(name = b"path", ty = "child", default = "default_paths")]
+    pub paths: Vec<String>,
+}
+
+#[test]
+fn test_default_expressions() -> anyhow::Result<()> {
+    let server: Server = raxb::de::from_str("<server/>")?;
+    assert_eq!(
+        server,
+        Server {
+            port: 8080,
+            host: "localhost".to_string(),
+            mode: Some("passive".to_string()),
+            paths: vec!["/".to_string()],
+        }
+    );
+    let server: Server = raxb::de::from_str(
+        r#"<server port="80"><host>example.org</host><mode>active</mode><path>/a</path><path>/b</path></server>"#,
+    )?;
+    assert_eq!(
+        server,
+        Server {
+            port: 80,
+            host: "example.org".to_string(),
+            mode: Some("active".to_string()),
+            paths: vec!["/a".to_string(), "/b".to_string()],
+        }
+    );
+    Ok(())
+}
+
+#[derive(Debug, XmlSerialize, XmlDeserialize, PartialEq)]
+#[xml(root = b"client", default)]
+pub struct Client {
+    #[xml(name = b"retries", ty = "attr")]
+    pub retries: u32,
+    #[xml(name = b"timeout", ty = "attr", default = "default_port")]
+    pub timeout: u16,
+    #[xml(name = b"name", ty = "child")]
+    pub name: String,
+    #[xml(name = b"proxy", ty = "child")]
+    pub proxy: Option<String>,
+    #[xml(name = b"server", ty = "child")]
+    pub servers: Vec<String>,
+    #[xml(skip)]
+    pub cache: Vec<u8>,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            timeout: 30,
+            name: "client".to_string(),
+            proxy: Some("proxy.local".to_string()),
+            servers: vec!["a".to_string()],
+            cache: vec![1, 2, 3],
+        }
+    }
+}
+
+#[test]
+fn test_container_default() -> anyhow::Result<()> {
+    let client: Client = raxb::de::from_str("<client/>")?;
+    assert_eq!(
+        client,
+        Client {
+            // the field default wins over the container default
+            timeout: 8080,
+            ..Default::default()
+        }
+    );
+    let client: Client = raxb::de::from_str(
+        r#"<client retries="5"><name>c1</name><server>b</server><server>c</server></client>"#,
+    )?;
+    assert_eq!(
+        client,
+        Client {
+            retries: 5,
+            timeout: 8080,
+            name: "c1".to_string(),
+            proxy: Some("proxy.local".to_string()),
+            servers: vec!["b".to_string(), "c".to_string()],
+            cache: vec![1, 2, 3],
+        }
+    );
+    Ok(())
+}
+
+#[derive(Debug, Default, XmlSerialize, XmlDeserialize, PartialEq)]
+pub struct Note {
+    #[xml(ty = "text", skip_serializing_if = "String::is_empty")]
+    pub text: String,
+}
+
+#[derive(Debug, Default, XmlSerialize, XmlDeserialize, PartialEq)]
+#[xml(root = b"job", default)]
+pub struct Job {
+    #[xml(name = b"priority", ty = "attr", skip_serializing_if = "is_zero")]
+    pub priority: u32,
+    #[xml(name = b"name", ty = "child", skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[xml(name = b"tag", ty = "child", skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[xml(name = b"note", ty = "child")]
+    pub note: Note,
+}
+
+#[test]
+fn test_skip_serializing_if() -> anyhow::Result<()> {
+    let job = Job::default();
+    let xml = raxb::ser::to_string(&job)?;
+    assert_eq!(xml, "<job><note/></job>");
+    assert_eq!(raxb::de::from_str::<Job>(&xml)?, job);
+
+    let job = Job {
+        priority: 2,
+        name: "build".to_string(),
+        tags: vec!["ci".to_string()],
+        note: Note {
+            text: "nightly".to_string(),
+        },
+    };
+    let xml = raxb::ser::to_string(&job)?;
+    assert_eq!(
+        xml,
+        r#"<job priority="2"><name>build</name><tag>ci</tag><note>nightly</note></job>"#
+    );
+    assert_eq!(raxb::de::from_str::<Job>(&xml)?, job);
+    Ok(())
+}
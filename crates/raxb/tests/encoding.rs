@@ -0,0 +1,112 @@
+#![cfg(feature = "encoding")]
+
+use raxb::{de::XmlDeserializeError, XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"customer")]
+pub struct Customer {
+    #[raxb(name = b"city", ty = "attr")]
+    pub city: String,
+    #[raxb(name = b"name", ty = "child")]
+    pub name: String,
+}
+
+fn latin1(s: &str) -> Vec<u8> {
+    s.chars().map(|c| c as u32 as u8).collect()
+}
+
+#[test]
+fn test_deserialize_iso_8859_1() -> anyhow::Result<()> {
+    let xml = latin1(
+        r#"<?xml version="1.0" encoding="ISO-8859-1"?><customer city="Köln"><name>Jörg Müller</name></customer>"#,
+    );
+    let customer: Customer = raxb::de::from_slice(&xml)?;
+    assert_eq!(customer.city, "Köln");
+    assert_eq!(customer.name, "Jörg Müller");
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_windows_1252() -> anyhow::Result<()> {
+    let mut xml =
+        b"<?xml version='1.0' encoding = 'windows-1252'?><customer city=\"Wien\"><name>".to_vec();
+    xml.push(0x80);
+    xml.extend_from_slice(b"</name></customer>");
+    let customer: Customer = raxb::de::from_slice(&xml)?;
+    assert_eq!(customer.name, "€");
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_from_reader() -> anyhow::Result<()> {
+    let xml = latin1(
+        r#"<?xml version="1.0" encoding="ISO-8859-1"?><customer city="Köln"><name>Jörg</name></customer>"#,
+    );
+    let customer: Customer = raxb::de::from_reader(std::io::BufReader::new(xml.as_slice()))?;
+    assert_eq!(customer.city, "Köln");
+    assert_eq!(customer.name, "Jörg");
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_utf8_declared_as_utf16() -> anyhow::Result<()> {
+    let xml = r#"<?xml version="1.0" encoding="UTF-16"?><customer city="Zürich"><name>Ärger</name></customer>"#;
+    let customer: Customer = raxb::de::from_slice(xml.as_bytes())?;
+    assert_eq!(customer.city, "Zürich");
+    assert_eq!(customer.name, "Ärger");
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_utf16_with_bom() -> anyhow::Result<()> {
+    let xml = r#"<?xml version="1.0" encoding="UTF-16"?><customer city="Zürich"><name>Ärger</name></customer>"#;
+    let mut bytes = vec![0xFE, 0xFF];
+    for unit in xml.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_be_bytes());
+    }
+    let customer: Customer = raxb::de::from_slice(&bytes)?;
+    assert_eq!(customer.city, "Zürich");
+    assert_eq!(customer.name, "Ärger");
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_unsupported_encoding() -> anyhow::Result<()> {
+    let xml = br#"<?xml version="1.0" encoding="x-unknown"?><customer city="a"><name>b</name></customer>"#;
+    let result = raxb::de::from_slice::<Customer>(xml);
+    assert!(
+        matches!(result, Err(XmlDeserializeError::UnsupportedEncoding(label)) if label == "x-unknown")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_serialize_iso_8859_1() -> anyhow::Result<()> {
+    let customer = Customer {
+        city: "Köln".to_string(),
+        name: "Jörg ✓".to_string(),
+    };
+    let bytes = raxb::ser::to_vec_with_encoding(&customer, "ISO-8859-1")?;
+    assert_eq!(
+        bytes,
+        latin1(
+            r#"<?xml version="1.0" encoding="ISO-8859-1" standalone="yes"?><customer city="Köln"><name>Jörg &#10003;</name></customer>"#
+        )
+    );
+    let de: Customer = raxb::de::from_slice(&bytes)?;
+    assert_eq!(de, customer);
+    Ok(())
+}
+
+#[test]
+fn test_serialize_utf16() -> anyhow::Result<()> {
+    let customer = Customer {
+        city: "Zürich".to_string(),
+        name: "Ärger".to_string(),
+    };
+    let bytes = raxb::ser::to_vec_with_encoding(&customer, "UTF-16")?;
+    assert_eq!(&bytes[..4], &[0xFF, 0xFE, b'<', 0]);
+    let de: Customer = raxb::de::from_slice(&bytes)?;
+    assert_eq!(de, customer);
+    Ok(())
+}
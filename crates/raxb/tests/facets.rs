@@ -0,0 +1,196 @@
+use raxb::{
+    de::XmlDeserializeError,
+    facet::{Facet, Violation},
+    ser::XmlSerializeError,
+    XmlDeserialize, XmlSerialize,
+};
+
+#[derive(Debug, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Amount {
+    #[raxb(name = b"currency", ty = "attr", enumeration("EUR", "USD"))]
+    pub currency: String,
+    #[raxb(ty = "text", min = 0.0, max = 1_000_000.0)]
+    pub content: f64,
+}
+
+#[derive(Debug, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"order", validate = "check_order")]
+pub struct Order {
+    #[raxb(name = b"priority", ty = "attr", min = 1, max = 9)]
+    pub priority: u8,
+    #[raxb(name = b"name", ty = "child", min_length = 1, max_length = 35)]
+    pub name: String,
+    #[raxb(name = b"note", ty = "child", max_length = 5)]
+    pub note: Option<String>,
+    #[raxb(name = b"line", ty = "child", min = -10, max = 10)]
+    pub lines: Vec<i32>,
+    #[raxb(name = b"amount", ty = "child")]
+    pub amount: Amount,
+    #[raxb(name = b"discount", ty = "child", default)]
+    pub discount: f64,
+}
+
+fn check_order(order: &Order) -> Result<(), String> {
+    if order.discount > order.amount.content {
+        return Err("discount is greater than the amount".to_string());
+    }
+    Ok(())
+}
+
+fn order() -> Order {
+    Order {
+        priority: 1,
+        name: "Order".to_string(),
+        note: Some("note".to_string()),
+        lines: vec![-10, 10],
+        amount: Amount {
+            currency: "EUR".to_string(),
+            content: 10.0,
+        },
+        discount: 2.0,
+    }
+}
+
+fn deserialize_error(xml: &str) -> Violation {
+    match raxb::de::from_str::<Order>(xml) {
+        Err(XmlDeserializeError::Constraint(violation)) => violation,
+        result => panic!("expected a constraint violation, got {result:?}"),
+    }
+}
+
+#[test]
+fn test_valid() -> anyhow::Result<()> {
+    let xml = raxb::ser::to_string(&order())?;
+    assert_eq!(
+        xml,
+        r#"<order priority="1"><name>Order</name><note>note</note><line>-10</line><line>10</line><amount currency="EUR">10</amount><discount>2</discount></order>"#
+    );
+    assert_eq!(raxb::de::from_str::<Order>(&xml)?, order());
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_violations() {
+    let violation = deserialize_error(
+        r#"<order priority="0"><name>a</name><amount currency="EUR">1</amount></order>"#,
+    );
+    assert_eq!(
+        violation,
+        Violation::new("priority", Facet::Min("1".to_string()))
+    );
+    assert_eq!(violation.to_string(), "value of 'priority' is less than 1");
+
+    let violation = deserialize_error(
+        r#"<order priority="1"><name></name><amount currency="EUR">1</amount></order>"#,
+    );
+    assert_eq!(violation, Violation::new("name", Facet::MinLength(1)));
+
+    let violation = deserialize_error(
+        r#"<order priority="1"><name>a</name><note>123456</note><amount currency="EUR">1</amount></order>"#,
+    );
+    assert_eq!(violation, Violation::new("note", Facet::MaxLength(5)));
+
+    let violation = deserialize_error(
+        r#"<order priority="1"><name>a</name><line>1</line><line>11</line><amount currency="EUR">1</amount></order>"#,
+    );
+    assert_eq!(
+        violation,
+        Violation::new("line", Facet::Max("10".to_string()))
+    );
+
+    let violation = deserialize_error(
+        r#"<order priority="1"><name>a</name><amount currency="CHF">1</amount></order>"#,
+    );
+    assert_eq!(
+        violation,
+        Violation::new(
+            "currency",
+            Facet::Enumeration(vec!["EUR".to_string(), "USD".to_string()])
+        )
+    );
+    assert_eq!(
+        violation.to_string(),
+        "value of 'currency' is not one of EUR, USD"
+    );
+
+    let violation = deserialize_error(
+        r#"<order priority="1"><name>a</name><amount currency="EUR">-1</amount></order>"#,
+    );
+    assert_eq!(
+        violation,
+        Violation::new("content", Facet::Min("0".to_string()))
+    );
+
+    let violation = deserialize_error(
+        r#"<order priority="1"><name>a</name><amount currency="EUR">1</amount><discount>5</discount></order>"#,
+    );
+    assert_eq!(
+        violation,
+        Violation::custom("discount is greater than the amount")
+    );
+}
+
+#[test]
+fn test_serialize_violations() {
+    let order = Order {
+        name: "x".repeat(36),
+        ..order()
+    };
+    assert!(matches!(
+        raxb::ser::to_string(&order),
+        Err(XmlSerializeError::Constraint(violation)) if violation == Violation::new("name", Facet::MaxLength(35))
+    ));
+
+    let order = Order {
+        discount: 20.0,
+        ..self::order()
+    };
+    let err = raxb::ser::to_string(&order).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "constraint violated: discount is greater than the amount"
+    );
+}
+
+#[cfg(feature = "regex")]
+mod pattern {
+    use super::*;
+
+    #[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+    #[raxb(root = b"account")]
+    pub struct Account {
+        #[raxb(
+            name = b"iban",
+            ty = "attr",
+            pattern = "[A-Z]{2}[0-9]{2}[A-Z0-9]{1,30}"
+        )]
+        pub iban: String,
+        #[raxb(name = b"code", ty = "child", pattern = r"\d{3}")]
+        pub codes: Vec<u32>,
+    }
+
+    #[test]
+    fn test_pattern() -> anyhow::Result<()> {
+        let xml = r#"<account iban="DE89370400440532013000"><code>123</code></account>"#;
+        let account: Account = raxb::de::from_str(xml)?;
+        assert_eq!(raxb::ser::to_string(&account)?, xml);
+
+        // the pattern has to match the whole value
+        let xml = r#"<account iban="xDE89370400440532013000"/>"#;
+        assert!(matches!(
+            raxb::de::from_str::<Account>(xml),
+            Err(XmlDeserializeError::Constraint(violation)) if violation.field.as_deref() == Some("iban")
+        ));
+
+        let account = Account {
+            iban: "DE89370400440532013000".to_string(),
+            codes: vec![123, 1234],
+        };
+        let err = raxb::ser::to_string(&account).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r"constraint violated: value of 'code' does not match the pattern '\d{3}'"
+        );
+        Ok(())
+    }
+}
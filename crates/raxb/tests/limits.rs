@@ -0,0 +1,253 @@
+use raxb::{
+    de::{DeserializeLimits, DeserializeOptions, Limit, XmlDeserializeError},
+    XmlDeserialize,
+};
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+pub struct Node {
+    #[raxb(name = b"name", ty = "attr")]
+    pub name: Option<String>,
+    #[raxb(name = b"node", ty = "child")]
+    pub children: Vec<Node>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+#[raxb(root = b"tree")]
+pub struct Tree {
+    #[raxb(name = b"node", ty = "child")]
+    pub nodes: Vec<Node>,
+    #[raxb(name = b"comment", ty = "child")]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+pub enum Expr {
+    #[raxb(name = b"num")]
+    Num(u32),
+    #[raxb(name = b"neg")]
+    Neg(Box<Expr>),
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+#[raxb(root = b"calc")]
+pub struct Calc {
+    #[raxb(name = b"expr", ty = "child")]
+    pub expr: Expr,
+}
+
+fn options(limits: DeserializeLimits) -> DeserializeOptions {
+    DeserializeOptions {
+        limits,
+        ..Default::default()
+    }
+}
+
+fn nested(depth: usize) -> String {
+    format!(
+        "<tree>{}{}</tree>",
+        "<node>".repeat(depth),
+        "</node>".repeat(depth)
+    )
+}
+
+#[test]
+fn test_unlimited_by_default() -> anyhow::Result<()> {
+    let tree: Tree = raxb::de::from_str(&nested(200))?;
+    assert_eq!(tree.nodes.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_max_depth() -> anyhow::Result<()> {
+    let limits = DeserializeLimits {
+        max_depth: Some(10),
+        ..Default::default()
+    };
+    let tree: Tree = raxb::de::from_str_with_options(&nested(9), &options(limits))?;
+    assert_eq!(tree.nodes.len(), 1);
+    let err = raxb::de::from_str_with_options::<Tree>(&nested(10), &options(limits)).unwrap_err();
+    assert!(matches!(
+        err,
+        XmlDeserializeError::LimitExceeded(Limit::Depth(10))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_max_depth_of_enums() -> anyhow::Result<()> {
+    let limits = DeserializeLimits {
+        max_depth: Some(10),
+        ..Default::default()
+    };
+    let calc = |depth: usize| {
+        format!(
+            "<calc><expr>{}<num>1</num>{}</expr></calc>",
+            "<neg>".repeat(depth),
+            "</neg>".repeat(depth)
+        )
+    };
+    let result: Calc = raxb::de::from_str_with_options(&calc(8), &options(limits))?;
+    assert!(matches!(result.expr, Expr::Neg(_)));
+    let err = raxb::de::from_str_with_options::<Calc>(&calc(9), &options(limits)).unwrap_err();
+    assert!(matches!(
+        err,
+        XmlDeserializeError::LimitExceeded(Limit::Depth(10))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_limits_of_skipped_elements() -> anyhow::Result<()> {
+    let limits = DeserializeLimits {
+        max_depth: Some(10),
+        max_elements: Some(5),
+        ..Default::default()
+    };
+    let unknown = |depth: usize| {
+        format!(
+            "<tree>{}{}</tree>",
+            "<unknown>".repeat(depth),
+            "</unknown>".repeat(depth)
+        )
+    };
+    let tree: Tree = raxb::de::from_str_with_options(&unknown(4), &options(limits))?;
+    assert!(tree.nodes.is_empty());
+    let err = raxb::de::from_str_with_options::<Tree>(&unknown(5), &options(limits)).unwrap_err();
+    assert!(matches!(
+        err,
+        XmlDeserializeError::LimitExceeded(Limit::Elements(5))
+    ));
+    let limits = DeserializeLimits {
+        max_depth: Some(10),
+        ..Default::default()
+    };
+    raxb::de::from_str_with_options::<Tree>(&unknown(9), &options(limits))?;
+    let err = raxb::de::from_str_with_options::<Tree>(&unknown(10), &options(limits)).unwrap_err();
+    assert!(matches!(
+        err,
+        XmlDeserializeError::LimitExceeded(Limit::Depth(10))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_truncated_skipped_element() {
+    for xml in [
+        "<tree><comment>a</comment><unknown><deep>",
+        "<tree><comment>a</comment><unknown>",
+        "<tree><comment>a<b>",
+    ] {
+        let result = raxb::de::from_str::<Tree>(xml);
+        assert!(
+            matches!(result, Err(XmlDeserializeError::UnexpectedEof)),
+            "{xml}: {result:?}"
+        );
+    }
+}
+
+#[test]
+fn test_max_elements() -> anyhow::Result<()> {
+    let limits = DeserializeLimits {
+        max_elements: Some(5),
+        ..Default::default()
+    };
+    let xml = "<tree><node/><node/><node/><node/></tree>";
+    let tree: Tree = raxb::de::from_str_with_options(xml, &options(limits))?;
+    assert_eq!(tree.nodes.len(), 4);
+    let xml = "<tree><node/><node/><node/><node/><node/></tree>";
+    let err = raxb::de::from_str_with_options::<Tree>(xml, &options(limits)).unwrap_err();
+    assert!(matches!(
+        err,
+        XmlDeserializeError::LimitExceeded(Limit::Elements(5))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_max_text_length() -> anyhow::Result<()> {
+    let limits = DeserializeLimits {
+        max_text_length: Some(8),
+        ..Default::default()
+    };
+    let tree: Tree = raxb::de::from_str_with_options(
+        "<tree><comment>12345678</comment></tree>",
+        &options(limits),
+    )?;
+    assert_eq!(tree.comment.as_deref(), Some("12345678"));
+    let err = raxb::de::from_str_with_options::<Tree>(
+        "<tree><comment>123456789</comment></tree>",
+        &options(limits),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        XmlDeserializeError::LimitExceeded(Limit::TextLength(8))
+    ));
+    let err = raxb::de::from_str_with_options::<Tree>(
+        r#"<tree><node name="123456789"/></tree>"#,
+        &options(limits),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        XmlDeserializeError::LimitExceeded(Limit::TextLength(8))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_max_vec_length() -> anyhow::Result<()> {
+    let limits = DeserializeLimits {
+        max_vec_length: Some(3),
+        ..Default::default()
+    };
+    let xml = "<tree><node/><node/><node/></tree>";
+    let tree: Tree = raxb::de::from_str_with_options(xml, &options(limits))?;
+    assert_eq!(tree.nodes.len(), 3);
+    let xml = "<tree><node/><node/><node/><node/></tree>";
+    let err = raxb::de::from_str_with_options::<Tree>(xml, &options(limits)).unwrap_err();
+    assert!(matches!(
+        err,
+        XmlDeserializeError::LimitExceeded(Limit::VecLength(3))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_max_input_bytes() -> anyhow::Result<()> {
+    let xml = "<tree><node/><node/></tree>";
+    let limits = DeserializeLimits {
+        max_input_bytes: Some(xml.len()),
+        ..Default::default()
+    };
+    let tree: Tree = raxb::de::from_reader_with_options(xml.as_bytes(), &options(limits))?;
+    assert_eq!(tree.nodes.len(), 2);
+    let limits = DeserializeLimits {
+        max_input_bytes: Some(xml.len() - 1),
+        ..Default::default()
+    };
+    let err = raxb::de::from_str_with_options::<Tree>(xml, &options(limits)).unwrap_err();
+    assert!(matches!(
+        err,
+        XmlDeserializeError::LimitExceeded(Limit::InputBytes(_))
+    ));
+    let err = raxb::de::from_reader_with_options::<_, Tree>(xml.as_bytes(), &options(limits))
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        XmlDeserializeError::LimitExceeded(Limit::InputBytes(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_safe_limits() -> anyhow::Result<()> {
+    let err =
+        raxb::de::from_str_with_options::<Tree>(&nested(1000), &options(DeserializeLimits::safe()))
+            .unwrap_err();
+    assert!(matches!(
+        err,
+        XmlDeserializeError::LimitExceeded(Limit::Depth(64))
+    ));
+    Ok(())
+}
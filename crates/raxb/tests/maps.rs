@@ -0,0 +1,230 @@
+use std::collections::{BTreeMap, HashMap};
+
+use raxb::{de::XmlDeserializeError, ser::XmlSerializeError, XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"config")]
+pub struct Config {
+    #[raxb(name = b"param", key = b"name", ty = "child")]
+    pub params: BTreeMap<String, String>,
+    #[raxb(name = b"limit", key = b"id", ty = "child")]
+    pub limits: HashMap<u32, u64>,
+    #[raxb(name = b"server", key = b"id", ty = "child")]
+    pub servers: BTreeMap<String, Server>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Server {
+    #[raxb(name = b"host", ty = "child")]
+    pub host: String,
+    #[raxb(name = b"port", ty = "attr")]
+    pub port: u16,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"prices")]
+pub struct Prices {
+    #[raxb(name = b"date", ty = "attr")]
+    pub date: String,
+    #[raxb(key_by_name, ty = "child")]
+    pub amounts: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"document")]
+pub struct Document {
+    #[raxb(name = b"title", ty = "child")]
+    pub title: String,
+    #[raxb(key_by_name, ty = "child")]
+    pub sections: BTreeMap<String, Section>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Section {
+    #[raxb(name = b"line", ty = "child")]
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(transparent)]
+pub struct Seconds(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(simple)]
+pub enum Level {
+    #[raxb(value = "low")]
+    Low,
+    #[raxb(value = "high")]
+    High,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub enum Backend {
+    #[raxb(name = b"memory")]
+    Memory,
+    #[raxb(name = b"file")]
+    File(String),
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"settings")]
+pub struct Settings {
+    #[raxb(name = b"param", key = b"name", ty = "child")]
+    pub timeouts: BTreeMap<String, Seconds>,
+    #[raxb(name = b"level", key = b"name", ty = "child")]
+    pub levels: BTreeMap<String, Level>,
+    #[raxb(name = b"store", key = b"name", ty = "child")]
+    pub stores: BTreeMap<String, Backend>,
+}
+
+fn config() -> Config {
+    Config {
+        params: BTreeMap::from([
+            ("retries".to_string(), "3".to_string()),
+            ("timeout".to_string(), "30".to_string()),
+        ]),
+        limits: HashMap::from([(1, 100)]),
+        servers: BTreeMap::from([(
+            "main".to_string(),
+            Server {
+                host: "example.org".to_string(),
+                port: 443,
+            },
+        )]),
+    }
+}
+
+#[test]
+fn test_map_keyed_by_attribute() -> anyhow::Result<()> {
+    let xml = raxb::ser::to_string(&config())?;
+    assert_eq!(
+        xml,
+        r#"<config><param name="retries">3</param><param name="timeout">30</param><limit id="1">100</limit><server port="443" id="main"><host>example.org</host></server></config>"#
+    );
+    assert_eq!(raxb::de::from_str::<Config>(&xml)?, config());
+    Ok(())
+}
+
+#[test]
+fn test_map_missing_key() {
+    let result = raxb::de::from_str::<Config>(r#"<config><param>30</param></config>"#);
+    assert!(
+        matches!(result, Err(XmlDeserializeError::MissingAttribute(ref name)) if name.0 == b"name"),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn test_map_keyed_by_attribute_with_custom_values() -> anyhow::Result<()> {
+    let xml = r#"<settings><param name="timeout">30</param><level name="log">high</level><store name="cache"><memory/></store><store name="data"><file>db</file></store></settings>"#;
+    let settings: Settings = raxb::de::from_str(xml)?;
+    assert_eq!(
+        settings,
+        Settings {
+            timeouts: BTreeMap::from([("timeout".to_string(), Seconds(30))]),
+            levels: BTreeMap::from([("log".to_string(), Level::High)]),
+            stores: BTreeMap::from([
+                ("cache".to_string(), Backend::Memory),
+                ("data".to_string(), Backend::File("db".to_string())),
+            ]),
+        }
+    );
+    assert_eq!(raxb::ser::to_string(&settings)?, xml);
+    Ok(())
+}
+
+#[test]
+fn test_map_keyed_by_name() -> anyhow::Result<()> {
+    let prices = Prices {
+        date: "2024-01-01".to_string(),
+        amounts: BTreeMap::from([("EUR".to_string(), 10.0), ("USD".to_string(), 12.5)]),
+    };
+    let xml = raxb::ser::to_string(&prices)?;
+    assert_eq!(
+        xml,
+        r#"<prices date="2024-01-01"><EUR>10</EUR><USD>12.5</USD></prices>"#
+    );
+    assert_eq!(raxb::de::from_str::<Prices>(&xml)?, prices);
+    Ok(())
+}
+
+#[test]
+fn test_map_keyed_by_name_of_the_struct() -> anyhow::Result<()> {
+    let xml = r#"<prices date="2024-01-01"><eur>1.5</eur><prices>2</prices><usd>3</usd></prices>"#;
+    let prices: Prices = raxb::de::from_str(xml)?;
+    assert_eq!(
+        prices.amounts,
+        BTreeMap::from([
+            ("eur".to_string(), 1.5),
+            ("prices".to_string(), 2.0),
+            ("usd".to_string(), 3.0)
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_map_keyed_by_invalid_name() {
+    for key in ["a b", "<x", "1st", ""] {
+        let prices = Prices {
+            date: "2024-01-01".to_string(),
+            amounts: BTreeMap::from([(key.to_string(), 1.0)]),
+        };
+        let result = raxb::ser::to_string(&prices);
+        assert!(
+            matches!(result, Err(XmlSerializeError::InvalidName(ref name)) if name == key),
+            "{result:?}"
+        );
+    }
+}
+
+#[test]
+fn test_map_keyed_by_name_with_struct_values() -> anyhow::Result<()> {
+    let xml = r#"<document><intro><line>a</line><line>b</line></intro><title>Doc</title><body><line>c</line></body><empty/></document>"#;
+    let document: Document = raxb::de::from_str(xml)?;
+    assert_eq!(
+        document,
+        Document {
+            title: "Doc".to_string(),
+            sections: BTreeMap::from([
+                (
+                    "body".to_string(),
+                    Section {
+                        lines: vec!["c".to_string()]
+                    }
+                ),
+                ("empty".to_string(), Section { lines: vec![] }),
+                (
+                    "intro".to_string(),
+                    Section {
+                        lines: vec!["a".to_string(), "b".to_string()]
+                    }
+                ),
+            ]),
+        }
+    );
+    assert_eq!(
+        raxb::ser::to_string(&document)?,
+        r#"<document><title>Doc</title><body><line>c</line></body><empty></empty><intro><line>a</line><line>b</line></intro></document>"#
+    );
+    Ok(())
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn test_index_map() -> anyhow::Result<()> {
+    use raxb::indexmap::IndexMap;
+
+    #[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+    #[raxb(root = b"config")]
+    pub struct OrderedConfig {
+        #[raxb(name = b"param", key = b"name", ty = "child")]
+        pub params: IndexMap<String, String>,
+    }
+
+    let xml = r#"<config><param name="b">2</param><param name="a">1</param></config>"#;
+    let config: OrderedConfig = raxb::de::from_str(xml)?;
+    assert_eq!(config.params.keys().collect::<Vec<_>>(), ["b", "a"]);
+    assert_eq!(raxb::ser::to_string(&config)?, xml);
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use raxb::{XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, Clone, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(transparent)]
+pub struct OrderId(String);
+
+#[derive(Debug, Clone, Copy, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(transparent)]
+pub struct Quantity(u32);
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"address")]
+pub struct Address {
+    #[raxb(name = b"city", ty = "child")]
+    pub city: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(transparent)]
+pub struct ShippingAddress(Address);
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"urgent")]
+pub struct Urgent;
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Reference {
+    #[raxb(ty = "text")]
+    pub id: OrderId,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"order")]
+pub struct Order {
+    #[raxb(name = b"id", ty = "attr")]
+    pub id: OrderId,
+    #[raxb(name = b"parent", ty = "attr")]
+    pub parent: Option<OrderId>,
+    #[raxb(name = b"quantity", ty = "child")]
+    pub quantity: Quantity,
+    #[raxb(name = b"related", ty = "child")]
+    pub related: Vec<OrderId>,
+    #[raxb(name = b"reference", ty = "child")]
+    pub reference: Reference,
+    #[raxb(name = b"shipping", ty = "child")]
+    pub shipping: ShippingAddress,
+    #[raxb(name = b"urgent", ty = "child")]
+    pub urgent: Option<Urgent>,
+}
+
+#[test]
+fn test_newtype() -> anyhow::Result<()> {
+    let order = Order {
+        id: OrderId("A-1".to_string()),
+        parent: Some(OrderId("A-0".to_string())),
+        quantity: Quantity(3),
+        related: vec![OrderId("B-1".to_string()), OrderId("B-2".to_string())],
+        reference: Reference {
+            id: OrderId("R-1".to_string()),
+        },
+        shipping: ShippingAddress(Address {
+            city: "Berlin".to_string(),
+        }),
+        urgent: Some(Urgent),
+    };
+    let xml = raxb::ser::to_string(&order)?;
+    assert_eq!(
+        xml,
+        r#"<order id="A-1" parent="A-0"><quantity>3</quantity><related>B-1</related><related>B-2</related><reference>R-1</reference><shipping><city>Berlin</city></shipping><urgent/></order>"#
+    );
+    assert_eq!(raxb::de::from_str::<Order>(&xml)?, order);
+
+    let xml = r#"<order id="A-2"><quantity>1</quantity><reference>R-2</reference><shipping><city>Bonn</city></shipping></order>"#;
+    let order: Order = raxb::de::from_str(xml)?;
+    assert_eq!(order.parent, None);
+    assert_eq!(order.urgent, None);
+    Ok(())
+}
+
+#[test]
+fn test_newtype_root() -> anyhow::Result<()> {
+    let xml = r#"<address><city>Berlin</city></address>"#;
+    let shipping: ShippingAddress = raxb::de::from_str(xml)?;
+    assert_eq!(raxb::ser::to_string(&shipping)?, xml);
+    assert_eq!("7".parse::<Quantity>()?, Quantity(7));
+    assert_eq!(OrderId("A-1".to_string()).to_string(), "A-1");
+    Ok(())
+}
+
+#[test]
+fn test_unit_struct() -> anyhow::Result<()> {
+    assert_eq!(raxb::ser::to_string(&Urgent)?, "<urgent/>");
+    assert_eq!(raxb::de::from_str::<Urgent>("<urgent/>")?, Urgent);
+    assert_eq!(
+        raxb::de::from_str::<Urgent>("<urgent><note>ignored</note></urgent>")?,
+        Urgent
+    );
+    Ok(())
+}
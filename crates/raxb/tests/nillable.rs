@@ -0,0 +1,77 @@
+use raxb::{value::Nillable, XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Amount {
+    #[raxb(name = b"currency", ty = "attr")]
+    pub currency: String,
+    #[raxb(ty = "text")]
+    pub content: f64,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"invoice")]
+pub struct Invoice {
+    #[raxb(name = b"total", ty = "child")]
+    pub total: Nillable<i32>,
+    #[raxb(name = b"note", ty = "child")]
+    pub note: Option<Nillable<String>>,
+    #[raxb(name = b"amount", ty = "child")]
+    pub amount: Option<Nillable<Amount>>,
+}
+
+#[test]
+fn test_deserialize_nil_elements() -> anyhow::Result<()> {
+    let xml = r#"<invoice xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+        <total xsi:nil="true"/>
+        <note xsi:nil="true"></note>
+        <amount xsi:nil="1" currency="EUR"/>
+    </invoice>"#;
+    let invoice: Invoice = raxb::de::from_str(xml)?;
+    assert_eq!(
+        invoice,
+        Invoice {
+            total: Nillable::Nil,
+            note: Some(Nillable::Nil),
+            amount: Some(Nillable::Nil),
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_nillable_values() -> anyhow::Result<()> {
+    let xml = r#"<invoice xmlns:i="http://www.w3.org/2001/XMLSchema-instance">
+        <total i:nil="false">42</total>
+        <amount currency="EUR">9.5</amount>
+    </invoice>"#;
+    let invoice: Invoice = raxb::de::from_str(xml)?;
+    assert_eq!(
+        invoice,
+        Invoice {
+            total: Nillable::Value(42),
+            note: None,
+            amount: Some(Nillable::Value(Amount {
+                currency: "EUR".to_string(),
+                content: 9.5,
+            })),
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_serialize_nillable() -> anyhow::Result<()> {
+    let invoice = Invoice {
+        total: Nillable::Nil,
+        note: Some(Nillable::Value("a & b".to_string())),
+        amount: None,
+    };
+    let xml = raxb::ser::to_string(&invoice)?;
+    assert_eq!(
+        xml,
+        r#"<invoice><total xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:nil="true"/><note>a &amp; b</note></invoice>"#
+    );
+    let de: Invoice = raxb::de::from_str(&xml)?;
+    assert_eq!(de, invoice);
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use raxb::{value::RawXml, XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, Default, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Card {
+    #[raxb(name = b"number", ty = "attr")]
+    pub number: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub enum Payment {
+    #[raxb(name = b"card")]
+    Card(Card),
+    #[raxb(name = b"cash")]
+    Cash,
+    #[raxb(other)]
+    Other(RawXml),
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub enum Delivery {
+    #[raxb(name = b"pickup")]
+    Pickup,
+    #[raxb(other)]
+    Other(String),
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(simple)]
+pub enum Status {
+    #[raxb(value = "ACTIVE")]
+    Active,
+    #[raxb(other)]
+    Other(String),
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"order")]
+pub struct Order {
+    #[raxb(name = b"status", ty = "attr")]
+    pub status: Status,
+    #[raxb(name = b"payment", ty = "child")]
+    pub payment: Payment,
+    #[raxb(name = b"delivery", ty = "child")]
+    pub delivery: Delivery,
+}
+
+#[test]
+fn test_known_variants() -> anyhow::Result<()> {
+    let xml = r#"<order status="ACTIVE"><payment><card number="42"/></payment><delivery><pickup/></delivery></order>"#;
+    let order: Order = raxb::de::from_str(xml)?;
+    assert_eq!(
+        order,
+        Order {
+            status: Status::Active,
+            payment: Payment::Card(Card {
+                number: "42".to_string()
+            }),
+            delivery: Delivery::Pickup,
+        }
+    );
+    assert_eq!(raxb::ser::to_string(&order)?, xml);
+    Ok(())
+}
+
+#[test]
+fn test_other_variants() -> anyhow::Result<()> {
+    let xml = r#"<order status="BLOCKED"><payment><wallet provider="x"><id>7</id></wallet></payment><delivery><drone speed="fast"><eta>5</eta></drone></delivery></order>"#;
+    let order: Order = raxb::de::from_str(xml)?;
+    assert_eq!(
+        order,
+        Order {
+            status: Status::Other("BLOCKED".to_string()),
+            payment: Payment::Other(RawXml::new(r#"<wallet provider="x"><id>7</id></wallet>"#)),
+            delivery: Delivery::Other("drone".to_string()),
+        }
+    );
+    // the name of an element is all that is kept of it in `Other(String)`
+    assert_eq!(
+        raxb::ser::to_string(&order)?,
+        r#"<order status="BLOCKED"><payment><wallet provider="x"><id>7</id></wallet></payment><delivery><drone/></delivery></order>"#
+    );
+
+    let xml = r#"<order status="ACTIVE"><payment><voucher/></payment><delivery><drone/></delivery></order>"#;
+    let order: Order = raxb::de::from_str(xml)?;
+    assert_eq!(order.payment, Payment::Other(RawXml::new("<voucher/>")));
+    assert_eq!(raxb::ser::to_string(&order)?, xml);
+    Ok(())
+}
+
+#[test]
+fn test_other_namespaces() -> anyhow::Result<()> {
+    let xml = r#"<order xmlns:x="urn:x" status="ACTIVE"><payment><x:cash/></payment><delivery><x:drone><pickup/></x:drone></delivery></order>"#;
+    let order: Order = raxb::de::from_str(xml)?;
+    let Payment::Other(raw) = &order.payment else {
+        panic!("expected other payment, got {:?}", order.payment);
+    };
+    assert_eq!(raw.as_str(), "<x:cash/>");
+    assert_eq!(raw.namespaces(), &[("x".to_string(), "urn:x".to_string())]);
+    // the content of an element of another namespace is not taken for a variant
+    assert_eq!(order.delivery, Delivery::Other("drone".to_string()));
+    assert_eq!(
+        raxb::ser::to_string(&order)?,
+        r#"<order status="ACTIVE"><payment><x:cash xmlns:x="urn:x"/></payment><delivery><drone/></delivery></order>"#
+    );
+    Ok(())
+}
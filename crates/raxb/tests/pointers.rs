@@ -0,0 +1,96 @@
+use std::{rc::Rc, sync::Arc};
+
+use raxb::{XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"node")]
+pub struct Node {
+    #[raxb(name = b"name", ty = "attr")]
+    pub name: String,
+    #[raxb(name = b"node", ty = "child")]
+    pub next: Option<Box<Node>>,
+    #[raxb(name = b"leaf", ty = "child")]
+    pub leaves: Vec<Box<Leaf>>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Leaf {
+    #[raxb(ty = "text")]
+    pub content: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub enum Shape {
+    #[raxb(name = b"circle")]
+    Circle(Box<Leaf>),
+    #[raxb(name = b"group")]
+    Group(Box<Group>),
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Group {
+    #[raxb(name = b"item", ty = "child")]
+    pub items: Vec<Shape>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"catalog")]
+pub struct Catalog {
+    #[raxb(name = b"owner", ty = "child")]
+    pub owner: Rc<Leaf>,
+    #[raxb(name = b"shared", ty = "child")]
+    pub shared: Vec<Arc<Leaf>>,
+    #[raxb(name = b"shape", ty = "child")]
+    pub shape: Shape,
+}
+
+fn leaf(content: &str) -> Leaf {
+    Leaf {
+        content: content.to_string(),
+    }
+}
+
+#[test]
+fn test_recursive_box() -> anyhow::Result<()> {
+    let xml = r#"<node name="a"><node name="b"><node name="c"/><leaf>1</leaf></node><leaf>2</leaf><leaf>3</leaf></node>"#;
+    let node: Node = raxb::de::from_str(xml)?;
+    assert_eq!(
+        node,
+        Node {
+            name: "a".to_string(),
+            next: Some(Box::new(Node {
+                name: "b".to_string(),
+                next: Some(Box::new(Node {
+                    name: "c".to_string(),
+                    next: None,
+                    leaves: vec![],
+                })),
+                leaves: vec![Box::new(leaf("1"))],
+            })),
+            leaves: vec![Box::new(leaf("2")), Box::new(leaf("3"))],
+        }
+    );
+    assert_eq!(
+        raxb::ser::to_string(&node)?,
+        xml.replace(r#"<node name="c"/>"#, r#"<node name="c"></node>"#)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rc_arc_and_boxed_variants() -> anyhow::Result<()> {
+    let catalog = Catalog {
+        owner: Rc::new(leaf("me")),
+        shared: vec![Arc::new(leaf("x")), Arc::new(leaf("y"))],
+        shape: Shape::Group(Box::new(Group {
+            items: vec![Shape::Circle(Box::new(leaf("r=1")))],
+        })),
+    };
+    let xml = raxb::ser::to_string(&catalog)?;
+    assert_eq!(
+        xml,
+        r#"<catalog><owner>me</owner><shared>x</shared><shared>y</shared><shape><group><item><circle>r=1</circle></item></group></shape></catalog>"#
+    );
+    assert_eq!(raxb::de::from_str::<Catalog>(&xml)?, catalog);
+    Ok(())
+}
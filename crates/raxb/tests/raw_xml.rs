@@ -0,0 +1,222 @@
+use raxb::{
+    de::XmlDeserializeError,
+    ser::XmlSerialize as _,
+    value::{ConstStr, RawXml},
+    XmlDeserialize, XmlSerialize,
+};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"Envelope", tns(b"env", b"urn:env"))]
+pub struct Envelope {
+    #[raxb(default, ns = b"xmlns", name = b"env", ty = "attr", value = "urn:env")]
+    _xmlns: ConstStr,
+    #[raxb(ns = b"env", name = b"Header", ty = "child")]
+    pub header: String,
+    #[raxb(ns = b"p", name = b"order", ty = "child")]
+    pub payload: RawXml,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"order", tns(b"p", b"urn:payload"))]
+pub struct Order {
+    #[raxb(name = b"id", ty = "attr")]
+    pub id: String,
+    #[raxb(ns = b"p", name = b"item", ty = "child")]
+    pub items: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"batch")]
+pub struct Batch {
+    #[raxb(name = b"doc", ty = "child")]
+    pub docs: Vec<RawXml>,
+    #[raxb(name = b"extra", ty = "child")]
+    pub extra: Option<RawXml>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+#[raxb(tag = "xsi:type", tns(b"e", b"urn:ext"))]
+pub enum Entry {
+    #[raxb(name = b"Raw")]
+    Raw(RawXml),
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+#[raxb(root = b"list", tns(b"e", b"urn:ext"))]
+pub struct List {
+    #[raxb(ns = b"e", name = b"entry", ty = "child")]
+    pub entries: Vec<Entry>,
+}
+
+const PAYLOAD: &str = r#"<p:order id='1'  note="a &amp; b">
+    <!-- keep -->
+    <p:item>x &lt; y</p:item>
+    <p:item><![CDATA[<raw>]]></p:item><?pi data?>
+  </p:order>"#;
+
+#[test]
+fn test_deserialize_exact_bytes() -> anyhow::Result<()> {
+    let xml = format!(
+        r#"<env:Envelope xmlns:env="urn:env" xmlns:p="urn:payload">
+  <env:Header>h</env:Header>
+  {PAYLOAD}
+</env:Envelope>"#
+    );
+    let envelope: Envelope = raxb::de::from_str(&xml)?;
+    assert_eq!(envelope.header, "h");
+    assert_eq!(envelope.payload.as_str(), PAYLOAD);
+    let namespaces = envelope.payload.namespaces();
+    assert!(namespaces.contains(&("p".to_string(), "urn:payload".to_string())));
+    assert!(namespaces.contains(&("env".to_string(), "urn:env".to_string())));
+    Ok(())
+}
+
+#[test]
+fn test_truncated_payload() {
+    for xml in [r#"<batch><doc><a>1</a>"#, r#"<batch><doc><a>1</a><b>"#] {
+        let result = raxb::de::from_str::<Batch>(xml);
+        assert!(
+            matches!(result, Err(XmlDeserializeError::UnexpectedEof)),
+            "{xml}: {result:?}"
+        );
+    }
+}
+
+#[test]
+fn test_into_typed() -> anyhow::Result<()> {
+    let xml = r#"<env:Envelope xmlns:env="urn:env" xmlns:p="urn:payload"><env:Header>h</env:Header><p:order id="7">
+        <p:item>a</p:item>
+        <p:item>b</p:item>
+    </p:order></env:Envelope>"#;
+    let envelope: Envelope = raxb::de::from_str(xml)?;
+    let standalone = envelope.payload.to_standalone();
+    assert!(standalone.starts_with("<p:order xmlns:"));
+    assert!(standalone.contains(r#" xmlns:p="urn:payload""#));
+    assert!(standalone.ends_with(
+        r#" id="7">
+        <p:item>a</p:item>
+        <p:item>b</p:item>
+    </p:order>"#
+    ));
+    let order: Order = envelope.payload.into_typed()?;
+    assert_eq!(
+        order,
+        Order {
+            id: "7".to_string(),
+            items: vec!["a".to_string(), "b".to_string()],
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_own_namespace_declarations() -> anyhow::Result<()> {
+    let xml = r#"<env:Envelope xmlns:env="urn:env"><env:Header/><p:order xmlns:p="urn:payload" id="1"/></env:Envelope>"#;
+    let envelope: Envelope = raxb::de::from_str(xml)?;
+    assert_eq!(
+        envelope.payload.as_str(),
+        r#"<p:order xmlns:p="urn:payload" id="1"/>"#
+    );
+    assert_eq!(
+        envelope.payload.namespaces(),
+        &[("env".to_string(), "urn:env".to_string())]
+    );
+    let order: Order = envelope.payload.into_typed()?;
+    assert_eq!(order.id, "1");
+    assert!(order.items.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_serialize_verbatim() -> anyhow::Result<()> {
+    let payload =
+        r#"<p:order xmlns:p="urn:payload" id='1'><!-- keep --><p:item>x &lt; y</p:item></p:order>"#;
+    let xml = raxb::ser::to_string(&Envelope {
+        _xmlns: ConstStr::default(),
+        header: "h".to_string(),
+        payload: RawXml::new(payload),
+    })?;
+    assert_eq!(
+        xml,
+        format!(
+            r#"<env:Envelope xmlns:env="urn:env"><env:Header>h</env:Header>{payload}</env:Envelope>"#
+        )
+    );
+    let envelope: Envelope = raxb::de::from_str(&xml)?;
+    assert_eq!(envelope.payload.as_str(), payload);
+    Ok(())
+}
+
+#[test]
+fn test_serialize_inherited_namespaces() -> anyhow::Result<()> {
+    let xml = r#"<env:Envelope xmlns:env="urn:env"><env:Header>h</env:Header><p:order xmlns:p="urn:payload" id="1"><p:item>a</p:item></p:order></env:Envelope>"#;
+    let envelope: Envelope = raxb::de::from_str(xml)?;
+    let batch = Batch {
+        docs: vec![envelope.payload],
+        extra: None,
+    };
+    // the inherited `env` prefix is declared, although the payload does not use it
+    assert_eq!(
+        raxb::ser::to_string(&batch)?,
+        r#"<batch><p:order xmlns:env="urn:env" xmlns:p="urn:payload" id="1"><p:item>a</p:item></p:order></batch>"#
+    );
+    let xml = r#"<env:Envelope xmlns:env="urn:env" xmlns:p="urn:payload"><env:Header>h</env:Header><p:order id="1"/></env:Envelope>"#;
+    let envelope: Envelope = raxb::de::from_str(xml)?;
+    let serialized = raxb::ser::to_string(&Batch {
+        docs: vec![envelope.payload],
+        extra: None,
+    })?;
+    assert!(serialized.contains(r#" xmlns:p="urn:payload""#));
+    let mut reader = raxb::quick_xml::NsReader::from_str(&serialized);
+    reader.read_resolved_event()?;
+    let (ns, _) = reader.read_resolved_event()?;
+    assert_eq!(
+        ns,
+        raxb::quick_xml::name::ResolveResult::Bound(raxb::quick_xml::name::Namespace(
+            b"urn:payload"
+        ))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_xsi_type_variant_keeps_prefix() -> anyhow::Result<()> {
+    let xml = r#"<e:list xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:e="urn:ext"><e:entry xsi:type="e:Raw" a="1"><e:v/></e:entry></e:list>"#;
+    let list: List = raxb::de::from_str(xml)?;
+    let Entry::Raw(raw) = &list.entries[0];
+    assert!(raw
+        .as_str()
+        .starts_with(r#"<e:entry xsi:type="e:Raw" a="1">"#));
+    assert!(raw.as_str().ends_with("</e:entry>"));
+    Ok(())
+}
+
+#[test]
+fn test_optional_and_repeated() -> anyhow::Result<()> {
+    let xml = r#"<batch><doc a="1"/><doc>text <b>bold</b></doc></batch>"#;
+    let batch: Batch = raxb::de::from_str(xml)?;
+    assert_eq!(
+        batch,
+        Batch {
+            docs: vec![
+                RawXml::new(r#"<doc a="1"/>"#),
+                RawXml::new("<doc>text <b>bold</b></doc>"),
+            ],
+            extra: None,
+        }
+    );
+    assert_eq!(raxb::ser::to_string(&batch)?, xml);
+    Ok(())
+}
+
+#[test]
+fn test_serialize_with_attributes() -> anyhow::Result<()> {
+    let raw = RawXml::new(r#"<doc a="1">text</doc>"#);
+    let mut writer = raxb::quick_xml::Writer::new(Vec::new());
+    raw.xml_serialize_with_attributes("ignored", &[("a", "2"), ("b", "x & y")], &mut writer)?;
+    assert_eq!(
+        String::from_utf8(writer.into_inner())?,
+        r#"<doc b="x &amp; y" a="1">text</doc>"#
+    );
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use raxb::{XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(simple, rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Status {
+    InProgress,
+    #[raxb(value = "DONE")]
+    Completed,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(rename_all = "PascalCase")]
+pub enum Payment {
+    CreditCard(String),
+    BankTransfer,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"purchaseOrder", rename_all = "camelCase")]
+pub struct PurchaseOrder {
+    #[raxb(ty = "attr")]
+    pub order_id: String,
+    #[raxb(ty = "attr")]
+    pub status: Status,
+    #[raxb(ty = "child")]
+    pub delivery_date: String,
+    #[raxb(ty = "child")]
+    pub line_item: Vec<u32>,
+    #[raxb(ty = "sfc", default)]
+    pub is_gift: bool,
+    #[raxb(ty = "child")]
+    pub payment: Payment,
+    #[raxb(name = b"NOTE", ty = "child")]
+    pub customer_note: Option<String>,
+    #[raxb(ty = "child")]
+    pub r#type: String,
+}
+
+#[test]
+fn test_rename_all() -> anyhow::Result<()> {
+    let order = PurchaseOrder {
+        order_id: "1".to_string(),
+        status: Status::InProgress,
+        delivery_date: "2024-02-01".to_string(),
+        line_item: vec![1, 2],
+        is_gift: true,
+        payment: Payment::CreditCard("1234".to_string()),
+        customer_note: Some("fragile".to_string()),
+        r#type: "express".to_string(),
+    };
+    let xml = raxb::ser::to_string(&order)?;
+    assert_eq!(
+        xml,
+        r#"<purchaseOrder orderId="1" status="IN_PROGRESS"><deliveryDate>2024-02-01</deliveryDate><lineItem>1</lineItem><lineItem>2</lineItem><isGift/><payment><CreditCard>1234</CreditCard></payment><NOTE>fragile</NOTE><type>express</type></purchaseOrder>"#
+    );
+    assert_eq!(raxb::de::from_str::<PurchaseOrder>(&xml)?, order);
+
+    let xml = r#"<purchaseOrder orderId="2" status="DONE"><deliveryDate/><payment><BankTransfer/></payment><type/></purchaseOrder>"#;
+    let order: PurchaseOrder = raxb::de::from_str(xml)?;
+    assert_eq!(order.status, Status::Completed);
+    assert_eq!(order.payment, Payment::BankTransfer);
+    assert!(!order.is_gift);
+    Ok(())
+}
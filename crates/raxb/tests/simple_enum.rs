@@ -0,0 +1,150 @@
+use raxb::{de::XmlDeserializeError, XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(simple)]
+pub enum Status {
+    #[raxb(value = "ACTIVE")]
+    Active,
+    #[raxb(value = "INACTIVE")]
+    Inactive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(simple)]
+pub enum Currency {
+    EUR,
+    USD,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Amount {
+    #[raxb(name = b"currency", ty = "attr")]
+    pub currency: Currency,
+    #[raxb(ty = "text")]
+    pub content: f64,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Flag {
+    #[raxb(ty = "text")]
+    pub status: Status,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"account")]
+pub struct Account {
+    #[raxb(name = b"status", ty = "attr")]
+    pub status: Status,
+    #[raxb(name = b"previous", ty = "attr")]
+    pub previous: Option<Status>,
+    #[raxb(name = b"state", ty = "child")]
+    pub state: Status,
+    #[raxb(name = b"history", ty = "child")]
+    pub history: Vec<Status>,
+    #[raxb(name = b"flag", ty = "child")]
+    pub flag: Flag,
+    #[raxb(name = b"amount", ty = "child")]
+    pub amount: Amount,
+}
+
+#[test]
+fn test_simple_enum() -> anyhow::Result<()> {
+    let xml = r#"<account status="ACTIVE"><state> INACTIVE </state><history>ACTIVE</history><history>INACTIVE</history><flag>INACTIVE</flag><amount currency="USD">1.5</amount></account>"#;
+    let account: Account = raxb::de::from_str(xml)?;
+    assert_eq!(
+        account,
+        Account {
+            status: Status::Active,
+            previous: None,
+            state: Status::Inactive,
+            history: vec![Status::Active, Status::Inactive],
+            flag: Flag {
+                status: Status::Inactive
+            },
+            amount: Amount {
+                currency: Currency::USD,
+                content: 1.5,
+            },
+        }
+    );
+
+    let account = Account {
+        previous: Some(Status::Inactive),
+        ..account
+    };
+    let xml = raxb::ser::to_string(&account)?;
+    assert_eq!(
+        xml,
+        r#"<account status="ACTIVE" previous="INACTIVE"><state>INACTIVE</state><history>ACTIVE</history><history>INACTIVE</history><flag>INACTIVE</flag><amount currency="USD">1.5</amount></account>"#
+    );
+    assert_eq!(raxb::de::from_str::<Account>(&xml)?, account);
+    Ok(())
+}
+
+#[test]
+fn test_simple_enum_str() {
+    assert_eq!("EUR".parse::<Currency>().unwrap(), Currency::EUR);
+    assert_eq!(Status::Inactive.to_string(), "INACTIVE");
+}
+
+#[test]
+fn test_unknown_variant() {
+    let xml = r#"<account status="PAUSED"><state>ACTIVE</state><flag>ACTIVE</flag><amount currency="EUR">1</amount></account>"#;
+    let err = raxb::de::from_str::<Account>(xml).unwrap_err();
+    assert!(matches!(&err, XmlDeserializeError::UnknownVariant(value, _) if value == "PAUSED"));
+    assert_eq!(
+        err.to_string(),
+        "unknown variant 'PAUSED', expected one of ACTIVE, INACTIVE"
+    );
+
+    let xml = r#"<account status="ACTIVE"><state>ACTIVE</state><flag>ACTIVE</flag><amount currency="CHF">1</amount></account>"#;
+    let err = raxb::de::from_str::<Account>(xml).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unknown variant 'CHF', expected one of EUR, USD"
+    );
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Version(u32, u32);
+
+impl std::str::FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (major, minor) = s
+            .split_once('.')
+            .ok_or_else(|| format!("invalid version '{s}'"))?;
+        Ok(Self(
+            major
+                .parse()
+                .map_err(|_| format!("invalid major '{major}'"))?,
+            minor
+                .parse()
+                .map_err(|_| format!("invalid minor '{minor}'"))?,
+        ))
+    }
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+#[raxb(root = b"schema")]
+pub struct Schema {
+    #[raxb(name = b"version", ty = "attr")]
+    pub version: Version,
+    #[raxb(name = b"status", ty = "attr")]
+    pub status: Option<Status>,
+}
+
+#[test]
+fn test_custom_from_str_attribute() -> anyhow::Result<()> {
+    let schema: Schema = raxb::de::from_str(r#"<schema version="1.2" status="ACTIVE"/>"#)?;
+    assert_eq!(schema.version, Version(1, 2));
+    // other `FromStr` types than simple enums fall back to their default value
+    let schema: Schema = raxb::de::from_str(r#"<schema version="x"/>"#)?;
+    assert_eq!(schema.version, Version::default());
+    assert!(matches!(
+        raxb::de::from_str::<Schema>(r#"<schema version="1.2" status="x"/>"#),
+        Err(XmlDeserializeError::UnknownVariant(..))
+    ));
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use std::{collections::HashMap, marker::PhantomData};
+
+use raxb::{XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Invoice;
+
+fn default_version() -> u32 {
+    1
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"document")]
+pub struct Document<T> {
+    #[raxb(name = b"id", ty = "attr")]
+    pub id: String,
+    #[raxb(name = b"title", ty = "child")]
+    pub title: String,
+    #[raxb(skip)]
+    pub cache: HashMap<String, String>,
+    #[raxb(skip, default = "default_version")]
+    pub version: u32,
+    #[raxb(skip)]
+    pub kind: PhantomData<T>,
+}
+
+#[test]
+fn test_skip() -> anyhow::Result<()> {
+    let mut document = Document::<Invoice> {
+        id: "1".to_string(),
+        title: "Invoice".to_string(),
+        cache: HashMap::new(),
+        version: 7,
+        kind: PhantomData,
+    };
+    document.cache.insert("k".to_string(), "v".to_string());
+    let xml = raxb::ser::to_string(&document)?;
+    assert_eq!(xml, r#"<document id="1"><title>Invoice</title></document>"#);
+
+    let deserialized: Document<Invoice> = raxb::de::from_str(&xml)?;
+    assert_eq!(
+        deserialized,
+        Document {
+            cache: HashMap::new(),
+            version: 1,
+            ..document
+        }
+    );
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"account")]
+pub struct Account {
+    #[raxb(name = b"name", ty = "attr")]
+    pub name: String,
+    /// Written for the partner, but never read back.
+    #[raxb(name = b"display", ty = "child", skip_deserializing)]
+    pub display: String,
+    /// Read from the partner, but never written.
+    #[raxb(name = b"password", ty = "child", skip_serializing)]
+    pub password: Option<String>,
+}
+
+#[test]
+fn test_skip_serializing_and_deserializing() -> anyhow::Result<()> {
+    let xml = r#"<account name="a"><display>A</display><password>secret</password></account>"#;
+    let account: Account = raxb::de::from_str(xml)?;
+    assert_eq!(
+        account,
+        Account {
+            name: "a".to_string(),
+            display: String::new(),
+            password: Some("secret".to_string()),
+        }
+    );
+
+    let account = Account {
+        name: "b".to_string(),
+        display: "B".to_string(),
+        password: Some("secret".to_string()),
+    };
+    assert_eq!(
+        raxb::ser::to_string(&account)?,
+        r#"<account name="b"><display>B</display></account>"#
+    );
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"settings", default)]
+pub struct Settings {
+    #[raxb(name = b"level", ty = "attr")]
+    pub level: u8,
+    #[raxb(skip)]
+    pub loaded: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            loaded: true,
+        }
+    }
+}
+
+#[test]
+fn test_skip_with_container_default() -> anyhow::Result<()> {
+    let settings: Settings = raxb::de::from_str(r#"<settings level="3"/>"#)?;
+    assert_eq!(
+        settings,
+        Settings {
+            level: 3,
+            loaded: true,
+        }
+    );
+    Ok(())
+}
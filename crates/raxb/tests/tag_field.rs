@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+
+use raxb::{ser::XmlSerialize as _, value::QName, XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"payment")]
+pub struct Payment {
+    #[raxb(name = b"total", ty = "child")]
+    pub total: Amount,
+    #[raxb(key_by_name, ty = "child")]
+    pub fees: BTreeMap<String, Fee>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Amount {
+    #[raxb(ty = "tag")]
+    pub currency: String,
+    #[raxb(ty = "text")]
+    pub amount: f64,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(simple)]
+pub enum Currency {
+    #[raxb(value = "EUR")]
+    Eur,
+    #[raxb(value = "USD")]
+    Usd,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Fee {
+    #[raxb(ty = "tag")]
+    pub currency: Currency,
+    #[raxb(name = b"reason", ty = "attr")]
+    pub reason: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub enum Entry {
+    #[raxb(name = b"known")]
+    Known(Amount),
+    #[raxb(other)]
+    Other(Element),
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Element {
+    #[raxb(ty = "tag")]
+    pub name: QName,
+    #[raxb(ty = "text")]
+    pub content: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"entries")]
+pub struct Entries {
+    #[raxb(name = b"entry", ty = "child")]
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"extensions")]
+pub struct Extensions {
+    #[raxb(key_by_name, ns = b"x", ty = "child")]
+    pub items: BTreeMap<String, Element>,
+}
+
+#[test]
+fn test_tag_field() -> anyhow::Result<()> {
+    let xml =
+        r#"<payment><total>10</total><USD reason="transfer"/><EUR reason="exchange"/></payment>"#;
+    let payment: Payment = raxb::de::from_str(xml)?;
+    assert_eq!(
+        payment,
+        Payment {
+            total: Amount {
+                currency: "total".to_string(),
+                amount: 10.0,
+            },
+            fees: BTreeMap::from([
+                (
+                    "EUR".to_string(),
+                    Fee {
+                        currency: Currency::Eur,
+                        reason: "exchange".to_string(),
+                    }
+                ),
+                (
+                    "USD".to_string(),
+                    Fee {
+                        currency: Currency::Usd,
+                        reason: "transfer".to_string(),
+                    }
+                ),
+            ]),
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_serialize_tag_field() -> anyhow::Result<()> {
+    let amount = Amount {
+        currency: "EUR".to_string(),
+        amount: 10.0,
+    };
+    let mut writer = raxb::quick_xml::Writer::new(Vec::new());
+    amount.xml_serialize("ignored", &mut writer)?;
+    assert_eq!(String::from_utf8(writer.into_inner())?, "<EUR>10</EUR>");
+
+    let amount = Amount {
+        currency: String::new(),
+        amount: 10.0,
+    };
+    let mut writer = raxb::quick_xml::Writer::new(Vec::new());
+    amount.xml_serialize("total", &mut writer)?;
+    assert_eq!(String::from_utf8(writer.into_inner())?, "<total>10</total>");
+    Ok(())
+}
+
+#[test]
+fn test_qname_tag_field() -> anyhow::Result<()> {
+    let xml = r#"<entries><entry><known>1</known></entry><entry><note>a</note></entry></entries>"#;
+    let entries: Entries = raxb::de::from_str(xml)?;
+    assert_eq!(
+        entries.entries,
+        vec![
+            Entry::Known(Amount {
+                currency: "known".to_string(),
+                amount: 1.0,
+            }),
+            Entry::Other(Element {
+                name: QName::new("note"),
+                content: "a".to_string(),
+            }),
+        ]
+    );
+    assert_eq!(
+        raxb::ser::to_string(&Entries {
+            entries: vec![Entry::Other(Element {
+                name: QName::new("note"),
+                content: "a".to_string(),
+            })],
+        })?,
+        r#"<entries><entry><note>a</note></entry></entries>"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_prefixed_qname_tag_field() -> anyhow::Result<()> {
+    let xml = r#"<extensions xmlns:x="urn:x"><x:note>b</x:note><plain>c</plain></extensions>"#;
+    let extensions: Extensions = raxb::de::from_str(xml)?;
+    let element = Element {
+        name: QName::with_prefix("x", "note").with_namespace("urn:x"),
+        content: "b".to_string(),
+    };
+    assert_eq!(
+        extensions.items,
+        BTreeMap::from([("note".to_string(), element)])
+    );
+    let serialized = raxb::ser::to_string(&extensions)?;
+    assert_eq!(
+        serialized,
+        r#"<extensions><x:note xmlns:x="urn:x">b</x:note></extensions>"#
+    );
+    assert_eq!(raxb::de::from_str::<Extensions>(&serialized)?, extensions);
+    let element = Element {
+        name: QName::new("note").with_namespace("urn:y"),
+        content: "c".to_string(),
+    };
+    let mut writer = raxb::quick_xml::Writer::new(Vec::new());
+    element.xml_serialize("ignored", &mut writer)?;
+    assert_eq!(
+        String::from_utf8(writer.into_inner())?,
+        r#"<note xmlns="urn:y">c</note>"#
+    );
+    Ok(())
+}
@@ -0,0 +1,9 @@
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    #[cfg(feature = "regex")]
+    t.compile_fail("tests/ui/regex/*.rs");
+    #[cfg(not(feature = "regex"))]
+    t.compile_fail("tests/ui/no_regex/*.rs");
+}
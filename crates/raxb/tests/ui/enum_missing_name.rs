@@ -0,0 +1,10 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+pub enum Payment {
+    #[raxb(name = b"card")]
+    Card(String),
+    Cash,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+#[raxb(root = b"measure")]
+pub struct Measure<T> {
+    #[raxb(name = b"amount", ty = "attr")]
+    pub amount: Option<T>,
+}
+
+fn main() {}
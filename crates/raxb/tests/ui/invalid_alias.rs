@@ -0,0 +1,9 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+pub struct Amount {
+    #[raxb(alias = b"value", ty = "text")]
+    pub amount: f64,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+#[raxb(root = b"order", rename_all = "camel")]
+pub struct Order {
+    #[raxb(ty = "attr")]
+    pub order_id: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+pub struct Amount {
+    #[raxb(ty = "tag")]
+    pub currency: Option<String>,
+}
+
+fn main() {}
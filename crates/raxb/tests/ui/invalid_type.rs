@@ -0,0 +1,10 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+#[raxb(root = b"order")]
+pub struct Order {
+    #[raxb(name = b"id", ty = "element")]
+    pub id: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+#[raxb(root = b"a")]
+struct A {
+    #[raxb(name = b"count", ty = "child", whitespace = "collapse")]
+    count: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+#[raxb(root = b"order")]
+pub struct Order {
+    #[raxb(name = b"date", ty = "attr", with = "parse date")]
+    pub date: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+pub struct Order {
+    #[raxb(name = b"Line", wrapper = b"Lines", ty = "child")]
+    pub line: Option<String>,
+}
+
+fn main() {}
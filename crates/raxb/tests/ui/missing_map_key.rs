@@ -0,0 +1,11 @@
+use std::collections::BTreeMap;
+
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+pub struct Config {
+    #[raxb(name = b"param", ty = "child")]
+    pub params: BTreeMap<String, String>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+#[raxb(root = b"order")]
+pub struct Order {
+    #[raxb(name = b"id", ty = "attr")]
+    pub id: String,
+    pub cache: Vec<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use raxb::XmlSerialize;
+
+#[derive(XmlSerialize)]
+#[raxb(root = b"order")]
+pub struct Order {
+    #[raxb(name = "id", ty = "attr")]
+    pub id: String,
+}
+
+fn main() {}
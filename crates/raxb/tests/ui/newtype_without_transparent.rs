@@ -0,0 +1,6 @@
+use raxb::XmlSerialize;
+
+#[derive(XmlSerialize)]
+pub struct OrderId(String);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+#[raxb(root = b"account")]
+pub struct Account {
+    #[raxb(name = b"iban", ty = "attr", pattern = "[A-Z]{2}")]
+    pub iban: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+pub struct Note {
+    #[raxb(ty = "text")]
+    pub content: String,
+    #[raxb(name = b"author", ty = "child")]
+    pub author: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+#[raxb(transparent)]
+pub struct OrderId {
+    #[raxb(ty = "text")]
+    pub id: String,
+}
+
+fn main() {}
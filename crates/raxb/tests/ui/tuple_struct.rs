@@ -0,0 +1,6 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+pub struct Point(u32, u32);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+#[raxb(root = b"order")]
+pub struct Order {
+    #[raxb(nmae = b"id", ty = "attr")]
+    pub id: String,
+}
+
+fn main() {}
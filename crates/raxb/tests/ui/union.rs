@@ -0,0 +1,9 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+pub union Value {
+    pub int: u32,
+    pub float: f32,
+}
+
+fn main() {}
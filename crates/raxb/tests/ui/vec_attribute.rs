@@ -0,0 +1,10 @@
+use raxb::XmlSerialize;
+
+#[derive(XmlSerialize)]
+#[raxb(root = b"order")]
+pub struct Order {
+    #[raxb(name = b"tags", ty = "attr")]
+    pub tags: Vec<String>,
+}
+
+fn main() {}
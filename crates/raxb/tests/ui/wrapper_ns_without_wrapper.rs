@@ -0,0 +1,9 @@
+use raxb::XmlDeserialize;
+
+#[derive(XmlDeserialize)]
+pub struct Order {
+    #[raxb(name = b"Line", wrapper_ns = b"o", ty = "child")]
+    pub lines: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use raxb::XmlSerialize;
+
+#[derive(XmlSerialize)]
+#[raxb(tag = "xsi:type", tns(b"tns", b"urn:shapes"))]
+pub enum Shape {
+    #[raxb(name = b"Circle", ns = b"other")]
+    Circle,
+}
+
+fn main() {}
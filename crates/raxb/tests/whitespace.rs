@@ -0,0 +1,104 @@
+use raxb::{de::DeserializeOptions, XmlDeserialize};
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+pub struct Code {
+    #[raxb(name = b"lang", ty = "attr", whitespace = "collapse")]
+    pub lang: String,
+    #[raxb(ty = "text", whitespace = "preserve")]
+    pub content: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+pub struct Paragraph {
+    #[raxb(ty = "text")]
+    pub content: String,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+#[raxb(root = b"doc")]
+pub struct Doc {
+    #[raxb(name = b"title", ty = "child")]
+    pub title: String,
+    #[raxb(name = b"fixed", ty = "child", whitespace = "preserve")]
+    pub fixed: String,
+    #[raxb(name = b"address", ty = "child", whitespace = "replace")]
+    pub address: String,
+    #[raxb(name = b"name", ty = "child", whitespace = "collapse")]
+    pub name: String,
+    #[raxb(name = b"code", ty = "child")]
+    pub code: Code,
+    #[raxb(name = b"p", ty = "child")]
+    pub paragraphs: Vec<Paragraph>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+#[raxb(root = b"section")]
+pub struct Section {
+    #[raxb(name = b"title", ty = "child")]
+    pub title: String,
+    #[raxb(name = b"body", ty = "child")]
+    pub body: Body,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize)]
+pub struct Body {
+    #[raxb(name = b"pre", ty = "child")]
+    pub pre: String,
+    #[raxb(name = b"p", ty = "child")]
+    pub paragraph: Paragraph,
+}
+
+const XML: &str = "<doc>
+    <title>  The Title  </title>
+    <fixed>  42  </fixed>
+    <address>Main St.\n\t1</address>
+    <name>  John \n  Doe </name>
+    <code lang=\" rust  lang \">
+fn main() {}
+</code>
+    <p>  trimmed  </p>
+    <p xml:space=\"preserve\">  preserved  </p>
+</doc>";
+
+#[test]
+fn test_whitespace_facets() -> anyhow::Result<()> {
+    let doc: Doc = raxb::de::from_str(XML)?;
+    assert_eq!(doc.title, "The Title");
+    assert_eq!(doc.fixed, "  42  ");
+    assert_eq!(doc.address, "Main St.  1");
+    assert_eq!(doc.name, "John Doe");
+    assert_eq!(doc.code.lang, "rust lang");
+    assert_eq!(doc.code.content, "\nfn main() {}\n");
+    assert_eq!(doc.paragraphs[0].content, "trimmed");
+    assert_eq!(doc.paragraphs[1].content, "  preserved  ");
+    Ok(())
+}
+
+#[test]
+fn test_disable_global_trimming() -> anyhow::Result<()> {
+    let options = DeserializeOptions {
+        trim_text: false,
+        ..Default::default()
+    };
+    let doc: Doc = raxb::de::from_str_with_options(XML, &options)?;
+    assert_eq!(doc.title, "  The Title  ");
+    assert_eq!(doc.name, "John Doe");
+    assert_eq!(doc.paragraphs[0].content, "  trimmed  ");
+    Ok(())
+}
+
+#[test]
+fn test_xml_space_default_restores_trimming() -> anyhow::Result<()> {
+    let xml = r#"<section xml:space="preserve"><title> kept </title><body><pre> kept </pre><p xml:space="default"> trimmed </p></body></section>"#;
+    let section: Section = raxb::de::from_str(xml)?;
+    assert_eq!(section.title, " kept ");
+    assert_eq!(section.body.pre, " kept ");
+    assert_eq!(section.body.paragraph.content, "trimmed");
+    let options = DeserializeOptions {
+        trim_text: false,
+        ..Default::default()
+    };
+    let section: Section = raxb::de::from_str_with_options(xml, &options)?;
+    assert_eq!(section.body.paragraph.content, " trimmed ");
+    Ok(())
+}
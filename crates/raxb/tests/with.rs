@@ -0,0 +1,190 @@
+use raxb::{de::XmlDeserializeError, XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+/// Dates in the `DD.MM.YYYY` format of a partner.
+mod partner_date {
+    use super::Date;
+
+    pub fn deserialize(value: &str) -> Result<Date, String> {
+        let mut parts = value.split('.').map(str::parse::<u16>);
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Ok(day)), Some(Ok(month)), Some(Ok(year)), None) => Ok(Date {
+                year,
+                month: month as u8,
+                day: day as u8,
+            }),
+            _ => Err(format!("invalid date '{value}'")),
+        }
+    }
+
+    pub fn serialize(value: &Date) -> Result<String, String> {
+        Ok(format!(
+            "{:02}.{:02}.{:04}",
+            value.day, value.month, value.year
+        ))
+    }
+}
+
+fn parse_list(value: &str) -> Result<Vec<String>, std::convert::Infallible> {
+    Ok(value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+fn write_list(value: &[String]) -> Result<String, std::convert::Infallible> {
+    Ok(value.join(","))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Unit {
+    Piece,
+    Kilogram,
+}
+
+fn parse_unit(value: &str) -> Result<Unit, String> {
+    match value {
+        "C62" => Ok(Unit::Piece),
+        "KGM" => Ok(Unit::Kilogram),
+        _ => Err(format!("unknown unit code '{value}'")),
+    }
+}
+
+fn write_unit(value: &Unit) -> Result<String, String> {
+    Ok(match value {
+        Unit::Piece => "C62",
+        Unit::Kilogram => "KGM",
+    }
+    .to_string())
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Quantity {
+    #[raxb(
+        name = b"unit",
+        ty = "attr",
+        deserialize_with = "parse_unit",
+        serialize_with = "write_unit"
+    )]
+    pub unit: Unit,
+    #[raxb(ty = "text")]
+    pub amount: u32,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Stamp {
+    #[raxb(ty = "text", with = "partner_date")]
+    pub date: Date,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"order")]
+pub struct Order {
+    #[raxb(name = b"date", ty = "attr", with = "partner_date")]
+    pub date: Date,
+    #[raxb(name = b"due", ty = "attr", with = "partner_date")]
+    pub due: Option<Date>,
+    #[raxb(
+        name = b"tags",
+        ty = "attr",
+        deserialize_with = "parse_list",
+        serialize_with = "write_list"
+    )]
+    pub tags: Option<Vec<String>>,
+    #[raxb(name = b"delivery", ty = "child", with = "partner_date")]
+    pub deliveries: Vec<Date>,
+    #[raxb(name = b"shipped", ty = "child", with = "partner_date")]
+    pub shipped: Option<Date>,
+    #[raxb(
+        name = b"unit",
+        ty = "child",
+        deserialize_with = "parse_unit",
+        serialize_with = "write_unit"
+    )]
+    pub unit: Unit,
+    #[raxb(name = b"quantity", ty = "child")]
+    pub quantity: Quantity,
+    #[raxb(name = b"stamp", ty = "child")]
+    pub stamp: Stamp,
+}
+
+fn date(year: u16, month: u8, day: u8) -> Date {
+    Date { year, month, day }
+}
+
+#[test]
+fn test_deserialize_with() -> anyhow::Result<()> {
+    let xml = r#"<order date="01.02.2024" tags="a, b,c">
+        <delivery>03.02.2024</delivery>
+        <delivery> 04.02.2024 </delivery>
+        <unit>KGM</unit>
+        <quantity unit="C62">5</quantity>
+        <stamp>05.02.2024</stamp>
+    </order>"#;
+    let order: Order = raxb::de::from_str(xml)?;
+    assert_eq!(
+        order,
+        Order {
+            date: date(2024, 2, 1),
+            due: None,
+            tags: Some(vec!["a".to_string(), "b".to_string(), "c".to_string()]),
+            deliveries: vec![date(2024, 2, 3), date(2024, 2, 4)],
+            shipped: None,
+            unit: Unit::Kilogram,
+            quantity: Quantity {
+                unit: Unit::Piece,
+                amount: 5,
+            },
+            stamp: Stamp {
+                date: date(2024, 2, 5),
+            },
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_serialize_with() -> anyhow::Result<()> {
+    let order = Order {
+        date: date(2024, 2, 1),
+        due: Some(date(2024, 3, 1)),
+        tags: Some(vec!["a".to_string(), "b".to_string()]),
+        deliveries: vec![date(2024, 2, 3), date(2024, 2, 4)],
+        shipped: Some(date(2024, 2, 2)),
+        unit: Unit::Piece,
+        quantity: Quantity {
+            unit: Unit::Kilogram,
+            amount: 7,
+        },
+        stamp: Stamp {
+            date: date(2024, 2, 5),
+        },
+    };
+    let xml = raxb::ser::to_string(&order)?;
+    assert_eq!(
+        xml,
+        r#"<order date="01.02.2024" due="01.03.2024" tags="a,b"><delivery>03.02.2024</delivery><delivery>04.02.2024</delivery><shipped>02.02.2024</shipped><unit>C62</unit><quantity unit="KGM">7</quantity><stamp>05.02.2024</stamp></order>"#
+    );
+    let deserialized: Order = raxb::de::from_str(&xml)?;
+    assert_eq!(deserialized, order);
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_with_error() {
+    let xml = r#"<order date="2024-02-01"><unit>KGM</unit><quantity unit="C62">1</quantity><stamp>05.02.2024</stamp></order>"#;
+    let err = raxb::de::from_str::<Order>(xml).unwrap_err();
+    assert!(matches!(&err, XmlDeserializeError::Custom(msg) if msg == "invalid date '2024-02-01'"));
+
+    let xml = r#"<order date="01.02.2024"><unit>XXX</unit><quantity unit="C62">1</quantity><stamp>05.02.2024</stamp></order>"#;
+    let err = raxb::de::from_str::<Order>(xml).unwrap_err();
+    assert_eq!(err.to_string(), "unknown unit code 'XXX'");
+}
@@ -0,0 +1,104 @@
+use raxb::{value::ConstStr, XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"Order")]
+pub struct Order {
+    #[raxb(name = b"Id", ty = "attr")]
+    pub id: u32,
+    #[raxb(name = b"Line", wrapper = b"Lines", ty = "child")]
+    pub lines: Vec<Line>,
+    #[raxb(name = b"Tag", wrapper = b"Tags", skip_empty_wrapper, ty = "child")]
+    pub tags: Vec<String>,
+    #[raxb(name = b"Express", wrapper = b"Flags", skip_empty_wrapper, ty = "sfc")]
+    pub flags: Vec<bool>,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Line {
+    #[raxb(name = b"Sku", ty = "attr")]
+    pub sku: String,
+    #[raxb(name = b"Quantity", ty = "child")]
+    pub quantity: u32,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"Catalog")]
+#[raxb(tns(b"c", b"https://catalog.example.org/"))]
+pub struct Catalog {
+    #[raxb(
+        default,
+        ns = b"xmlns",
+        name = b"c",
+        ty = "attr",
+        value = "https://catalog.example.org/"
+    )]
+    _xmlns: ConstStr,
+    #[raxb(name = b"Item", wrapper = b"Items", wrapper_ns = b"c", ty = "child")]
+    pub items: Vec<String>,
+}
+
+fn line(sku: &str, quantity: u32) -> Line {
+    Line {
+        sku: sku.to_string(),
+        quantity,
+    }
+}
+
+#[test]
+fn test_wrapper() -> anyhow::Result<()> {
+    let order = Order {
+        id: 1,
+        lines: vec![line("a", 2), line("b", 3)],
+        tags: vec!["gift".to_string()],
+        flags: vec![true],
+    };
+    let xml = raxb::ser::to_string(&order)?;
+    assert_eq!(
+        xml,
+        r#"<Order Id="1"><Lines><Line Sku="a"><Quantity>2</Quantity></Line><Line Sku="b"><Quantity>3</Quantity></Line></Lines><Tags><Tag>gift</Tag></Tags><Flags><Express/></Flags></Order>"#
+    );
+    assert_eq!(raxb::de::from_str::<Order>(&xml)?, order);
+    Ok(())
+}
+
+#[test]
+fn test_empty_wrapper() -> anyhow::Result<()> {
+    let order = Order {
+        id: 1,
+        lines: vec![],
+        tags: vec![],
+        flags: vec![],
+    };
+    let xml = raxb::ser::to_string(&order)?;
+    assert_eq!(xml, r#"<Order Id="1"><Lines/></Order>"#);
+    assert_eq!(raxb::de::from_str::<Order>(&xml)?, order);
+    assert_eq!(
+        raxb::de::from_str::<Order>(r#"<Order Id="1"><Lines></Lines><Tags/></Order>"#)?,
+        order
+    );
+    Ok(())
+}
+
+#[test]
+fn test_wrapper_skips_unknown_elements() -> anyhow::Result<()> {
+    let xml = r#"<Order Id="1"><Line Sku="x"><Quantity>9</Quantity></Line><Lines><Note><Line Sku="y"/></Note><Line Sku="a"><Quantity>2</Quantity></Line></Lines><Tags><Tag>a</Tag><Tag>b</Tag></Tags></Order>"#;
+    let order: Order = raxb::de::from_str(xml)?;
+    assert_eq!(order.lines, vec![line("a", 2)]);
+    assert_eq!(order.tags, vec!["a".to_string(), "b".to_string()]);
+    Ok(())
+}
+
+#[test]
+fn test_wrapper_ns() -> anyhow::Result<()> {
+    let catalog = Catalog {
+        _xmlns: ConstStr::default(),
+        items: vec!["a".to_string(), "b".to_string()],
+    };
+    let xml = raxb::ser::to_string(&catalog)?;
+    assert_eq!(
+        xml,
+        r#"<c:Catalog xmlns:c="https://catalog.example.org/"><c:Items><Item>a</Item><Item>b</Item></c:Items></c:Catalog>"#
+    );
+    assert_eq!(raxb::de::from_str::<Catalog>(&xml)?.items, catalog.items);
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use raxb::{de::XmlDeserializeError, value::Nillable, XmlDeserialize, XmlSerialize};
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Circle {
+    #[raxb(name = b"radius", ty = "attr")]
+    pub radius: f64,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+pub struct Square {
+    #[raxb(name = b"side", ty = "child")]
+    pub side: f64,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(tag = "xsi:type", tns(b"tns", b"urn:shapes"))]
+pub enum Shape {
+    #[raxb(name = b"Circle")]
+    Circle(Circle),
+    #[raxb(name = b"Square")]
+    Square(Square),
+    #[raxb(name = b"Point")]
+    Point,
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"drawing")]
+pub struct Drawing {
+    #[raxb(name = b"shape", ty = "child")]
+    pub shapes: Vec<Shape>,
+}
+
+#[test]
+fn test_deserialize_xsi_type() -> anyhow::Result<()> {
+    let xml = r#"<drawing xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:s="urn:shapes">
+        <shape xsi:type="s:Circle" radius="2.5"/>
+        <shape xsi:type="s:Square"><side>3</side></shape>
+        <shape xmlns:t="urn:shapes" xsi:type="t:Point"></shape>
+    </drawing>"#;
+    let drawing: Drawing = raxb::de::from_str(xml)?;
+    assert_eq!(
+        drawing.shapes,
+        vec![
+            Shape::Circle(Circle { radius: 2.5 }),
+            Shape::Square(Square { side: 3.0 }),
+            Shape::Point,
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_unknown_xsi_type() -> anyhow::Result<()> {
+    let xml = r#"<drawing xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:s="urn:other">
+        <shape xsi:type="s:Circle" radius="2.5"/>
+    </drawing>"#;
+    let result = raxb::de::from_str::<Drawing>(xml);
+    assert!(
+        matches!(result, Err(XmlDeserializeError::UnknownVariant(name, _)) if name == "Circle")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_serialize_xsi_type() -> anyhow::Result<()> {
+    let drawing = Drawing {
+        shapes: vec![
+            Shape::Circle(Circle { radius: 1.5 }),
+            Shape::Square(Square { side: 2.0 }),
+            Shape::Point,
+        ],
+    };
+    let xml = raxb::ser::to_string(&drawing)?;
+    assert_eq!(
+        xml,
+        r#"<drawing><shape radius="1.5" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:tns="urn:shapes" xsi:type="tns:Circle"/><shape xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:tns="urn:shapes" xsi:type="tns:Square"><side>2</side></shape><shape xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:tns="urn:shapes" xsi:type="tns:Point"/></drawing>"#
+    );
+    let de: Drawing = raxb::de::from_str(&xml)?;
+    assert_eq!(de, drawing);
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(tag = "xsi:type", tns(b"tns", b"urn:values"))]
+pub enum Value {
+    #[raxb(name = b"Str")]
+    Str(String),
+    #[raxb(name = b"Num")]
+    Num(u32),
+    #[raxb(name = b"Opt")]
+    Opt(Nillable<u32>),
+}
+
+#[derive(Debug, PartialEq, XmlDeserialize, XmlSerialize)]
+#[raxb(root = b"values")]
+pub struct Values {
+    #[raxb(name = b"v", ty = "child")]
+    pub values: Vec<Value>,
+}
+
+#[test]
+fn test_xsi_type_built_in_variants() -> anyhow::Result<()> {
+    let values = Values {
+        values: vec![
+            Value::Str("a".to_string()),
+            Value::Num(1),
+            Value::Opt(Nillable::Nil),
+            Value::Opt(Nillable::Value(2)),
+        ],
+    };
+    let xml = raxb::ser::to_string(&values)?;
+    assert_eq!(
+        xml,
+        r#"<values><v xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:tns="urn:values" xsi:type="tns:Str">a</v><v xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:tns="urn:values" xsi:type="tns:Num">1</v><v xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:nil="true" xmlns:tns="urn:values" xsi:type="tns:Opt"/><v xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:tns="urn:values" xsi:type="tns:Opt">2</v></values>"#
+    );
+    assert_eq!(raxb::de::from_str::<Values>(&xml)?, values);
+    Ok(())
+}